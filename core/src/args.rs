@@ -0,0 +1,329 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Block, Ident, LitStr, Meta, Path, Token, Visibility,
+};
+
+use crate::{
+    block_on::BlockOn,
+    bounds::StrippedBound,
+    chains::ChainMapping,
+    combinators::Race,
+    delays::Delay,
+    mappings::{self, PathMapping},
+    methods::BlockingMethods,
+    sibling::{NameSpan, Sibling},
+    sinks::SinkTarget,
+    trait_pair::TraitPair,
+    wasm::WasmBindgen,
+};
+
+/// Every argument `Args` accepts, for suggesting one when an argument is misspelled.
+const KEYS: &[&str] = &[
+    "map",
+    "types",
+    "profile",
+    "chains",
+    "blocking_methods",
+    "closure",
+    "strip_bounds",
+    "strip_unpin",
+    "sink",
+    "before",
+    "after",
+    "unless_feature",
+    "when",
+    "doc_cfg",
+    "wasm_bindgen",
+    "sibling",
+    "rename",
+    "name_span",
+    "block_on",
+    "delay",
+    "trait_pair",
+    "strict",
+    "warn_leaks",
+    "macro_exprs",
+    "macro_tokens",
+    "hoist_awaits",
+    "deny_noop",
+    "vis",
+    "sync_body",
+    "race",
+];
+
+/// The arguments that add to a list, so they can be given more than once.
+const REPEATABLE: &[&str] = &[
+    "map",
+    "types",
+    "profile",
+    "chains",
+    "closure",
+    "strip_bounds",
+    "before",
+    "after",
+];
+
+/// Arguments passed to `#[remove_async_await(...)]`.
+#[derive(Default)]
+pub(crate) struct Args {
+    /// Paths to rewrite, from `map(from = to, ...)`.
+    pub(crate) mappings: Vec<PathMapping>,
+    /// Paths to rewrite in types, such as parameter and return types, from `types(from = to, ...)` and profiles.
+    pub(crate) type_mappings: Vec<PathMapping>,
+    /// Method chains whose awaited tail calls a blocking function instead, from `chains(root.method = hook, ...)`.
+    pub(crate) chains: Vec<ChainMapping>,
+    /// How awaited methods are renamed to their blocking siblings, from `blocking_methods(...)`.
+    pub(crate) blocking_methods: Option<BlockingMethods>,
+    /// Which branch of futures-lite's `race` to keep, from `race = ...`.
+    pub(crate) race: Race,
+    /// Whether awaited tasks like `task::spawn(future).await` are run inline, from `profile(async_std)` or `profile(smol)`.
+    pub(crate) inline_awaited_spawns: bool,
+    /// Generic parameters and arguments bounded by `Future` that should become closures, from `closure(...)`.
+    pub(crate) closure: Vec<Ident>,
+    /// Bounds to remove, from `strip_bounds`, `strip_bounds(...)` or `strip_unpin`.
+    pub(crate) strip_bounds: Vec<StrippedBound>,
+    /// What `Sink` bounds are rewritten to, from `sink = ...`.
+    pub(crate) sink: Option<SinkTarget>,
+    /// Attributes to expand before the transform, from `before(...)`.
+    pub(crate) before: Vec<Path>,
+    /// Attributes to re-emit last, from `after(...)`.
+    pub(crate) after: Vec<Path>,
+    /// The cfg predicate under which the async item is kept as is, from `unless_feature = "..."` or `when(...)`.
+    pub(crate) cfg: Option<TokenStream>,
+    /// Whether to show the cfg predicate of each variant on docs.rs, from `doc_cfg`.
+    pub(crate) doc_cfg: bool,
+    /// How to handle wasm-bindgen exports and `JsFuture`, from `wasm_bindgen` or `wasm_bindgen(...)`.
+    pub(crate) wasm_bindgen: Option<WasmBindgen>,
+    /// How to name the blocking copy emitted next to the async item, from `sibling`, `sibling = "..."` or
+    /// `rename = "..."`.
+    pub(crate) sibling: Option<Sibling>,
+    /// Where the names of generated items are spanned, from `name_span = "..."`.
+    pub(crate) name_span: NameSpan,
+    /// The executor that runs untouched bodies, from `block_on` or `block_on = ...`.
+    pub(crate) block_on: Option<BlockOn>,
+    /// The blocking delay that embassy-time's timers call, from `delay = ...`.
+    pub(crate) delay: Option<Delay>,
+    /// How to name and bridge the async and blocking traits, from `trait_pair` or `trait_pair(...)`.
+    pub(crate) trait_pair: Option<TraitPair>,
+    /// Whether it's an error for the transform to leave the item unchanged, from `deny_noop`.
+    pub(crate) deny_noop: bool,
+    /// Whether awaits left after the transform are errors, from `strict`.
+    pub(crate) strict: bool,
+    /// Whether functions that still use async crates or types get a warning, from `warn_leaks`.
+    pub(crate) warn_leaks: bool,
+    /// Whether the arguments of every macro are folded when they parse as expressions, from `macro_exprs`.
+    pub(crate) macro_exprs: bool,
+    /// Whether `async` and `.await` are removed from the tokens of macros whose arguments can't be folded, from
+    /// `macro_tokens`.
+    pub(crate) macro_tokens: bool,
+    /// Whether awaited expressions are bound to variables before their statement, from `hoist_awaits`.
+    pub(crate) hoist_awaits: bool,
+    /// The visibility of the blocking item, from `vis = "..."`.
+    pub(crate) vis: Option<Visibility>,
+    /// The body of the blocking function, from `sync_body = { ... }`.
+    pub(crate) sync_body: Option<Block>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args::default();
+        let mut seen = Vec::new();
+
+        while !input.is_empty() {
+            let key: Ident = input.parse()?;
+            // lists like `map(...)` can be split up, but anything else given twice would override the first one
+            if !REPEATABLE.contains(&key.to_string().as_str()) && seen.contains(&key) {
+                return Err(syn::Error::new(key.span(), "duplicate argument"));
+            }
+            seen.push(key.clone());
+            match key.to_string().as_str() {
+                "map" => {
+                    let content;
+                    parenthesized!(content in input);
+                    args.mappings
+                        .extend(Punctuated::<PathMapping, Token![,]>::parse_terminated(
+                            &content,
+                        )?);
+                }
+                "types" => {
+                    let content;
+                    parenthesized!(content in input);
+                    args.type_mappings.extend(
+                        Punctuated::<PathMapping, Token![,]>::parse_terminated(&content)?,
+                    );
+                }
+                "profile" => {
+                    let content;
+                    parenthesized!(content in input);
+                    for name in Punctuated::<Ident, Token![,]>::parse_terminated(&content)? {
+                        let profile = mappings::profile(&name)?;
+                        args.mappings.extend(profile.iter().cloned());
+                        args.type_mappings.extend(profile);
+                        // async-std's and smol's task handles give the output when they're awaited
+                        if name == "async_std" || name == "smol" {
+                            args.inline_awaited_spawns = true;
+                        }
+                    }
+                }
+                "chains" => {
+                    let content;
+                    parenthesized!(content in input);
+                    args.chains
+                        .extend(Punctuated::<ChainMapping, Token![,]>::parse_terminated(
+                            &content,
+                        )?);
+                }
+                "blocking_methods" => args.blocking_methods = Some(input.parse()?),
+                "closure" => {
+                    let content;
+                    parenthesized!(content in input);
+                    args.closure
+                        .extend(Punctuated::<Ident, Token![,]>::parse_terminated(&content)?);
+                }
+                "strip_bounds" => {
+                    if input.peek(syn::token::Paren) {
+                        let content;
+                        parenthesized!(content in input);
+                        args.strip_bounds.extend(
+                            Punctuated::<StrippedBound, Token![,]>::parse_terminated(&content)?,
+                        );
+                    } else {
+                        args.strip_bounds.extend(StrippedBound::defaults());
+                    }
+                }
+                "strip_unpin" => args.strip_bounds.push(StrippedBound::unpin()),
+                "sink" => {
+                    input.parse::<Token![=]>()?;
+                    args.sink = Some(input.parse()?);
+                }
+                "before" => {
+                    let content;
+                    parenthesized!(content in input);
+                    args.before
+                        .extend(Punctuated::<Path, Token![,]>::parse_terminated(&content)?);
+                }
+                "after" => {
+                    let content;
+                    parenthesized!(content in input);
+                    args.after
+                        .extend(Punctuated::<Path, Token![,]>::parse_terminated(&content)?);
+                }
+                "unless_feature" | "when" if args.cfg.is_some() => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "only one of `unless_feature` and `when` can be used",
+                    ))
+                }
+                "unless_feature" => {
+                    input.parse::<Token![=]>()?;
+                    let feature: LitStr = input.parse()?;
+                    args.cfg = Some(quote!(feature = #feature));
+                }
+                "when" => {
+                    let content;
+                    parenthesized!(content in input);
+                    let predicate: Meta = content.parse()?;
+                    args.cfg = Some(quote!(#predicate));
+                }
+                "doc_cfg" => args.doc_cfg = true,
+                "wasm_bindgen" => args.wasm_bindgen = Some(input.parse()?),
+                // `rename` can come first, so keep its template
+                "sibling" if args.sibling.is_some() && !input.peek(Token![=]) => {}
+                "sibling" => args.sibling = Some(input.parse()?),
+                "rename" => {
+                    input.parse::<Token![=]>()?;
+                    args.sibling = Some(Sibling::template(&input.parse()?)?);
+                }
+                "name_span" => {
+                    input.parse::<Token![=]>()?;
+                    args.name_span = input.parse()?;
+                }
+                "block_on" => args.block_on = Some(input.parse()?),
+                "delay" => args.delay = Some(input.parse()?),
+                "trait_pair" => args.trait_pair = Some(input.parse()?),
+                "strict" => args.strict = true,
+                "warn_leaks" => args.warn_leaks = true,
+                "macro_exprs" => args.macro_exprs = true,
+                "macro_tokens" => args.macro_tokens = true,
+                "hoist_awaits" => args.hoist_awaits = true,
+                "deny_noop" => args.deny_noop = true,
+                "vis" => {
+                    input.parse::<Token![=]>()?;
+                    let vis: LitStr = input.parse()?;
+                    args.vis = Some(vis.parse()?);
+                }
+                "sync_body" => {
+                    input.parse::<Token![=]>()?;
+                    args.sync_body = Some(input.parse()?);
+                }
+                "race" => {
+                    input.parse::<Token![=]>()?;
+                    args.race = input.parse()?;
+                }
+                _ => return Err(unknown_key(&key)),
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        // `name_span` can come before or after the arguments that generate names
+        if let Some(sibling) = &mut args.sibling {
+            sibling.span = args.name_span;
+        }
+        if let Some(trait_pair) = &mut args.trait_pair {
+            trait_pair.set_name_span(args.name_span);
+        }
+        Ok(args)
+    }
+}
+
+/// The error for an unknown argument, suggesting the closest known one if it looks like a typo.
+fn unknown_key(key: &Ident) -> syn::Error {
+    let name = key.to_string();
+    let closest = KEYS
+        .iter()
+        .map(|known| (edit_distance(&name, known), known))
+        .filter(|(distance, _)| *distance <= (name.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance);
+    let message = match closest {
+        Some((_, known)) => format!("unknown argument `{}`, did you mean `{}`?", key, known),
+        None => format!("unknown argument `{}`", key),
+    };
+    syn::Error::new(key.span(), message)
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Moves the next argument from `input` to `rest`, up to and including its comma. This lets attributes with their own
+/// keys pass everything else on to `Args`.
+pub(crate) fn pass_through(input: ParseStream, rest: &mut TokenStream) -> syn::Result<()> {
+    while !input.is_empty() {
+        let token: TokenTree = input.parse()?;
+        let comma = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ',');
+        rest.extend([token]);
+        if comma {
+            break;
+        }
+    }
+    Ok(())
+}
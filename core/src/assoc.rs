@@ -0,0 +1,128 @@
+use syn::{
+    fold::{self, Fold},
+    GenericArgument, GenericParam, Generics, Ident, ImplItem, Lifetime, PathArguments, TraitItem,
+    Type,
+};
+
+use crate::types;
+
+/// An associated type that was a future, like `type Fut: Future<Output = T>` or `type Fut<'a> = BoxFuture<'a, T>`.
+pub(crate) struct AssocFuture {
+    ident: Ident,
+    /// Generic parameters of the associated type, which are substituted in `output` wherever it's used.
+    generics: Generics,
+    output: Type,
+}
+
+/// Removes associated types that are futures from a trait, like `type Fut: Future<Output = T>`.
+pub(crate) fn remove_from_trait(items: &mut Vec<TraitItem>) -> Vec<AssocFuture> {
+    let mut removed = Vec::new();
+    items.retain(|item| match item {
+        TraitItem::Type(ty) => match types::future_bounds_output(&ty.bounds) {
+            Some(output) => {
+                removed.push(AssocFuture {
+                    ident: ty.ident.clone(),
+                    generics: ty.generics.clone(),
+                    output,
+                });
+                false
+            }
+            None => true,
+        },
+        _ => true,
+    });
+    removed
+}
+
+/// Removes associated types that are futures from an impl, like `type Fut = Ready<T>` or `type Fut = BoxFuture<'a, T>`.
+pub(crate) fn remove_from_impl(items: &mut Vec<ImplItem>) -> Vec<AssocFuture> {
+    let mut removed = Vec::new();
+    items.retain(|item| match item {
+        ImplItem::Type(ty) => match types::future_output(&ty.ty) {
+            Some(output) => {
+                removed.push(AssocFuture {
+                    ident: ty.ident.clone(),
+                    generics: ty.generics.clone(),
+                    output,
+                });
+                false
+            }
+            None => true,
+        },
+        _ => true,
+    });
+    removed
+}
+
+/// If `ty` is `Self::Fut` (or `Self::Fut<'a>`) and `Fut` is one of the associated types in `removed`, returns its
+/// output, with the associated type's generic parameters replaced by the arguments given to it.
+pub(crate) fn resolve(removed: &[AssocFuture], ty: &Type) -> Option<Type> {
+    let path = match ty {
+        Type::Path(ty) if ty.qself.is_none() && ty.path.leading_colon.is_none() => &ty.path,
+        _ => return None,
+    };
+    let assoc = match path.segments.iter().collect::<Vec<_>>().as_slice() {
+        [this, assoc] if this.ident == "Self" && this.arguments.is_empty() => *assoc,
+        _ => return None,
+    };
+    let removed = removed
+        .iter()
+        .find(|removed| assoc.ident == removed.ident)?;
+
+    let args = match &assoc.arguments {
+        PathArguments::None => Vec::new(),
+        PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+        PathArguments::Parenthesized(_) => return None,
+    };
+    let (mut lifetimes, mut types) = (
+        args.iter().filter_map(|arg| match arg {
+            GenericArgument::Lifetime(lifetime) => Some(lifetime),
+            _ => None,
+        }),
+        args.iter().filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+    );
+    let mut substitute = Substitute::default();
+    for param in &removed.generics.params {
+        match param {
+            GenericParam::Lifetime(param) => substitute
+                .lifetimes
+                .push((param.lifetime.clone(), lifetimes.next()?.clone())),
+            GenericParam::Type(param) => substitute
+                .types
+                .push((param.ident.clone(), types.next()?.clone())),
+            GenericParam::Const(_) => return None,
+        }
+    }
+    Some(substitute.fold_type(removed.output.clone()))
+}
+
+/// Replaces generic parameters of an associated type with the arguments it was used with.
+#[derive(Default)]
+struct Substitute {
+    lifetimes: Vec<(Lifetime, Lifetime)>,
+    types: Vec<(Ident, Type)>,
+}
+
+impl Fold for Substitute {
+    fn fold_lifetime(&mut self, l: Lifetime) -> Lifetime {
+        match self.lifetimes.iter().find(|(param, _)| *param == l) {
+            Some((_, arg)) => arg.clone(),
+            None => l,
+        }
+    }
+
+    fn fold_type(&mut self, t: Type) -> Type {
+        if let Type::Path(ty) = &t {
+            if let Some((_, arg)) = (self.types)
+                .iter()
+                .find(|(param, _)| ty.qself.is_none() && ty.path.is_ident(param))
+            {
+                return arg.clone();
+            }
+        }
+        fold::fold_type(self, t)
+    }
+}
@@ -0,0 +1,162 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse::{ParseStream, Parser},
+    parse_quote, Attribute, Ident, Meta, Path,
+};
+
+/// Attributes from other crates that only make sense on async items, matched by the last segment of their path.
+const ASYNC_ATTRS: &[&str] = &["async_trait", "async_recursion"];
+
+/// Crates with `main` and `test` attributes that start an async runtime, like `#[tokio::main]` and `#[tokio::test]`.
+const RUNTIMES: &[&str] = &["tokio", "async_std", "actix_rt", "actix_web"];
+
+/// Removes attributes like `#[async_trait]` and `#[async_recursion]`, which would fail or re-box everything once the
+/// item isn't async anymore, and runtime attributes like `#[tokio::main]`. Runtime test attributes like
+/// `#[tokio::test]` are replaced with `#[test]`.
+pub(crate) fn rewrite_async_attrs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !is_async_attr(attr) && !is_runtime_attr(attr, "main"));
+    for attr in attrs.iter_mut() {
+        if is_runtime_attr(attr, "test") {
+            *attr = parse_quote!(#[test]);
+        } else if wraps_runtime_test(attr) {
+            // `#[test_log::test(tokio::test)]` becomes `#[test_log::test]`, which uses `#[test]`
+            attr.meta = Meta::Path(attr.path().clone());
+        }
+    }
+    // mockall needs `#[automock]` before `#[async_trait]` on async traits, but a blocking trait should be mocked once
+    // every other attribute has expanded
+    let (automock, rest): (Vec<_>, Vec<_>) = attrs.drain(..).partition(is_automock);
+    *attrs = rest;
+    attrs.extend(automock);
+}
+
+fn is_async_attr(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| ASYNC_ATTRS.iter().any(|name| segment.ident == name))
+}
+
+fn is_automock(attr: &Attribute) -> bool {
+    let segments = &attr.path().segments;
+    match segments.len() {
+        1 => segments[0].ident == "automock",
+        2 => segments[0].ident == "mockall" && segments[1].ident == "automock",
+        _ => false,
+    }
+}
+
+/// Whether `attr` is a runtime's attribute called `name`, like `#[tokio::main(flavor = "current_thread")]`.
+fn is_runtime_attr(attr: &Attribute, name: &str) -> bool {
+    is_runtime_path(attr.path(), name)
+}
+
+fn is_runtime_path(path: &Path, name: &str) -> bool {
+    let segments = &path.segments;
+    segments.len() == 2
+        && RUNTIMES.iter().any(|runtime| segments[0].ident == runtime)
+        && segments[1].ident == name
+}
+
+/// Whether `attr` is a test attribute that wraps a runtime's test attribute, like `#[test_log::test(tokio::test)]`.
+fn wraps_runtime_test(attr: &Attribute) -> bool {
+    match attr.path().segments.last() {
+        Some(segment) if segment.ident == "test" => {}
+        _ => return false,
+    }
+    let inner = match attr.parse_args::<Meta>() {
+        Ok(inner) => inner,
+        Err(_) => return false,
+    };
+    is_runtime_path(inner.path(), "test")
+}
+
+/// Splits the outer attributes off the start of an item.
+fn split_outer(item: TokenStream) -> Option<(Vec<Attribute>, TokenStream)> {
+    let parser = |input: ParseStream| Ok((input.call(Attribute::parse_outer)?, input.parse()?));
+    parser.parse2(item).ok()
+}
+
+/// Takes the attributes matching `paths` out of `attrs`, in the order of `paths`.
+fn take_matching(attrs: &mut Vec<Attribute>, paths: &[Path]) -> Vec<Attribute> {
+    let mut taken = Vec::new();
+    for path in paths {
+        let (matching, rest) = attrs
+            .drain(..)
+            .partition(|attr| same_path(attr.path(), path));
+        taken.extend::<Vec<_>>(matching);
+        *attrs = rest;
+    }
+    taken
+}
+
+/// Whether `a` and `b` have the same segments, ignoring arguments (which attribute paths don't have).
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.leading_colon.is_some() == b.leading_colon.is_some()
+        && a.segments.len() == b.segments.len()
+        && a.segments
+            .iter()
+            .zip(&b.segments)
+            .all(|(a, b)| a.ident == b.ident)
+}
+
+/// If the item has any attributes from `before(...)`, moves them in front of the attribute being expanded, so they
+/// expand on the async item and the transform runs once they're done. `entry` names the attribute, like `dual`.
+pub(crate) fn expand_before(
+    before: &[Path],
+    entry: &str,
+    args: &TokenStream,
+    item: &TokenStream,
+) -> Option<TokenStream> {
+    if before.is_empty() {
+        return None;
+    }
+    let (mut attrs, item) = split_outer(item.clone())?;
+    let before = take_matching(&mut attrs, before);
+    if before.is_empty() {
+        return None;
+    }
+    let path = invoked_path(entry);
+    Some(quote! {
+        #(#before)*
+        #[#path(#args)]
+        #(#attrs)*
+        #item
+    })
+}
+
+/// The path of the attribute being expanded as it was written, like `raa::remove_async_await` for a renamed
+/// dependency or `maybe_async` for the compat attributes. Proc macros aren't told, so it's read from the source of
+/// the attribute, falling back to `::remove_async_await::entry` where the source isn't available.
+fn invoked_path(entry: &str) -> Path {
+    let written = Span::call_site().source_text().and_then(|text| {
+        // the attribute is written out unless it's in a `cfg_attr`
+        let text = text
+            .strip_prefix("#[")
+            .and_then(|text| text.strip_suffix(']'))
+            .unwrap_or(&text);
+        syn::parse_str::<Meta>(text).ok()
+    });
+    match written {
+        Some(meta) => meta.path().clone(),
+        None => {
+            let entry = Ident::new(entry, Span::call_site());
+            parse_quote!(::remove_async_await::#entry)
+        }
+    }
+}
+
+/// Moves the attributes from `after(...)` to the end of the item's attributes, so they expand last.
+pub(crate) fn emit_after(after: &[Path], item: TokenStream) -> TokenStream {
+    if after.is_empty() {
+        return item;
+    }
+    match split_outer(item.clone()) {
+        Some((mut attrs, item)) => {
+            let after = take_matching(&mut attrs, after);
+            quote!(#(#attrs)* #(#after)* #item)
+        }
+        None => item,
+    }
+}
@@ -0,0 +1,59 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, Block, Expr, ReturnType, Signature, Token,
+};
+
+use crate::{lifetimes, types};
+
+/// The executor used by `block_on` or `block_on = executor`, like `futures::executor::block_on` or
+/// `tokio::runtime::Handle::current().block_on`.
+pub(crate) struct BlockOn {
+    executor: TokenStream,
+}
+
+impl Parse for BlockOn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Token![=]) {
+            return Ok(BlockOn {
+                executor: quote!(::futures::executor::block_on),
+            });
+        }
+        input.parse::<Token![=]>()?;
+        // the executor is called with the future, so method calls like `handle.block_on` are fine too
+        let executor: Expr = input.parse()?;
+        Ok(BlockOn {
+            executor: executor.into_token_stream(),
+        })
+    }
+}
+
+impl BlockOn {
+    /// Turns an async function or a function returning a future into a blocking function that runs its untouched body
+    /// with the executor. Returns `false` if the function is neither, so it can be folded as usual.
+    pub(crate) fn wrap(&self, sig: &mut Signature, block: &mut Block) -> bool {
+        let output = match &sig.output {
+            ReturnType::Type(_, ty) => types::future_output(ty),
+            ReturnType::Default => None,
+        };
+        let executor = &self.executor;
+        let body = if sig.asyncness.take().is_some() {
+            quote!(async move #block)
+        } else if let Some(output) = output {
+            // the body already evaluates to the future
+            let unused_lifetimes = lifetimes::output_lifetimes(sig);
+            sig.output = if types::is_unit(&output) {
+                ReturnType::Default
+            } else {
+                ReturnType::Type(Default::default(), Box::new(output))
+            };
+            lifetimes::elide_unused(sig, unused_lifetimes);
+            quote!(#block)
+        } else {
+            return false;
+        };
+        *block = parse_quote!({ #executor(#body) });
+        true
+    }
+}
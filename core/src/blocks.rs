@@ -0,0 +1,125 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    parse_quote, token,
+    visit::{self, Visit},
+    Block, Expr, ExprAsync, ExprBlock, ExprClosure, ExprParen, ExprReturn, ExprTry, Item, Macro,
+    Stmt, Token,
+};
+
+/// Turns an async block into a plain block, or just its value for `async { value }`, so it doesn't trip
+/// `unused_braces` when it's returned.
+pub(crate) fn unasync(mut e: ExprAsync) -> Expr {
+    if e.attrs.is_empty() && matches!(e.block.stmts.as_slice(), [Stmt::Expr(_, None)]) {
+        if let Some(Stmt::Expr(inner, None)) = e.block.stmts.pop() {
+            return parenthesized(inner, e.async_token.span);
+        }
+    }
+    Expr::Block(ExprBlock {
+        attrs: e.attrs,
+        label: None,
+        block: e.block,
+    })
+}
+
+/// Wraps `e` in parentheses unless it's atomic, so it keeps its precedence where an `.await` or a call used to be,
+/// like `1 + 2` in `async { 1 + 2 }.await * 3`. An invisible group would only keep it until the tokens are printed,
+/// which `build::mirror` and the wasm blob do.
+pub(crate) fn parenthesized(e: Expr, span: Span) -> Expr {
+    match e {
+        Expr::Array(_)
+        | Expr::Await(_)
+        | Expr::Call(_)
+        | Expr::Field(_)
+        | Expr::Group(_)
+        | Expr::Index(_)
+        | Expr::Lit(_)
+        | Expr::Macro(_)
+        | Expr::MethodCall(_)
+        | Expr::Paren(_)
+        | Expr::Path(_)
+        | Expr::Struct(_)
+        | Expr::Try(_)
+        | Expr::Tuple(_) => e,
+        // resolved at the macro, so `unused_parens` doesn't fire where the parentheses aren't needed, like in a block's
+        // tail
+        e => Expr::Paren(ExprParen {
+            attrs: Vec::new(),
+            paren_token: token::Paren(span.resolved_at(Span::mixed_site())),
+            expr: Box::new(e),
+        }),
+    }
+}
+
+/// Whether an async block has a `return` or `?` of its own, which only leave the future while it's async but would
+/// leave the enclosing function once it's a plain block. `break` and `continue` can't leave an async block, so they
+/// mean the same either way.
+pub(crate) fn leaves(block: &Block) -> bool {
+    let mut finder = Finder { found: false };
+    finder.visit_block(block);
+    finder.found
+}
+
+/// Runs the (folded) body of an async block in a closure, so its `return` and `?` still only leave the block, see
+/// [`tokens::in_closure`](crate::tokens::in_closure).
+pub(crate) fn in_closure(capture: Option<Token![move]>, span: Span, block: Block) -> Expr {
+    let closure = crate::tokens::in_closure(capture.into_token_stream(), span, block.into_token_stream());
+    parse_quote!(#closure)
+}
+
+/// Flattens an async block at the end of `block`, the body of a function or closure returning a future, without a
+/// closure: its `return` and `?` leave the function with the same value once it's blocking.
+pub(crate) fn flatten_tail(block: &mut Block) {
+    if let Some(Stmt::Expr(tail, None)) = block.stmts.last_mut() {
+        flatten(tail);
+    }
+}
+
+/// Flattens `e` if it's an async block, like the body of `|| async move { ... }`.
+pub(crate) fn flatten(e: &mut Expr) {
+    if let Expr::Async(async_block) = e {
+        if async_block.attrs.is_empty() {
+            let Expr::Async(async_block) = std::mem::replace(e, Expr::Verbatim(TokenStream::new()))
+            else {
+                unreachable!()
+            };
+            *e = unasync(async_block);
+        }
+    }
+}
+
+/// Finds `return` and `?` that belong to a block, not to the closures, async blocks and items in it.
+struct Finder {
+    found: bool,
+}
+
+impl<'ast> Visit<'ast> for Finder {
+    fn visit_expr_return(&mut self, _: &'ast ExprReturn) {
+        self.found = true;
+    }
+
+    fn visit_expr_try(&mut self, _: &'ast ExprTry) {
+        self.found = true;
+    }
+
+    fn visit_expr_async(&mut self, _: &'ast ExprAsync) {}
+
+    fn visit_expr_closure(&mut self, _: &'ast ExprClosure) {}
+
+    fn visit_item(&mut self, _: &'ast Item) {}
+
+    fn visit_macro(&mut self, i: &'ast Macro) {
+        // the arguments of macros like `println!` can return too
+        self.found |= has_return(i.tokens.clone());
+        visit::visit_macro(self, i);
+    }
+}
+
+fn has_return(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "return",
+        TokenTree::Punct(punct) => punct.as_char() == '?',
+        TokenTree::Group(group) => has_return(group.stream()),
+        TokenTree::Literal(_) => false,
+    })
+}
@@ -0,0 +1,91 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Generics, Ident, Lifetime, Token, TypeParamBound, WherePredicate,
+};
+
+/// A bound to remove, from `strip_bounds(...)`.
+pub(crate) enum StrippedBound {
+    Trait(Ident),
+    Lifetime(Lifetime),
+}
+
+impl StrippedBound {
+    /// The bounds removed by a plain `strip_bounds`.
+    pub(crate) fn defaults() -> Vec<Self> {
+        vec![
+            StrippedBound::Trait(Ident::new("Send", proc_macro2::Span::call_site())),
+            StrippedBound::Trait(Ident::new("Sync", proc_macro2::Span::call_site())),
+            StrippedBound::Lifetime(Lifetime::new("'static", proc_macro2::Span::call_site())),
+        ]
+    }
+
+    /// The bound removed by `strip_unpin`.
+    pub(crate) fn unpin() -> Self {
+        StrippedBound::Trait(Ident::new("Unpin", proc_macro2::Span::call_site()))
+    }
+
+    fn matches(&self, bound: &TypeParamBound) -> bool {
+        match (self, bound) {
+            (StrippedBound::Trait(name), TypeParamBound::Trait(bound)) => bound
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == *name),
+            (StrippedBound::Lifetime(name), TypeParamBound::Lifetime(bound)) => bound == name,
+            _ => false,
+        }
+    }
+}
+
+impl Parse for StrippedBound {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Lifetime) {
+            input.parse().map(StrippedBound::Lifetime)
+        } else {
+            input.parse().map(StrippedBound::Trait)
+        }
+    }
+}
+
+/// Removes the bounds matching `stripped` from `bounds`.
+pub(crate) fn strip(
+    stripped: &[StrippedBound],
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+) -> Punctuated<TypeParamBound, Token![+]> {
+    bounds
+        .into_iter()
+        .filter(|bound| !stripped.iter().any(|stripped| stripped.matches(bound)))
+        .collect()
+}
+
+/// Removes the bounds matching `stripped` from generic parameters and the where clause, removing any where clause
+/// predicates that are left empty.
+pub(crate) fn strip_generics(stripped: &[StrippedBound], generics: &mut Generics) {
+    if stripped.is_empty() {
+        return;
+    }
+
+    for param in generics.type_params_mut() {
+        param.bounds = strip(stripped, std::mem::take(&mut param.bounds));
+        if param.bounds.is_empty() {
+            param.colon_token = None;
+        }
+    }
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::Type(mut predicate) => {
+                    predicate.bounds = strip(stripped, std::mem::take(&mut predicate.bounds));
+                    (!predicate.bounds.is_empty()).then_some(WherePredicate::Type(predicate))
+                }
+                predicate => Some(predicate),
+            })
+            .collect();
+        if where_clause.predicates.is_empty() {
+            generics.where_clause = None;
+        }
+    }
+}
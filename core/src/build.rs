@@ -0,0 +1,144 @@
+//! Helpers for build scripts, which transform whole modules ahead of time instead of with the attribute.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use quote::ToTokens;
+use syn::{
+    fold::{self, Fold},
+    Expr, ExprLit, Item, ItemMod, Lit, Meta,
+};
+
+use crate::RemoveAsyncAwait;
+
+/// Transforms the module at `source` and writes it to `$OUT_DIR/{name}.rs`, returning the path of the written file.
+/// `source` can either be a directory with a `mod.rs` or a module's file. It should be called from a build script,
+/// and the result included with `include!`:
+///
+/// ```rs
+/// // build.rs
+/// remove_async_await_core::build::mirror("src/async_impl", "blocking").unwrap();
+///
+/// // src/lib.rs
+/// mod blocking {
+///     include!(concat!(env!("OUT_DIR"), "/blocking.rs"));
+/// }
+/// ```
+///
+/// Submodules declared with `mod name;` are read and inlined, so the output is a single file. Inner attributes of the
+/// root file (like `//!` docs) are dropped, since `include!` doesn't allow them.
+pub fn mirror(source: impl AsRef<Path>, name: &str) -> io::Result<PathBuf> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "`OUT_DIR` isn't set, `mirror` should be called from a build script",
+        )
+    })?;
+    let output = Path::new(&out_dir).join(format!("{}.rs", name));
+    mirror_to(source, &output)?;
+    Ok(output)
+}
+
+/// Like [`mirror`], but writes the transformed module to `output`.
+pub fn mirror_to(source: impl AsRef<Path>, output: impl AsRef<Path>) -> io::Result<()> {
+    let source = source.as_ref();
+    let root = if source.is_dir() {
+        source.join("mod.rs")
+    } else {
+        source.to_owned()
+    };
+    let mut file = read_module(&root)?;
+    file.attrs.clear();
+    let file = Ungroup.fold_file(RemoveAsyncAwait::new().fold_file(file));
+    fs::write(output, file.into_token_stream().to_string())
+}
+
+/// Turns invisible groups into parentheses where they're needed, since printing the tokens drops the groups along
+/// with the precedence they keep.
+struct Ungroup;
+
+impl Fold for Ungroup {
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        match fold::fold_expr(self, e) {
+            Expr::Group(group) => crate::blocks::parenthesized(*group.expr, group.group_token.span),
+            e => e,
+        }
+    }
+}
+
+/// Reads the module at `path`, inlining its submodules.
+fn read_module(path: &Path) -> io::Result<syn::File> {
+    println!("cargo:rerun-if-changed={}", path.display());
+    let source = fs::read_to_string(path)?;
+    let mut file = syn::parse_file(&source).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("failed to parse {}: {}", path.display(), err),
+        )
+    })?;
+    // submodules of `mod.rs` are next to it, and submodules of `name.rs` are in `name/`
+    let dir = match path.file_stem() {
+        Some(stem) if stem != "mod" => path.with_extension(""),
+        _ => path.parent().map(Path::to_owned).unwrap_or_default(),
+    };
+    for item in &mut file.items {
+        if let Item::Mod(module) = item {
+            if module.content.is_none() {
+                inline_module(module, path, &dir)?;
+            }
+        }
+    }
+    Ok(file)
+}
+
+/// Replaces `mod name;` with `mod name { ... }`.
+fn inline_module(module: &mut ItemMod, parent: &Path, dir: &Path) -> io::Result<()> {
+    let path = match path_attr(module)? {
+        // `#[path]` is relative to the directory of the file it's in
+        Some(path) => parent.parent().unwrap_or(Path::new("")).join(path),
+        None => {
+            let name = module.ident.to_string();
+            let file = dir.join(format!("{}.rs", name));
+            if file.exists() {
+                file
+            } else {
+                dir.join(name).join("mod.rs")
+            }
+        }
+    };
+    let file = read_module(&path)?;
+    module.attrs.retain(|attr| !attr.path().is_ident("path"));
+    module.attrs.extend(file.attrs);
+    module.content = Some((Default::default(), file.items));
+    module.semi = None;
+    Ok(())
+}
+
+fn path_attr(module: &ItemMod) -> io::Result<Option<String>> {
+    let attr = match module
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("path"))
+    {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+    match &attr.meta {
+        Meta::NameValue(meta) => match &meta.value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(path),
+                ..
+            }) => Ok(Some(path.value())),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "`#[path]` should be a string",
+            )),
+        },
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "`#[path]` should be `#[path = \"...\"]`",
+        )),
+    }
+}
@@ -0,0 +1,198 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{
+    fold::{self, Fold},
+    punctuated::Punctuated,
+    Arm, Attribute, Block, ExprMatch, ExprStruct, Field, FieldValue, FieldsNamed, FieldsUnnamed,
+    File, ImplItem, Item, ItemEnum, ItemImpl, ItemMod, ItemTrait, Meta, Stmt, Token, TraitItem,
+    Variant,
+};
+
+/// Resolves the `#[cfg]` and `#[cfg_attr]` attributes in one of the copies `cfg_variants` emits, for the predicate it
+/// gates the copy with: in the copy where `predicate` holds (or doesn't), code gated on `predicate` (or its negation)
+/// is always (or never) compiled, so it's kept without the gate (or removed), and so is the copy itself. Predicates
+/// that don't only depend on `predicate`, like `feature = "other"`, are left for the compiler.
+pub(crate) fn resolve(tokens: TokenStream, predicate: &TokenStream, holds: bool) -> TokenStream {
+    // trait methods without a body aren't items, and are left as they are
+    let Ok(file) = syn::parse2::<File>(tokens.clone()) else {
+        return tokens;
+    };
+    Resolver {
+        predicate: predicate.to_string(),
+        holds,
+    }
+    .fold_file(file)
+    .into_token_stream()
+}
+
+struct Resolver {
+    predicate: String,
+    holds: bool,
+}
+
+impl Resolver {
+    /// Evaluates a cfg predicate, or returns `None` when it depends on more than `predicate`.
+    fn eval(&self, meta: &Meta) -> Option<bool> {
+        if meta.to_token_stream().to_string() == self.predicate {
+            return Some(self.holds);
+        }
+        let Meta::List(list) = meta else {
+            return None;
+        };
+        let nested = list
+            .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+            .ok()?;
+        let values: Vec<_> = nested.iter().map(|meta| self.eval(meta)).collect();
+        if list.path.is_ident("not") && values.len() == 1 {
+            values[0].map(|value| !value)
+        } else if list.path.is_ident("all") {
+            if values.contains(&Some(false)) {
+                Some(false)
+            } else {
+                values.iter().all(Option::is_some).then_some(true)
+            }
+        } else if list.path.is_ident("any") {
+            if values.contains(&Some(true)) {
+                Some(true)
+            } else {
+                values.iter().all(Option::is_some).then_some(false)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Resolves the attributes of a node, returning whether the node is compiled in this copy.
+    fn attrs(&self, attrs: &mut Vec<Attribute>) -> bool {
+        let mut resolved = Vec::with_capacity(attrs.len());
+        for attr in attrs.drain(..) {
+            if attr.path().is_ident("cfg") {
+                match attr.parse_args().ok().and_then(|meta| self.eval(&meta)) {
+                    Some(true) => continue,
+                    Some(false) => return false,
+                    None => {}
+                }
+            } else if attr.path().is_ident("cfg_attr") {
+                // `#[cfg_attr(predicate, attr, ...)]` becomes `#[attr] ...` or nothing
+                if let Ok(nested) =
+                    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                {
+                    let mut nested = nested.into_iter();
+                    if let Some(value) = nested.next().and_then(|meta| self.eval(&meta)) {
+                        if value {
+                            resolved.extend(nested.map(|meta| Attribute {
+                                meta,
+                                ..attr.clone()
+                            }));
+                        }
+                        continue;
+                    }
+                }
+            }
+            resolved.push(attr);
+        }
+        *attrs = resolved;
+        true
+    }
+
+    /// Removes the nodes in a list that aren't compiled in this copy, resolving the attributes of the rest.
+    fn retain<T, C>(&self, nodes: C, attrs: fn(&mut T) -> Option<&mut Vec<Attribute>>) -> C
+    where
+        C: IntoIterator<Item = T> + FromIterator<T>,
+    {
+        nodes
+            .into_iter()
+            .filter_map(|mut node| {
+                let compiled = attrs(&mut node).is_none_or(|attrs| self.attrs(attrs));
+                compiled.then_some(node)
+            })
+            .collect()
+    }
+}
+
+fn item_attrs(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    crate::keep::item_attrs(item)
+}
+
+fn impl_item_attrs(item: &mut ImplItem) -> Option<&mut Vec<Attribute>> {
+    match item {
+        ImplItem::Const(item) => Some(&mut item.attrs),
+        ImplItem::Fn(item) => Some(&mut item.attrs),
+        ImplItem::Type(item) => Some(&mut item.attrs),
+        ImplItem::Macro(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+fn trait_item_attrs(item: &mut TraitItem) -> Option<&mut Vec<Attribute>> {
+    match item {
+        TraitItem::Const(item) => Some(&mut item.attrs),
+        TraitItem::Fn(item) => Some(&mut item.attrs),
+        TraitItem::Type(item) => Some(&mut item.attrs),
+        TraitItem::Macro(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
+
+fn stmt_attrs(stmt: &mut Stmt) -> Option<&mut Vec<Attribute>> {
+    match stmt {
+        Stmt::Local(local) => Some(&mut local.attrs),
+        Stmt::Item(item) => item_attrs(item),
+        Stmt::Expr(expr, _) => crate::keep::expr_attrs(expr),
+        Stmt::Macro(mac) => Some(&mut mac.attrs),
+    }
+}
+
+impl Fold for Resolver {
+    fn fold_file(&mut self, mut f: File) -> File {
+        f.items = self.retain(f.items, item_attrs);
+        fold::fold_file(self, f)
+    }
+
+    fn fold_item_mod(&mut self, mut i: ItemMod) -> ItemMod {
+        if let Some((_, items)) = &mut i.content {
+            *items = self.retain(std::mem::take(items), item_attrs);
+        }
+        fold::fold_item_mod(self, i)
+    }
+
+    fn fold_item_impl(&mut self, mut i: ItemImpl) -> ItemImpl {
+        i.items = self.retain(i.items, impl_item_attrs);
+        fold::fold_item_impl(self, i)
+    }
+
+    fn fold_item_trait(&mut self, mut i: ItemTrait) -> ItemTrait {
+        i.items = self.retain(i.items, trait_item_attrs);
+        fold::fold_item_trait(self, i)
+    }
+
+    fn fold_item_enum(&mut self, mut i: ItemEnum) -> ItemEnum {
+        i.variants = self.retain(i.variants, |v: &mut Variant| Some(&mut v.attrs));
+        fold::fold_item_enum(self, i)
+    }
+
+    fn fold_fields_named(&mut self, mut f: FieldsNamed) -> FieldsNamed {
+        f.named = self.retain(f.named, |f: &mut Field| Some(&mut f.attrs));
+        fold::fold_fields_named(self, f)
+    }
+
+    fn fold_fields_unnamed(&mut self, mut f: FieldsUnnamed) -> FieldsUnnamed {
+        f.unnamed = self.retain(f.unnamed, |f: &mut Field| Some(&mut f.attrs));
+        fold::fold_fields_unnamed(self, f)
+    }
+
+    fn fold_block(&mut self, mut b: Block) -> Block {
+        b.stmts = self.retain(b.stmts, stmt_attrs);
+        fold::fold_block(self, b)
+    }
+
+    fn fold_expr_match(&mut self, mut e: ExprMatch) -> ExprMatch {
+        e.arms = self.retain(e.arms, |a: &mut Arm| Some(&mut a.attrs));
+        fold::fold_expr_match(self, e)
+    }
+
+    fn fold_expr_struct(&mut self, mut e: ExprStruct) -> ExprStruct {
+        e.fields = self.retain(e.fields, |f: &mut FieldValue| Some(&mut f.attrs));
+        fold::fold_expr_struct(self, e)
+    }
+}
@@ -0,0 +1,90 @@
+use quote::ToTokens;
+use syn::{
+    fold::Fold,
+    parse::{Parse, ParseStream},
+    parse_quote_spanned, Expr, ExprMethodCall, Ident, Path, Token,
+};
+
+/// A method chain whose awaited tail is rewritten to a call to a blocking function, from
+/// `chains(root.method = hook, ...)`, like `chains(sqlx::query.fetch_one = db::fetch_one)`.
+pub(crate) struct ChainMapping {
+    /// The function or macro the chain starts with, like `sqlx::query` or `sqlx::query_as!`.
+    root: Path,
+    /// Whether `root` is a macro.
+    is_macro: bool,
+    /// The awaited method at the end of the chain.
+    method: Ident,
+    /// The function or method called instead, like `db::fetch_one` or `self.db.fetch_one`.
+    hook: Expr,
+}
+
+impl Parse for ChainMapping {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let root = input.call(Path::parse_mod_style)?;
+        let is_macro = input.parse::<Option<Token![!]>>()?.is_some();
+        input.parse::<Token![.]>()?;
+        let method = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let hook: Expr = input.parse()?;
+        if !matches!(hook, Expr::Path(_) | Expr::Field(_)) {
+            return Err(syn::Error::new_spanned(
+                hook,
+                "expected a function, like `db::fetch_one`, or a method, like `self.db.fetch_one`",
+            ));
+        }
+        Ok(ChainMapping {
+            root,
+            is_macro,
+            method,
+            hook,
+        })
+    }
+}
+
+impl ChainMapping {
+    /// Whether `e` is the method at the end of a chain starting with `root`, like
+    /// `sqlx::query(sql).bind(id).fetch_one(pool)`. The root can be written with a shorter path, like `query(sql)`, when
+    /// it's imported.
+    fn matches(&self, e: &ExprMethodCall) -> bool {
+        if e.method != self.method {
+            return false;
+        }
+        let mut receiver = &*e.receiver;
+        let path = loop {
+            match receiver {
+                Expr::MethodCall(call) => receiver = &call.receiver,
+                Expr::Call(call) if !self.is_macro => match &*call.func {
+                    Expr::Path(func) if func.qself.is_none() => break &func.path,
+                    _ => return false,
+                },
+                Expr::Macro(mac) if self.is_macro => break &mac.mac.path,
+                _ => return false,
+            }
+        };
+        path.segments.len() <= self.root.segments.len()
+            && path
+                .segments
+                .iter()
+                .rev()
+                .zip(self.root.segments.iter().rev())
+                .all(|(a, b)| a.ident == b.ident)
+    }
+}
+
+/// If `e` is awaited at the end of a chain in `chains`, returns the hook it's rewritten to call.
+pub(crate) fn hook(chains: &[ChainMapping], e: &ExprMethodCall) -> Option<Expr> {
+    chains
+        .iter()
+        .find(|mapping| mapping.matches(e))
+        .map(|mapping| mapping.hook.clone())
+}
+
+/// Rewrites `chain.method(args)` to `hook(chain, args)`, so the hook gets the rest of the chain, like the query built
+/// by `sqlx::query(sql).bind(id)`, and runs it.
+pub(crate) fn call<F: Fold>(folder: &mut F, hook: Expr, e: ExprMethodCall) -> Expr {
+    let span = e.method.span();
+    let hook = hook.into_token_stream();
+    let receiver = folder.fold_expr(*e.receiver);
+    let args = e.args.into_iter().map(|arg| folder.fold_expr(arg));
+    parse_quote_spanned!(span=> #hook(#receiver, #(#args),*))
+}
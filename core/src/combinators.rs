@@ -0,0 +1,88 @@
+use quote::quote_spanned;
+use syn::{
+    fold::Fold, parse::Parse, parse::ParseStream, punctuated::Punctuated, Expr, ExprTuple, Ident,
+};
+
+use crate::blocks::parenthesized;
+
+/// Which branch `race` should be lowered to, from `race = first | second | error`.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum Race {
+    #[default]
+    First,
+    Second,
+    Error,
+}
+
+impl Parse for Race {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        match ident.to_string().as_str() {
+            "first" => Ok(Race::First),
+            "second" => Ok(Race::Second),
+            "error" => Ok(Race::Error),
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "expected `first`, `second` or `error`",
+            )),
+        }
+    }
+}
+
+/// Folds the expression of an `.await`, lowering futures-lite combinators like `future::zip(a, b)` or `a.race(b)`.
+pub(crate) fn fold_awaited<F: Fold>(folder: &mut F, race: Race, e: Expr) -> Expr {
+    let (name, a, b) = match e {
+        // `future::zip(a, b)`, `futures_lite::future::race(a, b)`, etc.
+        Expr::Call(call) if call.args.len() == 2 => {
+            let name = match &*call.func {
+                Expr::Path(func) if func.qself.is_none() => {
+                    let segments = &func.path.segments;
+                    match segments
+                        .len()
+                        .checked_sub(2)
+                        .map(|i| (&segments[i], &segments[i + 1]))
+                    {
+                        Some((module, name)) if module.ident == "future" => name.ident.clone(),
+                        _ => return folder.fold_expr(Expr::Call(call)),
+                    }
+                }
+                _ => return folder.fold_expr(Expr::Call(call)),
+            };
+            if !is_combinator(&name) {
+                return folder.fold_expr(Expr::Call(call));
+            }
+            let mut args = call.args.into_iter();
+            (name, args.next().unwrap(), args.next().unwrap())
+        }
+        // `a.zip(b)`, `a.race(b)`, etc. from `FutureExt`
+        Expr::MethodCall(call) if call.args.len() == 1 && is_combinator(&call.method) => (
+            call.method,
+            *call.receiver,
+            call.args.into_iter().next().unwrap(),
+        ),
+        e => return folder.fold_expr(e),
+    };
+
+    let span = name.span();
+    match name.to_string().as_str() {
+        // both futures run to completion, one after the other
+        "zip" => Expr::Tuple(ExprTuple {
+            attrs: Vec::new(),
+            paren_token: syn::token::Paren(span),
+            elems: Punctuated::from_iter([folder.fold_expr(a), folder.fold_expr(b)]),
+        }),
+        // `or` prefers the first future, so it always wins when run blocking
+        "or" => parenthesized(folder.fold_expr(a), span),
+        _ => match race {
+            Race::First => parenthesized(folder.fold_expr(a), span),
+            Race::Second => parenthesized(folder.fold_expr(b), span),
+            Race::Error => Expr::Verbatim(quote_spanned! {span=>
+                compile_error!("`race` can't be made blocking; pass `race = first` or `race = second` to pick the branch to keep")
+            }),
+        },
+    }
+}
+
+fn is_combinator(name: &Ident) -> bool {
+    name == "zip" || name == "race" || name == "or"
+}
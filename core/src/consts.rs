@@ -0,0 +1,39 @@
+use syn::{
+    fold::{self, Fold},
+    Block, Expr, Item,
+};
+
+/// Replaces the awaits in `e`, which is evaluated at compile time (like a const block, an array length or an enum
+/// discriminant), with errors pointing at them. Removing them would turn code that can't compile into code that can,
+/// so the blocking build would accept what the async one rejects.
+pub(crate) fn check(e: Expr) -> Expr {
+    ConstAwaits.fold_expr(e)
+}
+
+/// Same as `check`, for the block of `const { ... }`.
+pub(crate) fn check_block(b: Block) -> Block {
+    ConstAwaits.fold_block(b)
+}
+
+struct ConstAwaits;
+
+impl Fold for ConstAwaits {
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Await(e) => Expr::Verbatim(
+                syn::Error::new_spanned(
+                    &e,
+                    "`.await` can't be used here, since it's evaluated at compile time; the async build rejects it too",
+                )
+                .to_compile_error(),
+            ),
+            // futures and closures created at compile time can await whatever they like
+            Expr::Async(_) | Expr::Closure(_) => e,
+            e => fold::fold_expr(self, e),
+        }
+    }
+
+    fn fold_item(&mut self, i: Item) -> Item {
+        i
+    }
+}
@@ -0,0 +1,77 @@
+use quote::{format_ident, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote_spanned,
+    spanned::Spanned,
+    Expr, Token,
+};
+
+/// The blocking delay that embassy-time's timers are rewritten to, from `delay = ...`, like `std::thread::sleep` or
+/// `|duration: core::time::Duration| delay.delay_us(duration.as_micros() as u32)`. It's called with a
+/// `core::time::Duration`.
+pub(crate) struct Delay {
+    delay: Expr,
+}
+
+impl Parse for Delay {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![=]>()?;
+        Ok(Delay {
+            delay: input.parse()?,
+        })
+    }
+}
+
+impl Delay {
+    /// If `e` waits on embassy-time, like `Timer::after(duration)`, `Timer::after_millis(10)` or
+    /// `Delay.delay_ms(10)`, returns the call to the delay that waits as long.
+    pub(crate) fn call(&self, e: &Expr) -> Option<Expr> {
+        let span = e.span();
+        let duration = quote_spanned!(span=> ::core::time::Duration);
+        let duration: Expr = match e {
+            // embassy-time's own `Duration` converts to core's
+            Expr::Call(call) if crate::calls(call, &["Timer", "after"]) => {
+                let arg = &call.args[0];
+                parse_quote_spanned!(span=> ::core::convert::Into::<#duration>::into(#arg))
+            }
+            Expr::Call(call) => {
+                let unit = ["secs", "millis", "micros", "nanos"]
+                    .into_iter()
+                    .find(|unit| crate::calls(call, &["Timer", &format!("after_{}", unit)]))?;
+                let (constructor, arg) =
+                    (format_ident!("from_{}", unit, span = span), &call.args[0]);
+                parse_quote_spanned!(span=> #duration::#constructor(#arg))
+            }
+            // embassy-time's `Delay` is a unit struct, so it's used as a value, and its methods take a `u32`
+            Expr::MethodCall(call)
+                if call.args.len() == 1
+                    && matches!(&*call.receiver, Expr::Path(receiver) if receiver.path.segments.last().is_some_and(|segment| segment.ident == "Delay")) =>
+            {
+                let unit = match call.method.to_string().as_str() {
+                    "delay_ms" => "millis",
+                    "delay_us" => "micros",
+                    "delay_ns" => "nanos",
+                    _ => return None,
+                };
+                let (constructor, arg) =
+                    (format_ident!("from_{}", unit, span = span), &call.args[0]);
+                parse_quote_spanned!(span=> #duration::#constructor(
+                    <::core::primitive::u64 as ::core::convert::From<::core::primitive::u32>>::from(#arg)
+                ))
+            }
+            _ => return None,
+        };
+        Some(match &self.delay {
+            // a closure is written out, so it isn't called where it's declared
+            Expr::Closure(closure) if closure.inputs.len() == 1 => {
+                let (input, body) = (&closure.inputs[0], &closure.body);
+                parse_quote_spanned!(span=> {
+                    let #input = #duration;
+                    #body
+                })
+            }
+            Expr::Path(delay) => parse_quote_spanned!(span=> #delay(#duration)),
+            delay => parse_quote_spanned!(span=> (#delay)(#duration)),
+        })
+    }
+}
@@ -0,0 +1,54 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use proc_macro2::TokenStream;
+use syn::Item;
+
+/// The name of the dump for `input`, which is the name of the item.
+pub(crate) fn item_name(input: &TokenStream) -> String {
+    let ident = match syn::parse2::<Item>(input.clone()) {
+        Ok(Item::Fn(item)) => item.sig.ident,
+        Ok(Item::Trait(item)) => item.ident,
+        Ok(Item::Struct(item)) => item.ident,
+        Ok(Item::Type(item)) => item.ident,
+        Ok(Item::Static(item)) => item.ident,
+        Ok(Item::Const(item)) => item.ident,
+        Ok(Item::Mod(item)) => item.ident,
+        Ok(Item::Impl(item)) => match &*item.self_ty {
+            syn::Type::Path(ty) => match ty.path.segments.last() {
+                Some(segment) => segment.ident.clone(),
+                None => return "impl".to_owned(),
+            },
+            _ => return "impl".to_owned(),
+        },
+        _ => return "item".to_owned(),
+    };
+    ident.to_string()
+}
+
+/// Writes the output to `target/remove-async-await/{name}.rs`, formatted with prettyplease.
+pub(crate) fn write(name: &str, output: &TokenStream) {
+    let dir = target_dir().join("remove-async-await");
+    let formatted = crate::format::format(output);
+    let path = dir.join(format!("{}.rs", name));
+    if let Err(err) = fs::create_dir_all(&dir).and_then(|_| fs::write(&path, formatted)) {
+        println!("Failed to write {}: {}", path.display(), err);
+    } else {
+        println!("Output written to {}", path.display());
+    }
+}
+
+/// `CARGO_TARGET_DIR`, or the closest `target` directory above the crate being compiled.
+fn target_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os("CARGO_TARGET_DIR") {
+        return dir.into();
+    }
+    let manifest_dir = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default());
+    manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("target"))
+        .find(|dir| dir.is_dir())
+        .unwrap_or_else(|| Path::new(&manifest_dir).join("target"))
+}
@@ -0,0 +1,142 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use syn::{
+    parse::Parse, Item, ItemConst, ItemFn, ItemImpl, ItemStatic, ItemStruct, ItemTrait, ItemType,
+    TraitItemFn,
+};
+
+const SUPPORTED: &str = "remove_async_await currently only supports functions, trait methods, type aliases, structs, traits, impls, statics and consts";
+
+/// The supported kinds of items, in the order they're parsed.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Fn,
+    TraitMethod,
+    Type,
+    Struct,
+    Trait,
+    Impl,
+    Static,
+    Const,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Fn => "a function",
+            Kind::TraitMethod => "a trait method",
+            Kind::Type => "a type alias",
+            Kind::Struct => "a struct",
+            Kind::Trait => "a trait",
+            Kind::Impl => "an impl block",
+            Kind::Static => "a static",
+            Kind::Const => "a const",
+        }
+    }
+}
+
+/// The error for input that isn't one of the supported items. It says what the input looks like, and lists the errors
+/// from parsing it as each supported item. If it looks like a supported item, the error from parsing it as that item is
+/// emitted too, pointing at the token that broke parsing.
+pub(crate) fn unsupported(input: TokenStream) -> TokenStream {
+    let attempts = [
+        (Kind::Fn, attempt::<ItemFn>(&input)),
+        (Kind::TraitMethod, attempt::<TraitItemFn>(&input)),
+        (Kind::Type, attempt::<ItemType>(&input)),
+        (Kind::Struct, attempt::<ItemStruct>(&input)),
+        (Kind::Trait, attempt::<ItemTrait>(&input)),
+        (Kind::Impl, attempt::<ItemImpl>(&input)),
+        (Kind::Static, attempt::<ItemStatic>(&input)),
+        (Kind::Const, attempt::<ItemConst>(&input)),
+    ];
+    let mut details = String::from("\n\nparsing it as each of them failed:");
+    for (kind, err) in &attempts {
+        if let Some(err) = err {
+            details.push_str(&format!("\n- as {}: {}", kind.name(), err));
+        }
+    }
+
+    let (message, spanned) = match syn::parse2::<Item>(input.clone()) {
+        Ok(item) => match item_name(&item) {
+            Some(name) => (format!("{}, but the input is {}", SUPPORTED, name), None),
+            None => (SUPPORTED.to_owned(), None),
+        },
+        Err(item_err) => match detect(input) {
+            Some(kind) => {
+                let err = attempts
+                    .into_iter()
+                    .find(|(attempted, _)| *attempted == kind)
+                    .and_then(|(_, err)| err);
+                (
+                    format!(
+                        "the input looks like {}, but it couldn't be parsed as one",
+                        kind.name()
+                    ),
+                    err,
+                )
+            }
+            None => (
+                format!(
+                    "{}, and the input couldn't be parsed as any item",
+                    SUPPORTED
+                ),
+                Some(item_err),
+            ),
+        },
+    };
+    let mut err = syn::Error::new(Span::call_site(), message + &details);
+    if let Some(spanned) = spanned {
+        err.combine(spanned);
+    }
+    err.to_compile_error()
+}
+
+fn attempt<T: Parse>(input: &TokenStream) -> Option<syn::Error> {
+    syn::parse2::<T>(input.clone()).err()
+}
+
+/// Names an unsupported item, like `enum Value {}`.
+fn item_name(item: &Item) -> Option<&'static str> {
+    Some(match item {
+        Item::Enum(_) => "an enum",
+        Item::ExternCrate(_) => "an extern crate",
+        Item::ForeignMod(_) => "an extern block",
+        Item::Macro(_) => "a macro",
+        Item::Mod(_) => "a module (use `mirror` to transform a whole module)",
+        Item::TraitAlias(_) => "a trait alias",
+        Item::Union(_) => "a union",
+        Item::Use(_) => "a use declaration",
+        _ => return None,
+    })
+}
+
+/// Guesses what the input is meant to be from its first keyword, after its attributes and visibility.
+fn detect(input: TokenStream) -> Option<Kind> {
+    let mut tokens = input.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            // attributes, and the group of `pub(crate)`
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                tokens.next();
+            }
+            TokenTree::Group(_) => {}
+            TokenTree::Ident(ident) => match ident.to_string().as_str() {
+                "pub" | "async" | "unsafe" | "extern" | "default" => {}
+                "const" => {
+                    let is_fn = matches!(tokens.peek(), Some(TokenTree::Ident(next)) if next == "fn" || next == "async" || next == "unsafe");
+                    return Some(if is_fn { Kind::Fn } else { Kind::Const });
+                }
+                "fn" => return Some(Kind::Fn),
+                "type" => return Some(Kind::Type),
+                "struct" => return Some(Kind::Struct),
+                "trait" => return Some(Kind::Trait),
+                "impl" => return Some(Kind::Impl),
+                "static" => return Some(Kind::Static),
+                _ => return None,
+            },
+            // the ABI of `extern "C" fn`
+            TokenTree::Literal(_) => {}
+            TokenTree::Punct(_) => return None,
+        }
+    }
+    None
+}
@@ -0,0 +1,69 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+
+/// Parts of identifiers the transform could rewrite something for, case insensitively: the keywords, future, stream
+/// and sink types (like `BoxFuture`, `Ready` or `TryStreamExt`), IO traits like `AsyncRead`, async crates like `futures`
+/// and `async_std`, attributes like `#[async_trait]`, and spawned tasks.
+const TRIGGERS: &[&str] = &[
+    "async", "await", "future", "stream", "sink", "spawn", "ready",
+];
+
+/// Identifiers the transform could rewrite something for, apart from the ones matching `TRIGGERS`.
+const IDENTS: &[&str] = &["tokio", "JoinHandle", "sync_only", "replace_with", "keep"];
+
+/// Item keywords the transform supports, after the attributes, visibility and qualifiers like `unsafe`.
+const ITEMS: &[&str] = &["fn", "type", "struct", "trait", "impl", "static", "const"];
+
+/// Whether the transform would leave `input` as it is, since it's an item the transform supports and nothing in it is
+/// async. The check is on tokens alone, so it doesn't have to parse, fold or print the input, and errs on the side of
+/// parsing it: an item using a name like `is_async` still goes through the transform. Literals aren't checked. An
+/// untouched item with a syntax error is left for rustc to report.
+pub(crate) fn untouched(input: &TokenStream) -> bool {
+    supported(input) && idents_untouched(input)
+}
+
+fn idents_untouched(input: &TokenStream) -> bool {
+    input.clone().into_iter().all(|token| match token {
+        TokenTree::Ident(ident) => {
+            let ident = ident.to_string();
+            let lowercase = ident.to_lowercase();
+            !TRIGGERS.iter().any(|trigger| lowercase.contains(trigger))
+                && !IDENTS.contains(&ident.as_str())
+        }
+        TokenTree::Group(group) => idents_untouched(&group.stream()),
+        _ => true,
+    })
+}
+
+/// Whether `input` starts like an item the transform supports, so anything else still gets its error from the parse.
+fn supported(input: &TokenStream) -> bool {
+    let mut tokens = input.clone().into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            // an attribute
+            TokenTree::Punct(punct) if punct.as_char() == '#' => {
+                if !matches!(tokens.next(), Some(TokenTree::Group(_))) {
+                    return false;
+                }
+            }
+            TokenTree::Ident(ident) => match ident.to_string().as_str() {
+                // `pub(crate)` and the like
+                "pub" => {
+                    if matches!(tokens.peek(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis)
+                    {
+                        tokens.next();
+                    }
+                }
+                "unsafe" | "default" | "auto" => {}
+                // the ABI of `extern "C" fn`
+                "extern" => {
+                    if matches!(tokens.peek(), Some(TokenTree::Literal(_))) {
+                        tokens.next();
+                    }
+                }
+                keyword => return ITEMS.contains(&keyword),
+            },
+            _ => return false,
+        }
+    }
+    false
+}
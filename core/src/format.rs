@@ -0,0 +1,60 @@
+use proc_macro2::TokenStream;
+use syn::{
+    visit::{self, Visit},
+    Expr, ForeignItem, ImplItem, Item, Pat, TraitItem, Type,
+};
+
+/// Formats the output with prettyplease if it parses as a file, or as plain tokens if it doesn't.
+pub(crate) fn format(output: &TokenStream) -> String {
+    match syn::parse2::<syn::File>(output.clone()) {
+        Ok(file) if printable(&file) => prettyplease::unparse(&file),
+        _ => output.to_string(),
+    }
+}
+
+/// Whether prettyplease can print the file, since it panics on verbatim syntax, like body-less functions.
+fn printable(file: &syn::File) -> bool {
+    #[derive(Default)]
+    struct Verbatim(bool);
+
+    impl<'ast> Visit<'ast> for Verbatim {
+        fn visit_item(&mut self, i: &'ast Item) {
+            self.0 |= matches!(i, Item::Verbatim(_));
+            visit::visit_item(self, i);
+        }
+
+        fn visit_expr(&mut self, i: &'ast Expr) {
+            self.0 |= matches!(i, Expr::Verbatim(_));
+            visit::visit_expr(self, i);
+        }
+
+        fn visit_type(&mut self, i: &'ast Type) {
+            self.0 |= matches!(i, Type::Verbatim(_));
+            visit::visit_type(self, i);
+        }
+
+        fn visit_pat(&mut self, i: &'ast Pat) {
+            self.0 |= matches!(i, Pat::Verbatim(_));
+            visit::visit_pat(self, i);
+        }
+
+        fn visit_trait_item(&mut self, i: &'ast TraitItem) {
+            self.0 |= matches!(i, TraitItem::Verbatim(_));
+            visit::visit_trait_item(self, i);
+        }
+
+        fn visit_impl_item(&mut self, i: &'ast ImplItem) {
+            self.0 |= matches!(i, ImplItem::Verbatim(_));
+            visit::visit_impl_item(self, i);
+        }
+
+        fn visit_foreign_item(&mut self, i: &'ast ForeignItem) {
+            self.0 |= matches!(i, ForeignItem::Verbatim(_));
+            visit::visit_foreign_item(self, i);
+        }
+    }
+
+    let mut verbatim = Verbatim::default();
+    verbatim.visit_file(file);
+    !verbatim.0
+}
@@ -0,0 +1,158 @@
+use syn::{
+    parse_quote_spanned, punctuated::Punctuated, spanned::Spanned, FnArg, GenericParam, Ident, Pat,
+    PredicateType, Signature, Token, Type, TypeParamBound, WhereClause, WherePredicate,
+};
+
+use crate::types;
+
+/// Generic parameters of a function that were bounded by `Future`.
+#[derive(Default)]
+pub(crate) struct FutureParams {
+    /// Parameters that were removed, and the type each one resolves to.
+    pub(crate) resolved: Vec<(Ident, Type)>,
+    /// Arguments whose type became a closure, so awaiting them should call them instead.
+    pub(crate) closure_args: Vec<Ident>,
+    /// Arguments that were futures and now take the future's output.
+    pub(crate) resolved_args: Vec<Ident>,
+}
+
+/// Rewrites generic parameters like `F: Future<Output = T>`, and arguments like `f: impl Future<Output = T>`.
+/// Parameters and arguments listed in `closure` become `F: FnOnce() -> T` and `f: impl FnOnce() -> T`; the rest are
+/// removed so that arguments of type `F` take a `T` instead.
+pub(crate) fn rewrite_future_params(sig: &mut Signature, closure: &[Ident]) -> FutureParams {
+    let mut params = FutureParams::default();
+    let mut closure_params = Vec::new();
+
+    let generics = std::mem::take(&mut sig.generics.params);
+    for param in generics {
+        let mut ty = match param {
+            GenericParam::Type(ty) => ty,
+            param => {
+                sig.generics.params.push(param);
+                continue;
+            }
+        };
+        // the `Future` bound can either be on the parameter or in the where clause
+        let output = types::future_bounds_output(&ty.bounds).or_else(|| {
+            predicates_for(&mut sig.generics.where_clause, &ty.ident)
+                .find_map(|predicate| types::future_bounds_output(&predicate.bounds))
+        });
+        let output = match output {
+            Some(output) => output,
+            None => {
+                sig.generics.params.push(GenericParam::Type(ty));
+                continue;
+            }
+        };
+
+        if closure.contains(&ty.ident) {
+            ty.bounds = closure_bounds(ty.bounds, &output);
+            for predicate in predicates_for(&mut sig.generics.where_clause, &ty.ident) {
+                predicate.bounds = closure_bounds(std::mem::take(&mut predicate.bounds), &output);
+            }
+            closure_params.push(ty.ident.clone());
+            sig.generics.params.push(GenericParam::Type(ty));
+        } else {
+            // the parameter is gone, so its other bounds (like `F: Send`) have to go too
+            if let Some(where_clause) = &mut sig.generics.where_clause {
+                where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+                    .into_iter()
+                    .filter(|predicate| !bounds_param(predicate, &ty.ident))
+                    .collect();
+            }
+            params.resolved.push((ty.ident, output));
+        }
+    }
+    if sig.generics.params.is_empty() {
+        sig.generics.lt_token = None;
+        sig.generics.gt_token = None;
+    }
+    if matches!(&sig.generics.where_clause, Some(where_clause) if where_clause.predicates.is_empty())
+    {
+        sig.generics.where_clause = None;
+    }
+
+    for arg in &mut sig.inputs {
+        let arg = match arg {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(_) => continue,
+        };
+        let ident = match &*arg.pat {
+            Pat::Ident(pat) => &pat.ident,
+            _ => continue,
+        };
+        match &mut *arg.ty {
+            Type::Path(ty)
+                if ty.qself.is_none()
+                    && closure_params.iter().any(|param| ty.path.is_ident(param)) =>
+            {
+                params.closure_args.push(ident.clone());
+            }
+            Type::Path(ty)
+                if ty.qself.is_none()
+                    && params
+                        .resolved
+                        .iter()
+                        .any(|(param, _)| ty.path.is_ident(param)) =>
+            {
+                params.resolved_args.push(ident.clone());
+            }
+            // `f: impl Future<Output = T>`
+            Type::ImplTrait(ty) => {
+                let output = match types::future_bounds_output(&ty.bounds) {
+                    Some(output) => output,
+                    None => continue,
+                };
+                if closure.contains(ident) {
+                    ty.bounds = closure_bounds(std::mem::take(&mut ty.bounds), &output);
+                    params.closure_args.push(ident.clone());
+                } else {
+                    *arg.ty = output;
+                    params.resolved_args.push(ident.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    params
+}
+
+/// Returns the where clause predicates bounding the generic parameter `param`, like `F: Send`.
+fn predicates_for<'a>(
+    where_clause: &'a mut Option<WhereClause>,
+    param: &'a Ident,
+) -> impl Iterator<Item = &'a mut PredicateType> {
+    where_clause
+        .iter_mut()
+        .flat_map(|where_clause| where_clause.predicates.iter_mut())
+        .filter_map(move |predicate| match predicate {
+            WherePredicate::Type(predicate) if is_param(&predicate.bounded_ty, param) => {
+                Some(predicate)
+            }
+            _ => None,
+        })
+}
+
+/// Whether `predicate` bounds the generic parameter `param`.
+fn bounds_param(predicate: &WherePredicate, param: &Ident) -> bool {
+    matches!(predicate, WherePredicate::Type(predicate) if is_param(&predicate.bounded_ty, param))
+}
+
+fn is_param(ty: &Type, param: &Ident) -> bool {
+    matches!(ty, Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident(param))
+}
+
+/// Replaces the `Future<Output = T>` bound in `bounds` with `FnOnce() -> T`.
+fn closure_bounds(
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+    output: &Type,
+) -> Punctuated<TypeParamBound, Token![+]> {
+    bounds
+        .into_iter()
+        .map(|bound| match types::future_bounds_output([&bound]) {
+            Some(_) => parse_quote_spanned!(bound.span()=> FnOnce() -> #output),
+            None => bound,
+        })
+        .collect()
+}
@@ -0,0 +1,239 @@
+use proc_macro2::Span;
+use syn::{
+    fold::{self, Fold},
+    parse_quote, BinOp, Block, Expr, ExprAssign, ExprAwait, ExprBinary, ExprBlock, ExprForLoop,
+    ExprIf, ExprMatch, ExprParen, Ident, Item, Local, LocalInit, Stmt, UnOp,
+};
+
+use crate::keep;
+
+/// Hoists the awaited expressions in `stmt` into `let` bindings, from `hoist_awaits`, so their temporaries (like lock
+/// guards) are dropped before the rest of the statement runs instead of at its end. The statement and its bindings are
+/// wrapped in a block, so the values they resolve to are still dropped at the end of the statement, like in the async
+/// item.
+///
+/// Only the awaits that run first in the statement are hoisted, so nothing runs in a different order: in
+/// `a() + b().await`, `b()` stays where it is, since `a()` runs before it. Awaits that only run sometimes, like the
+/// ones in `if` branches or closures, stay where they are too, and so do the ones in nested blocks, which are hoisted
+/// within their block. Borrowed awaits like `let x = &foo().await;` stay as well, since the borrow extends the
+/// value's lifetime to the enclosing block, which a binding in the statement's block wouldn't live for. Statements with
+/// attributes are left alone, since they could be kept as is.
+pub(crate) fn hoist(stmt: Stmt) -> Stmt {
+    let mut hoister = Hoister {
+        bindings: Vec::new(),
+        stopped: false,
+    };
+    match stmt {
+        Stmt::Local(local) if local.attrs.is_empty() => {
+            let Some(init) = local.init else {
+                return Stmt::Local(local);
+            };
+            let mut expr = hoister.fold_outer(*init.expr);
+            if !hoister.bindings.is_empty() {
+                expr = hoister.scoped(Stmt::Expr(expr, None));
+                // a block can't come right before the `else` of `let ... else`
+                if init.diverge.is_some() {
+                    expr = Expr::Paren(ExprParen {
+                        attrs: Vec::new(),
+                        paren_token: Default::default(),
+                        expr: Box::new(expr),
+                    });
+                }
+            }
+            Stmt::Local(Local {
+                init: Some(LocalInit {
+                    expr: Box::new(expr),
+                    ..init
+                }),
+                ..local
+            })
+        }
+        Stmt::Expr(mut e, semi) => {
+            if keep::expr_attrs(&mut e).is_none_or(|attrs| attrs.is_empty()) {
+                e = hoister.fold_outer(e);
+            }
+            if hoister.bindings.is_empty() {
+                return Stmt::Expr(e, semi);
+            }
+            // the `;` goes in the block, which doesn't need one after it
+            Stmt::Expr(hoister.scoped(Stmt::Expr(e, semi)), None)
+        }
+        stmt => stmt,
+    }
+}
+
+struct Hoister {
+    bindings: Vec<Stmt>,
+    /// Whether something that isn't hoisted has run already, so the awaits after it can't be hoisted before it.
+    stopped: bool,
+}
+
+impl Hoister {
+    /// Hoists the awaits in `e`, apart from `e` itself if it's awaited, which would only move it to another `let`.
+    fn fold_outer(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Await(e) => Expr::Await(ExprAwait {
+                base: Box::new(self.fold_expr(*e.base)),
+                ..e
+            }),
+            e => self.fold_expr(e),
+        }
+    }
+
+    /// Wraps the bindings and `stmt` in a block, so they're dropped along with the statement.
+    fn scoped(&mut self, stmt: Stmt) -> Expr {
+        let mut stmts = std::mem::take(&mut self.bindings);
+        stmts.push(stmt);
+        Expr::Block(ExprBlock {
+            attrs: Vec::new(),
+            label: None,
+            block: Block {
+                brace_token: Default::default(),
+                stmts,
+            },
+        })
+    }
+}
+
+/// Whether evaluating `e` can't run anything, so it doesn't matter whether an await is hoisted before it. Operators
+/// and indexing could call the user's trait impls, so they count as running something, apart from dereferencing.
+fn is_inert(e: &Expr) -> bool {
+    match e {
+        Expr::Lit(_) | Expr::Path(_) | Expr::Closure(_) | Expr::Async(_) | Expr::Const(_) => true,
+        Expr::Field(e) => is_inert(&e.base),
+        Expr::Reference(e) => is_inert(&e.expr),
+        Expr::Paren(e) => is_inert(&e.expr),
+        Expr::Group(e) => is_inert(&e.expr),
+        Expr::Cast(e) => is_inert(&e.expr),
+        Expr::Unary(e) if matches!(e.op, UnOp::Deref(_)) => is_inert(&e.expr),
+        Expr::Tuple(e) => e.elems.iter().all(is_inert),
+        Expr::Array(e) => e.elems.iter().all(is_inert),
+        Expr::Struct(e) => {
+            e.fields.iter().all(|field| is_inert(&field.expr)) && e.rest.as_deref().is_none_or(is_inert)
+        }
+        Expr::Range(e) => {
+            e.start.as_deref().is_none_or(is_inert) && e.end.as_deref().is_none_or(is_inert)
+        }
+        _ => false,
+    }
+}
+
+/// Whether `e` is an awaited value, or a place in one like `foo().await.field`, whose lifetime a borrow could extend.
+fn borrows_await(e: &Expr) -> bool {
+    match e {
+        Expr::Await(_) => true,
+        Expr::Field(e) => borrows_await(&e.base),
+        Expr::Index(e) => borrows_await(&e.expr),
+        Expr::Paren(e) => borrows_await(&e.expr),
+        Expr::Group(e) => borrows_await(&e.expr),
+        _ => false,
+    }
+}
+
+impl Fold for Hoister {
+    fn fold_expr(&mut self, e: Expr) -> Expr {
+        if self.stopped {
+            return e;
+        }
+        let e = match e {
+            Expr::Await(e) if e.attrs.is_empty() => {
+                // everything that ran before is hoisted, and so is the base along with it
+                let base = self.fold_expr(*e.base);
+                self.stopped = false;
+                let await_token = e.await_token;
+                let dot_token = e.dot_token;
+                let name = Ident::new(
+                    &format!("__awaited_{}", self.bindings.len()),
+                    Span::mixed_site(),
+                );
+                // mutable in case the value is used that way, like the guard in `*counter.lock().await += 1`
+                self.bindings.push(parse_quote! {
+                    #[allow(unused_mut)]
+                    let mut #name = #base #dot_token #await_token;
+                });
+                return parse_quote!(#name);
+            }
+            // the borrow could extend the awaited value's lifetime past the statement
+            Expr::Reference(reference) if borrows_await(&reference.expr) => Expr::Reference(reference),
+            // only run sometimes, or more than once
+            Expr::Async(_)
+            | Expr::Block(_)
+            | Expr::Closure(_)
+            | Expr::Const(_)
+            | Expr::Loop(_)
+            | Expr::Macro(_)
+            | Expr::TryBlock(_)
+            | Expr::Unsafe(_)
+            | Expr::While(_) => e,
+            Expr::If(e) => Expr::If(ExprIf {
+                cond: Box::new(self.fold_expr(*e.cond)),
+                ..e
+            }),
+            Expr::Match(e) => Expr::Match(ExprMatch {
+                expr: Box::new(self.fold_expr(*e.expr)),
+                ..e
+            }),
+            Expr::ForLoop(e) => Expr::ForLoop(ExprForLoop {
+                expr: Box::new(self.fold_expr(*e.expr)),
+                ..e
+            }),
+            Expr::Binary(e) if matches!(e.op, BinOp::And(_) | BinOp::Or(_)) => {
+                Expr::Binary(ExprBinary {
+                    left: Box::new(self.fold_expr(*e.left)),
+                    ..e
+                })
+            }
+            // the value is evaluated before the place it's assigned to
+            Expr::Assign(e) => {
+                let right = self.fold_expr(*e.right);
+                Expr::Assign(ExprAssign {
+                    left: Box::new(self.fold_expr(*e.left)),
+                    right: Box::new(right),
+                    ..e
+                })
+            }
+            // which side of `+=` and the like is evaluated first depends on the types, so only one side is hoisted from,
+            // when the other doesn't run anything
+            Expr::Binary(e) if is_compound(&e.op) => {
+                if is_inert(&e.right) {
+                    Expr::Binary(ExprBinary {
+                        left: Box::new(self.fold_expr(*e.left)),
+                        ..e
+                    })
+                } else if is_inert(&e.left) {
+                    Expr::Binary(ExprBinary {
+                        right: Box::new(self.fold_expr(*e.right)),
+                        ..e
+                    })
+                } else {
+                    Expr::Binary(e)
+                }
+            }
+            e => fold::fold_expr(self, e),
+        };
+        if !is_inert(&e) {
+            self.stopped = true;
+        }
+        e
+    }
+
+    fn fold_item(&mut self, i: Item) -> Item {
+        i
+    }
+}
+
+fn is_compound(op: &BinOp) -> bool {
+    matches!(
+        op,
+        BinOp::AddAssign(_)
+            | BinOp::SubAssign(_)
+            | BinOp::MulAssign(_)
+            | BinOp::DivAssign(_)
+            | BinOp::RemAssign(_)
+            | BinOp::BitXorAssign(_)
+            | BinOp::BitAndAssign(_)
+            | BinOp::BitOrAssign(_)
+            | BinOp::ShlAssign(_)
+            | BinOp::ShrAssign(_)
+    )
+}
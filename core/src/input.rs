@@ -0,0 +1,63 @@
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    token, Abi, Attribute, Block, Item, ItemFn, Signature, TraitItemFn, Visibility,
+};
+
+/// The item an attribute is on, parsed once and dispatched on by kind.
+pub(crate) enum Input {
+    /// Any item, including methods in impls and trait methods with a body, which parse as functions.
+    Item(Item),
+    /// A trait method without a body, like `async fn get(&self) -> u32;`.
+    TraitMethod(TraitItemFn),
+}
+
+impl Input {
+    /// Whether the input is a single function or method.
+    pub(crate) fn is_fn(&self) -> bool {
+        matches!(self, Input::Item(Item::Fn(_)) | Input::TraitMethod(_))
+    }
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // look past the attributes and visibility to see whether it's a function
+        let ahead = input.fork();
+        ahead.call(Attribute::parse_outer)?;
+        ahead.parse::<Visibility>()?;
+        if !is_fn(&ahead) {
+            return input.parse().map(Input::Item);
+        }
+
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let vis: Visibility = input.parse()?;
+        let sig: Signature = input.parse()?;
+        if let (Visibility::Inherited, true) = (&vis, input.peek(token::Semi)) {
+            return Ok(Input::TraitMethod(TraitItemFn {
+                attrs,
+                sig,
+                default: None,
+                semi_token: Some(input.parse()?),
+            }));
+        }
+        let content;
+        let brace_token = braced!(content in input);
+        attrs.extend(content.call(Attribute::parse_inner)?);
+        let stmts = content.call(Block::parse_within)?;
+        Ok(Input::Item(Item::Fn(ItemFn {
+            attrs,
+            vis,
+            sig,
+            block: Box::new(Block { brace_token, stmts }),
+        })))
+    }
+}
+
+/// Whether a function signature starts here, like `const async unsafe extern "C" fn`.
+fn is_fn(input: ParseStream) -> bool {
+    input.parse::<Option<token::Const>>().is_ok()
+        && input.parse::<Option<token::Async>>().is_ok()
+        && input.parse::<Option<token::Unsafe>>().is_ok()
+        && input.parse::<Option<Abi>>().is_ok()
+        && input.peek(token::Fn)
+}
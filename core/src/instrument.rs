@@ -0,0 +1,73 @@
+use proc_macro2::{Group, TokenStream, TokenTree};
+use quote::quote;
+use syn::{Attribute, Ident, MacroDelimiter, Meta};
+
+/// Rewrites `#[tracing::instrument]` for the blocking function. It's moved after the other attributes, so it expands on
+/// the final function, and arguments that were futures are removed from `skip(...)`, since they're plain values now.
+/// `err` and `ret` are kept: on an async function they record the future's output, and on a blocking one they record
+/// the returned value, which is that output once the function is transformed.
+pub(crate) fn rewrite_instrument(attrs: &mut Vec<Attribute>, resolved_args: &[Ident]) {
+    let (mut instrument, rest): (Vec<_>, Vec<_>) = attrs.drain(..).partition(is_instrument);
+    *attrs = rest;
+    for attr in &mut instrument {
+        if let Meta::List(list) = &mut attr.meta {
+            if let MacroDelimiter::Paren(_) = list.delimiter {
+                list.tokens = strip_skipped(list.tokens.clone(), resolved_args);
+            }
+        }
+    }
+    attrs.extend(instrument);
+}
+
+fn is_instrument(attr: &Attribute) -> bool {
+    let segments = &attr.path().segments;
+    match segments.len() {
+        1 => segments[0].ident == "instrument",
+        2 => segments[0].ident == "tracing" && segments[1].ident == "instrument",
+        _ => false,
+    }
+}
+
+/// Removes `resolved_args` from `skip(...)` in the arguments of `#[instrument(...)]`, removing `skip` entirely if
+/// nothing is left. The arguments can contain field expressions like `fields(id = %id)`, so they're handled as tokens.
+fn strip_skipped(args: TokenStream, resolved_args: &[Ident]) -> TokenStream {
+    let mut output = Vec::<Vec<TokenTree>>::new();
+    for arg in split_commas(args) {
+        match arg.as_slice() {
+            [TokenTree::Ident(key), TokenTree::Group(skipped)] if key == "skip" => {
+                let kept = split_commas(skipped.stream())
+                    .into_iter()
+                    .filter(|skipped| {
+                        !matches!(skipped.as_slice(), [TokenTree::Ident(ident)] if resolved_args.contains(ident))
+                    })
+                    .collect::<Vec<_>>();
+                if kept.is_empty() {
+                    continue;
+                }
+                let kept = join_commas(kept);
+                let mut group = Group::new(skipped.delimiter(), kept);
+                group.set_span(skipped.span());
+                output.push(vec![TokenTree::Ident(key.clone()), TokenTree::Group(group)]);
+            }
+            _ => output.push(arg),
+        }
+    }
+    join_commas(output)
+}
+
+fn split_commas(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut items = vec![Vec::new()];
+    for token in tokens {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => items.push(Vec::new()),
+            _ => items.last_mut().unwrap().push(token),
+        }
+    }
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+fn join_commas(items: Vec<Vec<TokenTree>>) -> TokenStream {
+    let items = items.into_iter().map(TokenStream::from_iter);
+    quote!(#(#items),*)
+}
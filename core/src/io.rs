@@ -0,0 +1,133 @@
+use syn::{
+    parse_quote_spanned, punctuated::Punctuated, token, Ident, Path, Token, TypeParamBound,
+    UseGroup, UsePath, UseTree,
+};
+
+/// The crates with futures' IO traits, whose extension traits are rewritten too.
+const FUTURES: &[&str] = &["futures", "futures_io", "futures_lite", "futures_util"];
+
+/// If `ident` is an async IO trait or one of futures' extension traits, like `AsyncReadExt`, returns the name of the
+/// equivalent `std::io` trait.
+fn std_io_name(ident: &Ident) -> Option<&'static str> {
+    Some(match ident.to_string().as_str() {
+        "AsyncRead" | "AsyncReadExt" => "Read",
+        "AsyncWrite" | "AsyncWriteExt" => "Write",
+        "AsyncSeek" | "AsyncSeekExt" => "Seek",
+        "AsyncBufRead" | "AsyncBufReadExt" => "BufRead",
+        _ => return None,
+    })
+}
+
+/// If `path` is an async IO trait from tokio or futures, like `AsyncRead`, returns the equivalent `std::io` trait.
+fn std_io_trait(path: &Path) -> Option<Path> {
+    let last = path.segments.last()?;
+    if !last.arguments.is_empty() || last.ident.to_string().ends_with("Ext") {
+        return None;
+    }
+    let name = Ident::new(std_io_name(&last.ident)?, last.ident.span());
+    Some(parse_quote_spanned!(name.span()=> ::std::io::#name))
+}
+
+/// Rewrites a path through one of futures' IO traits, like `futures::io::AsyncReadExt::read_to_end`, to go through
+/// the `std::io` trait instead, like `::std::io::Read::read_to_end`.
+pub(crate) fn rewrite_path(path: &mut Path) {
+    let mut segments = path.segments.iter();
+    if !segments
+        .next()
+        .is_some_and(|first| FUTURES.iter().any(|name| first.ident == name))
+    {
+        return;
+    }
+    let Some(position) = segments
+        .position(|segment| std_io_name(&segment.ident).is_some())
+        .map(|position| position + 1)
+    else {
+        return;
+    };
+    let ident = &path.segments[position].ident;
+    let name = Ident::new(std_io_name(ident).unwrap(), ident.span());
+    let mut rewritten: Path = parse_quote_spanned!(name.span()=> ::std::io::#name);
+    rewritten
+        .segments
+        .extend(path.segments.iter().skip(position + 1).cloned());
+    *path = rewritten;
+}
+
+/// Rewrites the imports of futures' IO traits in a `use` tree, like `use futures::io::{AsyncReadExt, Cursor};`, to
+/// import the `std::io` traits under the same names, like `use {futures::io::Cursor, std::io::Read as AsyncReadExt};`,
+/// so their methods are called on the blocking IO objects and calls through the traits' names keep working.
+pub(crate) fn rewrite_use(tree: &mut UseTree) {
+    let mut traits = Vec::new();
+    let rest = take_traits(tree.clone(), None, &mut traits);
+    if traits.is_empty() {
+        return;
+    }
+    *tree = match rest {
+        None if traits.len() == 1 => traits.pop().unwrap(),
+        rest => UseTree::Group(UseGroup {
+            brace_token: token::Brace::default(),
+            items: rest.into_iter().chain(traits).collect(),
+        }),
+    };
+}
+
+/// Removes the imports of futures' IO traits from `tree`, adding the `std::io` imports that replace them to `traits`.
+/// `futures` is whether the tree is in one of the futures crates, or `None` at the root.
+fn take_traits(tree: UseTree, futures: Option<bool>, traits: &mut Vec<UseTree>) -> Option<UseTree> {
+    let (ident, rename) = match tree {
+        UseTree::Path(path) => {
+            let futures = futures.unwrap_or_else(|| FUTURES.iter().any(|name| path.ident == name));
+            return take_traits(*path.tree, Some(futures), traits).map(|tree| {
+                UseTree::Path(UsePath {
+                    tree: Box::new(tree),
+                    ..path
+                })
+            });
+        }
+        UseTree::Group(group) => {
+            let len = group.items.len();
+            let items: Punctuated<_, _> = group
+                .items
+                .into_iter()
+                .filter_map(|tree| take_traits(tree, futures, traits))
+                .collect();
+            return (items.len() == len || !items.is_empty())
+                .then_some(UseTree::Group(UseGroup { items, ..group }));
+        }
+        UseTree::Name(name) if futures == Some(true) && std_io_name(&name.ident).is_some() => {
+            (name.ident.clone(), name.ident)
+        }
+        UseTree::Rename(rename)
+            if futures == Some(true) && std_io_name(&rename.ident).is_some() =>
+        {
+            (rename.ident, rename.rename)
+        }
+        tree => return Some(tree),
+    };
+    let span = ident.span();
+    let name = Ident::new(std_io_name(&ident).unwrap(), span);
+    traits.push(parse_quote_spanned!(span=> std::io::#name as #rename));
+    None
+}
+
+/// Rewrites async IO traits in `bounds`, like `AsyncRead + Unpin`, to `std::io` traits. `Unpin` is removed along with
+/// them, since it's only needed to call the async traits' methods.
+pub(crate) fn rewrite_bounds(bounds: &mut Punctuated<TypeParamBound, Token![+]>) {
+    let mut rewritten = false;
+    for bound in bounds.iter_mut() {
+        if let TypeParamBound::Trait(bound) = bound {
+            if let Some(path) = std_io_trait(&bound.path) {
+                bound.path = path;
+                rewritten = true;
+            }
+        }
+    }
+    if rewritten {
+        *bounds = std::mem::take(bounds)
+            .into_iter()
+            .filter(|bound| {
+                !matches!(bound, TypeParamBound::Trait(bound) if bound.path.segments.last().is_some_and(|segment| segment.ident == "Unpin"))
+            })
+            .collect();
+    }
+}
@@ -0,0 +1,99 @@
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use syn::{
+    parse::{ParseStream, Parser},
+    Attribute, Expr, ImplItem, Item, Local, Path, TraitItem,
+};
+
+/// Returns whether the attribute is `#[remove_async_await::name]`.
+pub(crate) fn is_marker(path: &Path, name: &str) -> bool {
+    let mut segments = path.segments.iter().map(|segment| &segment.ident);
+    matches!(
+        (segments.next(), segments.next(), segments.next()),
+        (Some(krate), Some(marker), None) if krate == "remove_async_await" && marker == name
+    )
+}
+
+/// Removes `#[remove_async_await::keep]` from `attrs`, returning whether it was there.
+fn take(attrs: &mut Vec<Attribute>) -> bool {
+    let len = attrs.len();
+    attrs.retain(|attr| !is_marker(attr.path(), "keep"));
+    attrs.len() != len
+}
+
+macro_rules! attrs {
+    ($value:expr, $ty:ident: $($variant:ident),*) => {
+        match $value {
+            $($ty::$variant(inner) => Some(&mut inner.attrs),)*
+            _ => None,
+        }
+    };
+}
+
+pub(crate) fn item_attrs(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    attrs!(item, Item: Const, Enum, ExternCrate, Fn, ForeignMod, Impl, Macro, Mod, Static, Struct, Trait, TraitAlias, Type, Union, Use)
+}
+
+pub(crate) fn expr_attrs(expr: &mut Expr) -> Option<&mut Vec<Attribute>> {
+    attrs!(expr, Expr: Array, Assign, Async, Await, Binary, Block, Break, Call, Cast, Closure, Const, Continue, Field, ForLoop, Group, If, Index, Infer, Let, Lit, Loop, Macro, Match, MethodCall, Paren, Path, Range, RawAddr, Reference, Repeat, Return, Struct, Try, TryBlock, Tuple, Unary, Unsafe, While, Yield)
+}
+
+/// Removes `#[remove_async_await::keep]` from the item, returning whether it should be left as is.
+pub(crate) fn item(item: &mut Item) -> bool {
+    item_attrs(item).is_some_and(take)
+}
+
+pub(crate) fn impl_item(item: &mut ImplItem) -> bool {
+    let attrs = attrs!(item, ImplItem: Const, Fn, Type, Macro);
+    attrs.is_some_and(take)
+}
+
+pub(crate) fn trait_item(item: &mut TraitItem) -> bool {
+    let attrs = attrs!(item, TraitItem: Const, Fn, Type, Macro);
+    attrs.is_some_and(take)
+}
+
+pub(crate) fn local(local: &mut Local) -> bool {
+    take(&mut local.attrs)
+}
+
+pub(crate) fn expr(expr: &mut Expr) -> bool {
+    expr_attrs(expr).is_some_and(take)
+}
+
+/// Removes every `#[remove_async_await::keep]` and `#[remove_async_await::replace_with(...)]` from the untouched async
+/// item, since Rust doesn't allow attribute macros on statements and expressions.
+pub(crate) fn strip(tokens: TokenStream) -> TokenStream {
+    let mut output = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(pound) if pound.as_char() == '#' => {
+                if let Some(TokenTree::Group(group)) = tokens.peek() {
+                    if group.delimiter() == Delimiter::Bracket
+                        && Parser::parse2(attr_path, group.stream()).is_ok_and(|path| {
+                            is_marker(&path, "keep") || is_marker(&path, "replace_with")
+                        })
+                    {
+                        tokens.next();
+                        continue;
+                    }
+                }
+                output.push(TokenTree::Punct(pound));
+            }
+            TokenTree::Group(group) => {
+                let mut stripped = Group::new(group.delimiter(), strip(group.stream()));
+                stripped.set_span(group.span());
+                output.push(TokenTree::Group(stripped));
+            }
+            token => output.push(token),
+        }
+    }
+    output.into_iter().collect()
+}
+
+/// Parses the path of an attribute, ignoring its arguments.
+fn attr_path(input: ParseStream) -> syn::Result<Path> {
+    let path = input.call(Path::parse_mod_style)?;
+    input.parse::<TokenStream>()?;
+    Ok(path)
+}
@@ -0,0 +1,148 @@
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{
+    fold::{self, Fold},
+    spanned::Spanned,
+    visit::{self, Visit},
+    Block, ExprAsync, ExprClosure, Ident, ImplItemFn, Item, ItemFn, Path, Signature, Stmt,
+    TraitItemFn,
+};
+
+use crate::strict::ASYNC_TYPES;
+
+/// Crates that only make sense in async code, apart from the modules that run futures from blocking code.
+const ASYNC_CRATES: &[&str] = &["tokio", "futures", "async_std", "wasm_bindgen_futures"];
+const EXECUTOR_MODULES: &[&str] = &["executor", "runtime"];
+
+/// Adds a warning to every function in the blocking item that still uses async crates or types, like `tokio::spawn`
+/// or `Stream`, which compile but need a runtime. Proc macros can't emit warnings on stable, so the warning comes from
+/// a deprecated constant used at the start of the function, which means `#[allow(deprecated)]` on the function
+/// silences it. That breaks crates denying warnings, so it only runs with `warn_leaks`.
+pub(crate) fn warn(output: TokenStream) -> TokenStream {
+    let mut warner = Warner { warned: false };
+    let warned = if let Ok(file) = syn::parse2::<syn::File>(output.clone()) {
+        warner.fold_file(file).into_token_stream()
+    } else if let Ok(method) = syn::parse2::<TraitItemFn>(output.clone()) {
+        warner.fold_trait_item_fn(method).into_token_stream()
+    } else {
+        return output;
+    };
+    // leave the tokens as they are unless there's something to warn about
+    if warner.warned {
+        warned
+    } else {
+        output
+    }
+}
+
+struct Warner {
+    warned: bool,
+}
+
+impl Warner {
+    fn warn(&mut self, sig: &Signature, block: &mut Block) {
+        if sig.asyncness.is_some() {
+            return;
+        }
+        let mut finder = Finder::default();
+        finder.visit_signature(sig);
+        finder.visit_block(block);
+        let span = match finder.span {
+            Some(span) => span,
+            None => return,
+        };
+        let leaks = finder
+            .leaks
+            .iter()
+            .map(|leak| format!("`{}`", leak))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let note = format!(
+            "`{}` still uses async code after the transform: {}; rewrite it with `map(...)` or `types(...)`, or allow `deprecated` on the function if it's on purpose",
+            sig.ident, leaks
+        );
+        let leak = Ident::new("async_leak", span);
+        let stmt: Stmt = syn::parse_quote! {
+            {
+                #[deprecated(note = #note)]
+                #[allow(non_upper_case_globals)]
+                const async_leak: () = ();
+                let () = #leak;
+            }
+        };
+        block.stmts.insert(0, stmt);
+        self.warned = true;
+    }
+}
+
+impl Fold for Warner {
+    fn fold_item_fn(&mut self, i: ItemFn) -> ItemFn {
+        let mut i = fold::fold_item_fn(self, i);
+        self.warn(&i.sig, &mut i.block);
+        i
+    }
+
+    fn fold_impl_item_fn(&mut self, i: ImplItemFn) -> ImplItemFn {
+        let mut i = fold::fold_impl_item_fn(self, i);
+        self.warn(&i.sig, &mut i.block);
+        i
+    }
+
+    fn fold_trait_item_fn(&mut self, i: TraitItemFn) -> TraitItemFn {
+        let mut i = fold::fold_trait_item_fn(self, i);
+        if let Some(block) = &mut i.default {
+            self.warn(&i.sig, block);
+        }
+        i
+    }
+}
+
+/// Collects the async paths in a function, without going into nested items or async code that's left.
+#[derive(Default)]
+struct Finder {
+    leaks: Vec<String>,
+    span: Option<Span>,
+}
+
+impl Finder {
+    fn is_leak(path: &Path) -> bool {
+        let mut segments = path.segments.iter().map(|segment| &segment.ident);
+        let first = segments.next();
+        let second = segments.next();
+        let krate = first.is_some_and(|first| ASYNC_CRATES.iter().any(|name| first == name))
+            && !second.is_some_and(|second| EXECUTOR_MODULES.iter().any(|name| second == name));
+        let ty = path
+            .segments
+            .last()
+            .is_some_and(|last| ASYNC_TYPES.iter().any(|name| last.ident == name));
+        krate || ty
+    }
+}
+
+impl<'ast> Visit<'ast> for Finder {
+    fn visit_item(&mut self, _: &'ast Item) {}
+
+    fn visit_expr_async(&mut self, _: &'ast ExprAsync) {}
+
+    fn visit_expr_closure(&mut self, i: &'ast ExprClosure) {
+        if i.asyncness.is_none() {
+            visit::visit_expr_closure(self, i);
+        }
+    }
+
+    fn visit_path(&mut self, i: &'ast Path) {
+        if Self::is_leak(i) {
+            let leak = i
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            if !self.leaks.contains(&leak) {
+                self.leaks.push(leak);
+            }
+            self.span.get_or_insert_with(|| i.span());
+        }
+        visit::visit_path(self, i);
+    }
+}
@@ -0,0 +1,1059 @@
+//! The transform behind [remove-async-await](https://docs.rs/remove-async-await), for using it without going through
+//! macro expansion, like in code generators and tests. Please see the documentation of remove-async-await for what the
+//! transform does and the arguments it takes.
+//!
+//! ```rs
+//! let file: syn::File = syn::parse_str(source)?;
+//! let file = remove_async_await_core::remove_async_await(file);
+//! ```
+//!
+//! [`RemoveAsyncAwait`] implements [`syn::fold::Fold`], so single items, signatures and expressions can be transformed
+//! too.
+//!
+//! Everything but the [`tokens`] module needs the default `full` feature, which is what builds syn.
+
+/// Builds the items only with the `full` feature, which is what needs syn.
+macro_rules! full {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "full")]
+            $item
+        )*
+    };
+}
+
+pub mod tokens;
+
+full! {
+    mod args;
+    mod assoc;
+    mod attrs;
+    mod block_on;
+    mod blocks;
+    mod bounds;
+    pub mod build;
+    mod cfgs;
+    mod chains;
+    mod combinators;
+    mod consts;
+    mod delays;
+    #[cfg(feature = "debug")]
+    mod dump;
+    mod errors;
+    mod fast;
+    #[cfg(any(feature = "debug", feature = "testing"))]
+    mod format;
+    mod generics;
+    mod hoist;
+    mod input;
+    mod instrument;
+    mod io;
+    mod keep;
+    mod leaks;
+    mod lifetimes;
+    mod macros;
+    mod mappings;
+    mod markers;
+    mod methods;
+    mod mirror;
+    mod sibling;
+    mod sinks;
+    mod streams;
+    mod strict;
+    mod tasks;
+    #[cfg(feature = "testing")]
+    pub mod testing;
+    mod trait_pair;
+    mod types;
+    mod unified;
+    mod vis;
+    mod wasm;
+
+    use proc_macro2::TokenStream;
+    use quote::{quote, ToTokens};
+    use syn::{
+        fold::{self, Fold},
+        parse_quote, token, Attribute, Block, Expr, ExprAwait, ExprBlock, ExprCall, ExprClosure,
+        ExprConst, ExprMacro, ExprPath, ExprRepeat, File, GenericArgument, Generics, Ident, ImplItem,
+        ImplItemFn, Item, ItemConst, ItemFn, ItemImpl, ItemMod, ItemStatic, ItemTrait, ItemUse, Local, Macro,
+        Path, PredicateType, ReturnType, Signature, Stmt, TraitBound, TraitItem, TraitItemFn, Type,
+        TypeArray, TypeGroup, TypeImplTrait, TypeParam, TypePath, TypeTraitObject, Variant,
+    };
+
+    use crate::{
+        args::Args, assoc::AssocFuture, generics::FutureParams, input::Input, sinks::SinkTarget,
+    };
+}
+
+/// The folder that removes async and awaits, which can be used with any of [`syn::fold::Fold`]'s methods. `async` and
+/// future parameters are removed by the item methods like `fold_item_fn`, while `fold_signature` and `fold_expr` only
+/// rewrite return types and expressions.
+#[cfg(feature = "full")]
+pub struct RemoveAsyncAwait {
+    args: Args,
+    /// State for the function being folded.
+    scope: FnScope,
+    /// Associated types of the trait or impl being folded that were futures, and the type each one resolves to.
+    assoc_futures: Vec<AssocFuture>,
+    /// Functions in the item being folded that are async or return futures, so calls to them are futures.
+    async_fns: Vec<Ident>,
+}
+
+#[cfg(feature = "full")]
+#[derive(Default)]
+struct FnScope {
+    /// Whether the function returns a boxed stream, so `Box::pin(stream)` and `stream.boxed()` should become
+    /// `Box::new(iterator)`.
+    boxed_stream_output: bool,
+    /// Whether the function returns a future, like `BoxFuture<'a, T>`, so whatever it boxes is a future.
+    future_output: bool,
+    /// Whether the function returns a task handle like `JoinHandle<T>`, so spawned tasks should be inlined.
+    join_handle_output: bool,
+    /// Arguments that are sinks which became closures with `sink = closure`, and their `Error`.
+    sinks: Vec<(Ident, Type)>,
+    /// Generic parameters that were bounded by `Future`.
+    future_params: FutureParams,
+    /// Parameters whose type is a receiver of `blocking_methods(on(...))`.
+    method_receivers: Vec<Ident>,
+    /// Parameters and variables that are streams, see `streams::is_stream`.
+    streams: Vec<Ident>,
+}
+
+#[cfg(feature = "full")]
+impl Default for RemoveAsyncAwait {
+    fn default() -> Self {
+        RemoveAsyncAwait::from_args(Args::default())
+    }
+}
+
+#[cfg(feature = "full")]
+impl RemoveAsyncAwait {
+    /// Creates a folder without any arguments, like `#[remove_async_await]`.
+    pub fn new() -> Self {
+        RemoveAsyncAwait::default()
+    }
+
+    /// Creates a folder with the same arguments as `#[remove_async_await(...)]`, like `map(a = b)`.
+    pub fn with_args(args: TokenStream) -> syn::Result<Self> {
+        Ok(RemoveAsyncAwait::from_args(syn::parse2(args)?))
+    }
+
+    fn from_args(args: Args) -> Self {
+        RemoveAsyncAwait {
+            args,
+            scope: FnScope::default(),
+            assoc_futures: Vec::new(),
+            async_fns: Vec::new(),
+        }
+    }
+
+    /// Whether `e` is an argument that became a closure.
+    fn is_closure_arg(&self, e: &ExprPath) -> bool {
+        e.qself.is_none()
+            && (self.scope.future_params.closure_args)
+                .iter()
+                .any(|arg| e.path.is_ident(arg))
+    }
+
+    /// If `t` is a removed generic parameter bounded by `Future` (or its `Output`), returns the type it resolves to.
+    fn resolved_future_param(&self, t: &Type) -> Option<Type> {
+        let t = match t {
+            Type::Path(t) if t.qself.is_none() && t.path.leading_colon.is_none() => t,
+            _ => return None,
+        };
+        let mut segments = t.path.segments.iter();
+        let param = segments.next()?;
+        if !param.arguments.is_empty() || !segments.all(|segment| segment.ident == "Output") {
+            return None;
+        }
+        (self.scope.future_params.resolved)
+            .iter()
+            .find(|(ident, _)| param.ident == *ident)
+            .map(|(_, ty)| ty.clone())
+    }
+
+    /// Whether `Box::pin(e)` or `e.boxed()` boxes a future or stream: `e` is an async block, a call to a function known
+    /// to be rewritten or a stream constructor, or the function returns a boxed future or stream anyway. Calls to
+    /// anything else, like `make_buf()`, could return values, so they keep their box.
+    fn boxes_async(&self, e: &Expr) -> bool {
+        fn is_future(folder: &RemoveAsyncAwait, e: &Expr) -> bool {
+            match e {
+                Expr::Async(_) => true,
+                Expr::Paren(e) => is_future(folder, &e.expr),
+                Expr::Group(e) => is_future(folder, &e.expr),
+                Expr::Call(e) => matches!(&*e.func, Expr::Path(func)
+                    if func.qself.is_none() && folder.is_rewritten_fn(&func.path)),
+                _ => false,
+            }
+        }
+        self.scope.future_output
+            || self.scope.boxed_stream_output
+            || is_future(self, e)
+            || streams::is_constructed(e)
+    }
+
+    /// Whether `path` names a function whose blocking version doesn't return a future: an async function or one
+    /// returning a future defined in the item, like `compute` or `Self::compute`, a call the blocking copy from
+    /// `sibling` makes to another copy, or a mapped path.
+    fn is_rewritten_fn(&self, path: &Path) -> bool {
+        let last = &path.segments.last().unwrap().ident;
+        let defined = path.leading_colon.is_none()
+            && (path.segments.len() == 1
+                || (path.segments.len() == 2 && path.segments[0].ident == "Self"));
+        (defined && self.async_fns.contains(last))
+            || (self.args.sibling)
+                .as_ref()
+                .is_some_and(|sibling| sibling.is_local(path))
+            || sibling::is_mapped(&self.args.mappings, path)
+    }
+
+    /// Remembers the functions among `sigs` that are async or return futures, see `is_rewritten_fn`.
+    fn define_fns<'a>(&mut self, sigs: impl IntoIterator<Item = &'a Signature>) {
+        let fns = sigs.into_iter().filter(|sig| sibling::is_async(sig));
+        self.async_fns.extend(fns.map(|sig| sig.ident.clone()));
+    }
+
+    /// Removes and replaces attributes that only make sense on async items, see `attrs::rewrite_async_attrs`.
+    fn rewrite_attrs(&self, attrs: &mut Vec<Attribute>) {
+        attrs::rewrite_async_attrs(attrs);
+        if let Some(wasm_bindgen) = &self.args.wasm_bindgen {
+            wasm_bindgen.strip_attrs(attrs);
+        }
+    }
+
+    /// Starts folding a function with the signature `sig` and `body`, rewriting its generics. Returns the scope of the
+    /// outer function, which should be restored once it's folded.
+    fn enter_fn(&mut self, sig: &mut Signature, body: Option<&Block>) -> FnScope {
+        let scope = FnScope {
+            boxed_stream_output: match &sig.output {
+                ReturnType::Type(_, ty) => streams::boxed_iterator(ty).is_some(),
+                ReturnType::Default => false,
+            },
+            future_output: match &sig.output {
+                ReturnType::Type(_, ty) => types::future_output(ty).is_some(),
+                ReturnType::Default => false,
+            },
+            join_handle_output: match &sig.output {
+                ReturnType::Type(_, ty) => tasks::returns_task(ty, body),
+                ReturnType::Default => false,
+            },
+            sinks: match &self.args.sink {
+                Some(SinkTarget::Closure) => sinks::sink_args(sig),
+                _ => Vec::new(),
+            },
+            // before the `Stream` bounds are rewritten
+            streams: streams::stream_params(sig),
+            future_params: generics::rewrite_future_params(sig, &self.args.closure),
+            method_receivers: match &self.args.blocking_methods {
+                Some(methods) => methods.typed_receivers(sig),
+                None => Vec::new(),
+            },
+        };
+        std::mem::replace(&mut self.scope, scope)
+    }
+}
+
+#[cfg(feature = "full")]
+impl Fold for RemoveAsyncAwait {
+    // leave anything marked with `#[remove_async_await::keep]` as is
+    fn fold_item(&mut self, mut i: Item) -> Item {
+        if keep::item(&mut i) {
+            return i;
+        }
+        fold::fold_item(self, i)
+    }
+
+    fn fold_file(&mut self, f: File) -> File {
+        self.define_fns(item_fns(&f.items));
+        fold::fold_file(self, f)
+    }
+
+    fn fold_item_mod(&mut self, m: ItemMod) -> ItemMod {
+        if let Some((_, items)) = &m.content {
+            self.define_fns(item_fns(items));
+        }
+        fold::fold_item_mod(self, m)
+    }
+
+    fn fold_impl_item(&mut self, mut i: ImplItem) -> ImplItem {
+        if keep::impl_item(&mut i) {
+            return i;
+        }
+        fold::fold_impl_item(self, i)
+    }
+
+    fn fold_trait_item(&mut self, mut i: TraitItem) -> TraitItem {
+        if keep::trait_item(&mut i) {
+            return i;
+        }
+        fold::fold_trait_item(self, i)
+    }
+
+    fn fold_local(&mut self, mut l: Local) -> Local {
+        if keep::local(&mut l) {
+            return l;
+        }
+        // remember which variables are streams, before the initializer becomes an iterator
+        if let Some((ident, stream)) = streams::local_stream(&l, &self.scope.streams) {
+            let ident = ident.clone();
+            self.scope.streams.retain(|name| *name != ident);
+            if stream {
+                self.scope.streams.push(ident);
+            }
+        }
+        fold::fold_local(self, l)
+    }
+
+    // awaits in const contexts, like `const { ... }`, array lengths and enum discriminants, are errors
+    fn fold_item_const(&mut self, mut i: ItemConst) -> ItemConst {
+        i.expr = Box::new(consts::check(*i.expr));
+        fold::fold_item_const(self, i)
+    }
+
+    fn fold_item_static(&mut self, mut i: ItemStatic) -> ItemStatic {
+        i.expr = Box::new(consts::check(*i.expr));
+        fold::fold_item_static(self, i)
+    }
+
+    fn fold_variant(&mut self, mut v: Variant) -> Variant {
+        if let Some((eq, discriminant)) = v.discriminant {
+            v.discriminant = Some((eq, consts::check(discriminant)));
+        }
+        fold::fold_variant(self, v)
+    }
+
+    fn fold_type_array(&mut self, mut t: TypeArray) -> TypeArray {
+        t.len = consts::check(t.len);
+        fold::fold_type_array(self, t)
+    }
+
+    fn fold_expr_repeat(&mut self, mut e: ExprRepeat) -> ExprRepeat {
+        e.len = Box::new(consts::check(*e.len));
+        fold::fold_expr_repeat(self, e)
+    }
+
+    fn fold_expr_const(&mut self, mut e: ExprConst) -> ExprConst {
+        e.block = consts::check_block(e.block);
+        fold::fold_expr_const(self, e)
+    }
+
+    fn fold_generic_argument(&mut self, a: GenericArgument) -> GenericArgument {
+        match a {
+            GenericArgument::Const(e) => GenericArgument::Const(self.fold_expr(consts::check(e))),
+            a => fold::fold_generic_argument(self, a),
+        }
+    }
+
+    fn fold_item_fn(&mut self, mut i: ItemFn) -> ItemFn {
+        // taken first, so functions nested in the body don't get it
+        let sync_body = self.args.sync_body.take();
+        // the function can box calls to itself
+        self.define_fns([&i.sig]);
+        // run the untouched body with the executor from `block_on`
+        if let (Some(block_on), None) = (&self.args.block_on, &sync_body) {
+            if block_on.wrap(&mut i.sig, &mut i.block) {
+                self.rewrite_attrs(&mut i.attrs);
+                return i;
+            }
+        }
+        // remove async functions, or the async block a function returning a future ends with
+        if i.sig.asyncness.take().is_none() {
+            blocks::flatten_tail(&mut i.block);
+        }
+        self.rewrite_attrs(&mut i.attrs);
+        let outer = self.enter_fn(&mut i.sig, Some(&i.block));
+        instrument::rewrite_instrument(&mut i.attrs, &self.scope.future_params.resolved_args);
+        let mut i = fold::fold_item_fn(self, i);
+        self.scope = outer;
+        // keep the transformed signature with the body from `sync_body`
+        if let Some(body) = sync_body {
+            *i.block = body;
+        }
+        i
+    }
+
+    fn fold_item_trait(&mut self, mut i: ItemTrait) -> ItemTrait {
+        // remove associated futures like `type Fut: Future<Output = T>`, so `Self::Fut` can be rewritten to `T`
+        self.rewrite_attrs(&mut i.attrs);
+        self.assoc_futures = assoc::remove_from_trait(&mut i.items);
+        self.define_fns(i.items.iter().filter_map(|item| match item {
+            TraitItem::Fn(item) => Some(&item.sig),
+            _ => None,
+        }));
+        let mut i = fold::fold_item_trait(self, i);
+        i.supertraits = bounds::strip(&self.args.strip_bounds, i.supertraits);
+        if i.supertraits.is_empty() {
+            i.colon_token = None;
+        }
+        i
+    }
+
+    fn fold_item_impl(&mut self, mut i: ItemImpl) -> ItemImpl {
+        // and their definitions, like `type Fut = Ready<T>`
+        self.rewrite_attrs(&mut i.attrs);
+        self.assoc_futures = assoc::remove_from_impl(&mut i.items);
+        self.define_fns(i.items.iter().filter_map(|item| match item {
+            ImplItem::Fn(item) => Some(&item.sig),
+            _ => None,
+        }));
+        fold::fold_item_impl(self, i)
+    }
+
+    fn fold_impl_item_fn(&mut self, mut i: ImplItemFn) -> ImplItemFn {
+        let sync_body = self.args.sync_body.take();
+        self.define_fns([&i.sig]);
+        // run the untouched body with the executor from `block_on`
+        if let (Some(block_on), None) = (&self.args.block_on, &sync_body) {
+            if block_on.wrap(&mut i.sig, &mut i.block) {
+                self.rewrite_attrs(&mut i.attrs);
+                return i;
+            }
+        }
+        // remove async methods
+        if i.sig.asyncness.take().is_none() {
+            blocks::flatten_tail(&mut i.block);
+        }
+        self.rewrite_attrs(&mut i.attrs);
+        let outer = self.enter_fn(&mut i.sig, Some(&i.block));
+        instrument::rewrite_instrument(&mut i.attrs, &self.scope.future_params.resolved_args);
+        let mut i = fold::fold_impl_item_fn(self, i);
+        self.scope = outer;
+        if let Some(body) = sync_body {
+            i.block = body;
+        }
+        i
+    }
+
+    fn fold_trait_item_fn(&mut self, mut i: TraitItemFn) -> TraitItemFn {
+        let sync_body = self.args.sync_body.take();
+        self.define_fns([&i.sig]);
+        if let (Some(block_on), Some(block), None) =
+            (&self.args.block_on, &mut i.default, &sync_body)
+        {
+            if block_on.wrap(&mut i.sig, block) {
+                self.rewrite_attrs(&mut i.attrs);
+                return i;
+            }
+        }
+        // remove async trait methods
+        if let (None, Some(block)) = (i.sig.asyncness.take(), &mut i.default) {
+            blocks::flatten_tail(block);
+        }
+        self.rewrite_attrs(&mut i.attrs);
+        let outer = self.enter_fn(&mut i.sig, i.default.as_ref());
+        instrument::rewrite_instrument(&mut i.attrs, &self.scope.future_params.resolved_args);
+        let mut i = fold::fold_trait_item_fn(self, i);
+        self.scope = outer;
+        if let Some(body) = sync_body {
+            i.default = Some(body);
+        }
+        i
+    }
+
+    fn fold_signature(&mut self, mut s: Signature) -> Signature {
+        // remove `'async_trait` from methods written like `async_trait`'s expansion
+        let mut unused_lifetimes = lifetimes::strip_async_trait(&mut s);
+        let returns_future = matches!(&s.output, ReturnType::Type(_, ty)
+            if types::future_output(ty).is_some() || self.scope.join_handle_output);
+        unused_lifetimes.extend(lifetimes::output_lifetimes(&s));
+        let mut s = fold::fold_signature(self, s);
+        // rewrite `-> impl Future<Output = T>` and `-> JoinHandle<T>` to `-> T` (boxed futures have already been
+        // rewritten by `fold_type`)
+        if let (true, ReturnType::Type(_, ty)) = (returns_future, &s.output) {
+            let output = types::future_output(ty)
+                .or_else(|| tasks::join_handle_output(ty))
+                .unwrap_or_else(|| (**ty).clone());
+            s.output = if types::is_unit(&output) {
+                ReturnType::Default
+            } else {
+                ReturnType::Type(Default::default(), Box::new(output))
+            };
+        }
+        // lifetimes like the `'a` in `-> BoxFuture<'a, T>` might not be needed anymore
+        lifetimes::elide_unused(&mut s, unused_lifetimes);
+        s
+    }
+
+    fn fold_return_type(&mut self, r: ReturnType) -> ReturnType {
+        let returns_unit = matches!(&r, ReturnType::Type(_, ty) if types::is_unit(ty));
+        let r = fold::fold_return_type(self, r);
+        // return types like `-> BoxFuture<'a, ()>` or `-> Self::Fut` may have been rewritten to `-> ()`
+        match r {
+            ReturnType::Type(_, ty) if types::is_unit(&ty) && !returns_unit => ReturnType::Default,
+            r => r,
+        }
+    }
+
+    fn fold_generics(&mut self, g: Generics) -> Generics {
+        let mut g = fold::fold_generics(self, g);
+        bounds::strip_generics(&self.args.strip_bounds, &mut g);
+        g
+    }
+
+    fn fold_type_param(&mut self, p: TypeParam) -> TypeParam {
+        let mut p = fold::fold_type_param(self, p);
+        // rewrite `R: AsyncRead + Unpin` to `R: std::io::Read`
+        io::rewrite_bounds(&mut p.bounds);
+        p
+    }
+
+    fn fold_predicate_type(&mut self, p: PredicateType) -> PredicateType {
+        let mut p = fold::fold_predicate_type(self, p);
+        io::rewrite_bounds(&mut p.bounds);
+        p
+    }
+
+    fn fold_type_impl_trait(&mut self, t: TypeImplTrait) -> TypeImplTrait {
+        let mut t = fold::fold_type_impl_trait(self, t);
+        io::rewrite_bounds(&mut t.bounds);
+        let bounds = bounds::strip(&self.args.strip_bounds, t.bounds.clone());
+        if !bounds.is_empty() {
+            t.bounds = bounds;
+        }
+        t
+    }
+
+    fn fold_type_trait_object(&mut self, t: TypeTraitObject) -> TypeTraitObject {
+        let mut t = fold::fold_type_trait_object(self, t);
+        io::rewrite_bounds(&mut t.bounds);
+        let bounds = bounds::strip(&self.args.strip_bounds, t.bounds.clone());
+        if !bounds.is_empty() {
+            t.bounds = bounds;
+        }
+        t
+    }
+
+    fn fold_trait_bound(&mut self, b: TraitBound) -> TraitBound {
+        let mut b = fold::fold_trait_bound(self, b);
+        // rewrite mapped traits, such as `embedded_hal_async::i2c::I2c` to `embedded_hal::i2c::I2c`
+        mappings::map_path(&self.args.type_mappings, &mut b.path);
+        // rewrite `Stream<Item = T>` to `Iterator<Item = T>`
+        if let Some(path) = streams::iterator_bound(&b.path) {
+            b.path = path;
+        }
+        // rewrite `Sink<T, Error = E>` with `sink = ...`
+        if let Some(target) = &self.args.sink {
+            if let Some(path) = sinks::rewrite_bound(target, &b.path) {
+                b.path = path;
+            }
+        }
+        // rewrite `AsyncFnMut(A) -> T` to `FnMut(A) -> T`
+        if let Some(last) = b.path.segments.last_mut() {
+            let name = last.ident.to_string();
+            if let "AsyncFn" | "AsyncFnMut" | "AsyncFnOnce" = name.as_str() {
+                last.ident = Ident::new(&name["Async".len()..], last.ident.span());
+            }
+        }
+        b
+    }
+
+    fn fold_expr_closure(&mut self, mut e: ExprClosure) -> ExprClosure {
+        // the async block a closure returns, like in `|| async move { ... }`, can return from the closure
+        blocks::flatten(&mut e.body);
+        let mut e = fold::fold_expr_closure(self, e);
+        // remove async closures
+        e.asyncness = None;
+        e
+    }
+
+    fn fold_type(&mut self, t: Type) -> Type {
+        // rewrite removed generic parameters like `F: Future<Output = T>` (and `F::Output`) to `T`
+        if let Some(ty) = self.resolved_future_param(&t) {
+            return ty;
+        }
+        // rewrite removed associated futures like `Self::Fut` to `T`
+        if let Some(ty) = assoc::resolve(&self.assoc_futures, &t) {
+            return ty;
+        }
+        let t = fold::fold_type(self, t);
+        // rewrite boxed futures like `BoxFuture<'a, T>` to `T`, wherever they are
+        if types::is_boxed_future(&t) {
+            types::future_output(&t).unwrap()
+        } else if let Some(iter) = streams::boxed_iterator(&t) {
+            // rewrite boxed streams like `BoxStream<'a, T>` to `Box<dyn Iterator<Item = T> + 'a>`
+            iter
+        } else {
+            match t {
+                // `&mut (dyn AsyncRead + Unpin)` is left with one bound, so the parentheses would trip `unused_parens`
+                Type::Paren(t) if matches!(&*t.elem, Type::TraitObject(object) if object.bounds.len() == 1) => {
+                    Type::Group(TypeGroup {
+                        group_token: token::Group(t.paren_token.span.join()),
+                        elem: t.elem,
+                    })
+                }
+                t => t,
+            }
+        }
+    }
+
+    fn fold_expr(&mut self, mut e: Expr) -> Expr {
+        if keep::expr(&mut e) {
+            return e;
+        }
+        match e {
+            // remove await, lowering futures-lite combinators like `future::zip(a, b)`
+            Expr::Await(e) => match *e.base {
+                // awaiting an argument that became a closure calls it
+                Expr::Path(base) if self.is_closure_arg(&base) => Expr::Call(ExprCall {
+                    attrs: e.attrs,
+                    func: Box::new(Expr::Path(base)),
+                    paren_token: token::Paren(e.await_token.span),
+                    args: Default::default(),
+                }),
+                // `sqlx::query(sql).fetch_one(pool).await` becomes `db::fetch_one(sqlx::query(sql), pool)` with `chains(...)`
+                Expr::MethodCall(call) if chains::hook(&self.args.chains, &call).is_some() => {
+                    let hook = chains::hook(&self.args.chains, &call).unwrap();
+                    chains::call(self, hook, call)
+                }
+                // `proxy.call(args).await` becomes `proxy.call_blocking(args)` with `blocking_methods(suffix = "_blocking")`
+                Expr::MethodCall(mut call)
+                    if self.args.blocking_methods.as_ref().is_some_and(|methods| {
+                        methods.applies(&call, &self.scope.method_receivers)
+                    }) =>
+                {
+                    let methods = self.args.blocking_methods.as_ref().unwrap();
+                    call.method = methods.blocking_name(&call.method);
+                    self.fold_expr(Expr::MethodCall(call))
+                }
+                // `Timer::after(duration).await` calls the blocking delay with `delay = ...`
+                base if self
+                    .args
+                    .delay
+                    .as_ref()
+                    .is_some_and(|delay| delay.call(&base).is_some()) =>
+                {
+                    let call = self.args.delay.as_ref().unwrap().call(&base).unwrap();
+                    self.fold_expr(call)
+                }
+                // `task::spawn(future).await` is the future's output with `profile(async_std)` and `profile(smol)`
+                Expr::Call(call) if self.args.inline_awaited_spawns && tasks::is_spawn(&call) => {
+                    tasks::inline_spawn(self, call)
+                }
+                base => {
+                    let mut base = awaited_value(base);
+                    // call the blocking copies of local functions with `sibling`
+                    if let Some(sibling) = &self.args.sibling {
+                        base = sibling.rename_awaited(&self.args.mappings, base);
+                    }
+                    combinators::fold_awaited(self, self.args.race, base)
+                }
+            },
+            // remove async blocks, keeping `return` and `?` inside them from leaving the function
+            Expr::Async(e) if blocks::leaves(&e.block) => {
+                let block = self.fold_block(e.block);
+                blocks::in_closure(e.capture, e.async_token.span, block)
+            }
+            Expr::Async(e) => self.fold_expr(blocks::unasync(e)),
+            // remove `std::future::ready(value)`, since the value is already ready
+            Expr::Call(e) if calls(&e, &["future", "ready"]) => {
+                match e.args.into_iter().next().unwrap() {
+                    // `ready(())` becomes `{}`, so it doesn't trip clippy's `unused_unit` when it's returned
+                    Expr::Tuple(unit) if unit.elems.is_empty() => Expr::Block(ExprBlock {
+                        attrs: unit.attrs,
+                        label: None,
+                        block: Block {
+                            brace_token: token::Brace(unit.paren_token.span),
+                            stmts: Vec::new(),
+                        },
+                    }),
+                    value => self.fold_expr(value),
+                }
+            }
+            // `MaybeFuture::new(future)` and `MaybeFuture::ready(value)` are the value itself once it's not a future
+            Expr::Call(e)
+                if calls(&e, &["MaybeFuture", "new"]) || calls(&e, &["MaybeFuture", "ready"]) =>
+            {
+                self.fold_expr(e.args.into_iter().next().unwrap())
+            }
+            // remove `value.into_future()` and `IntoFuture::into_future(value)`, since awaiting `value` gives the same.
+            // `StreamExt::into_future` is something else, so it's left alone on streams
+            Expr::MethodCall(e)
+                if e.method == "into_future"
+                    && e.args.is_empty()
+                    && !self.scope.boxed_stream_output
+                    && !streams::is_stream(&e.receiver, &self.scope.streams) =>
+            {
+                self.fold_expr(*e.receiver)
+            }
+            Expr::Call(e) if calls(&e, &["IntoFuture", "into_future"]) => {
+                self.fold_expr(e.args.into_iter().next().unwrap())
+            }
+            // inline `tokio::spawn(future)` and `spawn_blocking(f)` in functions returning a `JoinHandle`
+            Expr::Call(e) if self.scope.join_handle_output && tasks::is_spawn(&e) => {
+                tasks::inline_spawn(self, e)
+            }
+            // rewrite `sink.send(item)` to `sink(item)` with `sink = closure`
+            Expr::MethodCall(e) if sinks::closure_call(&self.scope.sinks, &e).is_some() => {
+                let call = sinks::closure_call(&self.scope.sinks, &e).unwrap();
+                self.fold_expr(call)
+            }
+            // rewrite `JsFuture::from(promise)` with `wasm_bindgen(js_future = path)`
+            Expr::Call(e)
+                if (self.args.wasm_bindgen.as_ref())
+                    .is_some_and(|wasm_bindgen| wasm_bindgen.js_future_call(&e).is_some()) =>
+            {
+                let e = fold::fold_expr_call(self, e);
+                let wasm_bindgen = self.args.wasm_bindgen.as_ref().unwrap();
+                wasm_bindgen.js_future_call(&e).unwrap()
+            }
+            // rewrite `Box::pin(stream)` to `Box::new(iterator)`, and remove `Box::pin(future)` since boxed futures are
+            // rewritten to their output. Anything else, like `Box::pin(node)`, keeps its box
+            Expr::Call(e) if calls(&e, &["Box", "pin"]) && self.boxes_async(&e.args[0]) => {
+                let boxed_stream =
+                    self.scope.boxed_stream_output || streams::is_constructed(&e.args[0]);
+                let inner = self.fold_expr(e.args.into_iter().next().unwrap());
+                if boxed_stream {
+                    parse_quote!(::std::boxed::Box::new(#inner))
+                } else {
+                    inner
+                }
+            }
+            // same for `stream.boxed()` and `future.boxed()`
+            Expr::MethodCall(e)
+                if (e.method == "boxed" || e.method == "boxed_local")
+                    && e.args.is_empty()
+                    && self.boxes_async(&e.receiver) =>
+            {
+                let boxed_stream =
+                    self.scope.boxed_stream_output || streams::is_constructed(&e.receiver);
+                let inner = self.fold_expr(*e.receiver);
+                if boxed_stream {
+                    parse_quote!(::std::boxed::Box::new(#inner))
+                } else {
+                    inner
+                }
+            }
+            // `spawn_or_call!(...)` becomes a call or thread, whatever the features are
+            Expr::Macro(m) if markers::spawned(&m.mac).is_some() => {
+                match markers::spawned(&m.mac).unwrap() {
+                    Ok(spawned) => self.fold_expr(spawned),
+                    Err(err) => Expr::Verbatim(err.to_compile_error()),
+                }
+            }
+            // `maybe_await!(expr)` is removed like `expr.await`, whatever the features are
+            Expr::Macro(m) if markers::maybe_awaited(&m.mac).is_some() => {
+                match markers::maybe_awaited(&m.mac).unwrap() {
+                    Ok(base) => self.fold_expr(Expr::Await(ExprAwait {
+                        attrs: m.attrs,
+                        base: Box::new(base),
+                        dot_token: token::Dot(m.mac.bang_token.span),
+                        await_token: token::Await(m.mac.bang_token.span),
+                    })),
+                    Err(err) => Expr::Verbatim(err.to_compile_error()),
+                }
+            }
+            // lower stream constructors and methods like `stream::iter(x)` and `.buffered(n)`
+            e @ (Expr::Call(_) | Expr::MethodCall(_)) => {
+                let on_stream = match &e {
+                    Expr::MethodCall(e) => {
+                        self.scope.boxed_stream_output
+                            || streams::is_stream(&e.receiver, &self.scope.streams)
+                    }
+                    _ => false,
+                };
+                streams::fold_stream_expr(self, e, on_stream)
+            }
+            _ => fold::fold_expr(self, e),
+        }
+    }
+
+    fn fold_stmt(&mut self, s: Stmt) -> Stmt {
+        match s {
+            // `maybe_await!(expr);` and `spawn_or_call!(...);` parse as statements, but they're folded as expressions
+            Stmt::Macro(s)
+                if markers::maybe_awaited(&s.mac).is_some()
+                    || markers::spawned(&s.mac).is_some() =>
+            {
+                Stmt::Expr(
+                    self.fold_expr(Expr::Macro(ExprMacro {
+                        attrs: s.attrs,
+                        mac: s.mac,
+                    })),
+                    s.semi_token,
+                )
+            }
+            s => fold::fold_stmt(self, s),
+        }
+    }
+
+    fn fold_block(&mut self, mut b: Block) -> Block {
+        // drop `async_only! { ... }`, and inline `sync_only! { ... }` and `#[replace_with(...)]`, which are written for
+        // the blocking build already
+        self.define_fns(b.stmts.iter().filter_map(|stmt| match stmt {
+            Stmt::Item(Item::Fn(item)) => Some(&item.sig),
+            _ => None,
+        }));
+        let len = b.stmts.len();
+        let mut stmts = Vec::with_capacity(len);
+        for (i, mut stmt) in std::mem::take(&mut b.stmts).into_iter().enumerate() {
+            match markers::expand(&mut stmt, i + 1 == len) {
+                Some(Ok(expanded)) => stmts.extend(expanded),
+                Some(Err(err)) => {
+                    stmts.push(Stmt::Expr(Expr::Verbatim(err.to_compile_error()), None))
+                }
+                // bind awaited expressions first with `hoist_awaits`, so their temporaries are dropped before the rest of
+                // the statement
+                None if self.args.hoist_awaits => stmts.push(self.fold_stmt(hoist::hoist(stmt))),
+                None => stmts.push(self.fold_stmt(stmt)),
+            }
+        }
+        b.stmts = stmts;
+        b
+    }
+
+    fn fold_macro(&mut self, m: Macro) -> Macro {
+        // remove awaits in the arguments of macros like `println!`
+        let (exprs, tokens) = (self.args.macro_exprs, self.args.macro_tokens);
+        macros::fold_macro(self, m, exprs, tokens)
+    }
+
+    fn fold_expr_path(&mut self, e: ExprPath) -> ExprPath {
+        let mut e = fold::fold_expr_path(self, e);
+        // rewrite mapped paths, such as `tokio::time::sleep` to `std::thread::sleep`
+        if e.qself.is_none() {
+            mappings::map_path(&self.args.mappings, &mut e.path);
+            // and calls through futures' IO traits, like `AsyncReadExt::read_to_end(reader, buf)`
+            io::rewrite_path(&mut e.path);
+        }
+        e
+    }
+
+    fn fold_item_use(&mut self, mut i: ItemUse) -> ItemUse {
+        // rewrite mapped imports, such as `use tokio::time::sleep;` to `use std::thread::sleep;`
+        let mappings = self.args.mappings.iter().chain(&self.args.type_mappings);
+        mappings::map_use(mappings, &mut i.tree);
+        // and import `std::io::Read` instead of `futures::io::AsyncReadExt`
+        io::rewrite_use(&mut i.tree);
+        i
+    }
+
+    fn fold_type_path(&mut self, t: TypePath) -> TypePath {
+        let mut t = fold::fold_type_path(self, t);
+        // rewrite mapped types, such as `reqwest::Client` to `reqwest::blocking::Client`
+        if t.qself.is_none() {
+            mappings::map_path(&self.args.type_mappings, &mut t.path);
+        }
+        t
+    }
+}
+
+/// Removes the borrowing, pinning and boxing needed to await a future in place, like in `(&mut future).await`,
+/// `future.as_mut().await`, `Pin::new(&mut future).await` and `Box::pin(future).await`, since the future has been
+/// rewritten to its output.
+#[cfg(feature = "full")]
+fn awaited_value(e: Expr) -> Expr {
+    match e {
+        Expr::Reference(e) if e.mutability.is_some() => awaited_value(*e.expr),
+        Expr::Paren(e) if matches!(&*e.expr, Expr::Reference(_)) => awaited_value(*e.expr),
+        Expr::MethodCall(e) if e.method == "as_mut" && e.args.is_empty() => {
+            awaited_value(*e.receiver)
+        }
+        Expr::Call(e) if calls(&e, &["Pin", "new"]) || calls(&e, &["Box", "pin"]) => {
+            awaited_value(e.args.into_iter().next().unwrap())
+        }
+        Expr::MethodCall(e)
+            if (e.method == "boxed" || e.method == "boxed_local") && e.args.is_empty() =>
+        {
+            awaited_value(*e.receiver)
+        }
+        e => e,
+    }
+}
+
+/// The signatures of the functions among `items`.
+#[cfg(feature = "full")]
+fn item_fns(items: &[Item]) -> impl Iterator<Item = &Signature> {
+    items.iter().filter_map(|item| match item {
+        Item::Fn(item) => Some(&item.sig),
+        _ => None,
+    })
+}
+
+/// Whether `e` calls a function with a single argument, whose path ends with `suffix`. For example, `["future", "ready"]`
+/// matches both `std::future::ready(value)` and `futures::future::ready(value)`.
+#[cfg(feature = "full")]
+pub(crate) fn calls(e: &ExprCall, suffix: &[&str]) -> bool {
+    match &*e.func {
+        Expr::Path(func) if func.qself.is_none() && e.args.len() == 1 => {
+            path_ends_with(&func.path, suffix)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the last segments of `path` are `suffix`.
+#[cfg(feature = "full")]
+pub(crate) fn path_ends_with(path: &Path, suffix: &[&str]) -> bool {
+    path.segments.len() >= suffix.len()
+        && path
+            .segments
+            .iter()
+            .rev()
+            .zip(suffix.iter().rev())
+            .all(|(segment, name)| segment.ident == name)
+}
+
+/// Expands `#[remove_async_await(args)]` on `input`.
+#[cfg(feature = "full")]
+pub fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand_as("remove_async_await", args, input)
+}
+
+/// Expands the attribute named `entry`, which takes the same arguments as `remove_async_await` once they're converted.
+#[cfg(feature = "full")]
+fn expand_as(entry: &str, args: TokenStream, input: TokenStream) -> TokenStream {
+    #[cfg(feature = "debug")]
+    {
+        println!();
+        println!("Input: {}", input);
+    }
+
+    #[cfg(feature = "debug")]
+    let name = dump::item_name(&input);
+    let output = expand_item(entry, args, input);
+
+    #[cfg(feature = "debug")]
+    {
+        println!();
+        println!("Output: {}", output);
+        println!();
+        dump::write(&name, &output);
+    }
+
+    output
+}
+
+#[cfg(feature = "full")]
+fn expand_item(entry: &str, args: TokenStream, input: TokenStream) -> TokenStream {
+    // nothing in the item is async, so it's left as it is without parsing, folding and printing it
+    if args.is_empty() && fast::untouched(&input) {
+        return input;
+    }
+    let args_tokens = args.clone();
+    let args = match syn::parse2::<Args>(args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    // let attributes from `before(...)` expand first, transforming the item once they're done
+    if let Some(output) = attrs::expand_before(&args.before, entry, &args_tokens, &input) {
+        return output;
+    }
+    let after = args.after.clone();
+    let cfg = args.cfg.clone();
+    let doc_cfg = args.doc_cfg;
+    let sibling = args.sibling.clone();
+    let vis = args.vis.clone();
+    let strict = args.strict;
+    let warn_leaks = args.warn_leaks;
+    let deny_noop = args.deny_noop;
+    let mut folder = RemoveAsyncAwait::from_args(args);
+    let parsed = match syn::parse2::<Input>(input.clone()) {
+        Ok(parsed) => parsed,
+        Err(_) => return errors::unsupported(input),
+    };
+    // the body only makes sense for a single function
+    if folder.args.sync_body.is_some() && !parsed.is_fn() {
+        return quote! {
+            compile_error!("`sync_body` can only be used on functions and methods.");
+        };
+    }
+    // emit the async and blocking traits from one definition
+    if let Some(trait_pair) = folder.args.trait_pair.take() {
+        return trait_pair.expand(&mut folder, parsed);
+    }
+    // keep the async item and emit a renamed blocking copy next to it
+    if let Some(sibling) = sibling {
+        if sibling.is_identity() {
+            return syn::Error::new_spanned(
+                &input,
+                "the template gives the blocking copy the same name as the async item",
+            )
+            .to_compile_error();
+        }
+        let mut blocking = sibling::blocking_copy(&mut folder, &sibling, parsed);
+        if let Some(vis) = &vis {
+            blocking = vis::apply(vis, blocking);
+        }
+        let blocking = strict::check(strict, warn_leaks, blocking);
+        let blocking = attrs::emit_after(&after, blocking);
+        let input = keep::strip(input);
+        return quote!(#input #blocking);
+    }
+
+    macro_rules! to_token_stream {
+        ($input: expr) => {{
+            #[cfg(feature = "debug")]
+            {
+                println!();
+                println!("Parsed input: {:#?}", input);
+                println!();
+            }
+            $input.to_token_stream()
+        }};
+    }
+
+    // functions (which also covers methods in impls), trait methods, type aliases (which also covers associated types in
+    // impls), structs, traits, impls, statics and consts are supported
+    let output = match parsed {
+        Input::Item(Item::Fn(item)) => to_token_stream!(folder.fold_item_fn(item)),
+        Input::TraitMethod(item) => to_token_stream!(folder.fold_trait_item_fn(item)),
+        Input::Item(Item::Type(item)) => to_token_stream!(folder.fold_item_type(item)),
+        Input::Item(Item::Struct(item)) => to_token_stream!(folder.fold_item_struct(item)),
+        Input::Item(Item::Trait(item)) => to_token_stream!(folder.fold_item_trait(item)),
+        Input::Item(Item::Impl(item)) => to_token_stream!(folder.fold_item_impl(item)),
+        Input::Item(Item::Static(item)) => to_token_stream!(folder.fold_item_static(item)),
+        Input::Item(Item::Const(item)) => to_token_stream!(folder.fold_item_const(item)),
+        Input::Item(_) => errors::unsupported(input.clone()),
+    };
+
+    // catch attributes left on items that aren't async anymore
+    if deny_noop && output.to_string() == input.to_string() {
+        return syn::Error::new_spanned(
+            input,
+            "this item has nothing async to remove, so `remove_async_await` doesn't change it; remove the attribute or `deny_noop`",
+        )
+        .to_compile_error();
+    }
+    let output = match &vis {
+        Some(vis) => vis::apply(vis, output),
+        None => output,
+    };
+    let output = strict::check(strict, warn_leaks, output);
+    let mut output = attrs::emit_after(&after, output);
+    // keep the async item as is when the cfg predicate from `unless_feature` holds
+    if let Some(cfg) = cfg {
+        output = unified::cfg_variants(&cfg, doc_cfg, input, output);
+    }
+
+    output
+}
+
+/// Removes async and awaits from every item in `file`, like `#[remove_async_await]` on each of them.
+#[cfg(feature = "full")]
+pub fn remove_async_await(file: File) -> File {
+    RemoveAsyncAwait::new().fold_file(file)
+}
+
+/// Expands `#[remove_async_await::test(args)]` on `input`.
+#[cfg(feature = "full")]
+pub fn expand_test(args: TokenStream, input: TokenStream) -> TokenStream {
+    unified::test(args, input)
+}
+
+/// Expands `#[remove_async_await::main(args)]` on `input`.
+#[cfg(feature = "full")]
+pub fn expand_main(args: TokenStream, input: TokenStream) -> TokenStream {
+    unified::main(args, input)
+}
+
+/// Expands `#[remove_async_await::mirror(args)]` on `input`.
+#[cfg(feature = "full")]
+pub fn expand_mirror(args: TokenStream, input: TokenStream) -> TokenStream {
+    mirror::expand(args, input)
+}
+
+/// Expands `#[remove_async_await::dual(args)]` on `input`.
+#[cfg(feature = "full")]
+pub fn expand_dual(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand_as("dual", unified::dual_args(args), input)
+}
@@ -0,0 +1,217 @@
+use syn::{
+    fold::{self, Fold},
+    punctuated::Punctuated,
+    FnArg, GenericParam, Lifetime, Receiver, ReturnType, Signature, Token, TypeParamBound,
+    TypeReference, WherePredicate,
+};
+
+/// Lifetime parameters of `sig` that are used in its return type.
+pub(crate) fn output_lifetimes(sig: &Signature) -> Vec<Lifetime> {
+    let used = collect(|collect| {
+        collect.fold_return_type(sig.output.clone());
+    });
+    sig.generics
+        .lifetimes()
+        .map(|param| param.lifetime.clone())
+        .filter(|lifetime| used.contains(lifetime))
+        .collect()
+}
+
+/// Elides the lifetime parameters in `candidates` that `sig` no longer needs, because they aren't used in the return
+/// type anymore (for example, after `Self::Fut<'a>` was rewritten to `T`). Otherwise, clippy's `needless_lifetimes`
+/// would complain about them. Lifetimes with bounds, or used more than once in the arguments, are kept.
+pub(crate) fn elide_unused(sig: &mut Signature, candidates: Vec<Lifetime>) {
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => collect(|collect| {
+            collect.fold_type((**ty).clone());
+        }),
+        ReturnType::Default => Vec::new(),
+    };
+    let inputs = collect(|collect| {
+        for input in sig.inputs.clone() {
+            match input {
+                // the receiver's type repeats its lifetime, like `&'a Self` for `&'a self`, unless it's written out
+                FnArg::Receiver(receiver) if receiver.colon_token.is_none() => {
+                    if let Some((_, Some(lifetime))) = receiver.reference {
+                        collect.fold_lifetime(lifetime);
+                    }
+                }
+                input => {
+                    collect.fold_fn_arg(input);
+                }
+            }
+        }
+    });
+    let bounds = collect(|collect| {
+        for param in sig.generics.params.clone() {
+            match param {
+                GenericParam::Lifetime(param) => {
+                    for bound in param.bounds {
+                        collect.fold_lifetime(bound);
+                    }
+                }
+                param => {
+                    collect.fold_generic_param(param);
+                }
+            }
+        }
+        if let Some(where_clause) = sig.generics.where_clause.clone() {
+            collect.fold_where_clause(where_clause);
+        }
+    });
+
+    for lifetime in candidates {
+        let count = |lifetimes: &[Lifetime]| lifetimes.iter().filter(|l| **l == lifetime).count();
+        let bounded = sig.generics.lifetimes().any(|param| {
+            param.lifetime == lifetime && (!param.bounds.is_empty() || param.colon_token.is_some())
+        });
+        if bounded || count(&output) > 0 || count(&bounds) > 0 || count(&inputs) > 1 {
+            continue;
+        }
+
+        sig.generics.params = std::mem::take(&mut sig.generics.params)
+            .into_iter()
+            .filter(|param| !matches!(param, GenericParam::Lifetime(param) if param.lifetime == lifetime))
+            .collect();
+        let mut elide = Elide(lifetime);
+        sig.inputs = std::mem::take(&mut sig.inputs)
+            .into_iter()
+            .map(|input| elide.fold_fn_arg(input))
+            .collect();
+    }
+    if sig.generics.params.is_empty() {
+        sig.generics.lt_token = None;
+        sig.generics.gt_token = None;
+    }
+}
+
+fn collect(f: impl FnOnce(&mut Collect)) -> Vec<Lifetime> {
+    let mut collect = Collect(Vec::new());
+    f(&mut collect);
+    collect.0
+}
+
+/// Collects every lifetime it folds.
+struct Collect(Vec<Lifetime>);
+
+impl Fold for Collect {
+    fn fold_lifetime(&mut self, l: Lifetime) -> Lifetime {
+        self.0.push(l.clone());
+        l
+    }
+}
+
+/// Elides a lifetime, so `&'a T` becomes `&T` and `Foo<'a>` becomes `Foo<'_>`.
+struct Elide(Lifetime);
+
+impl Fold for Elide {
+    fn fold_type_reference(&mut self, t: TypeReference) -> TypeReference {
+        let mut t = fold::fold_type_reference(self, t);
+        if t.lifetime.as_ref().is_some_and(|l| l.ident == "_") {
+            t.lifetime = None;
+        }
+        t
+    }
+
+    fn fold_receiver(&mut self, r: Receiver) -> Receiver {
+        let mut r = fold::fold_receiver(self, r);
+        if let Some((_, lifetime)) = &mut r.reference {
+            if lifetime.as_ref().is_some_and(|l| l.ident == "_") {
+                *lifetime = None;
+            }
+        }
+        r
+    }
+
+    fn fold_lifetime(&mut self, l: Lifetime) -> Lifetime {
+        if l == self.0 {
+            Lifetime::new("'_", l.span())
+        } else {
+            l
+        }
+    }
+}
+
+/// Removes the `'async_trait` lifetime that `async_trait` adds to methods, along with the bounds using it (like
+/// `'life0: 'async_trait` and `Self: 'async_trait`). Returns the lifetimes that were bounded by it, since they might not
+/// be needed anymore either.
+pub(crate) fn strip_async_trait(sig: &mut Signature) -> Vec<Lifetime> {
+    let is_async_trait = |lifetime: &Lifetime| lifetime.ident == "async_trait";
+    if !sig
+        .generics
+        .lifetimes()
+        .any(|param| is_async_trait(&param.lifetime))
+    {
+        return Vec::new();
+    }
+
+    let mut bounded = Vec::new();
+    sig.generics.params = std::mem::take(&mut sig.generics.params)
+        .into_iter()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(param) if is_async_trait(&param.lifetime) => None,
+            GenericParam::Lifetime(mut param) => {
+                if param.bounds.iter().any(is_async_trait) {
+                    bounded.push(param.lifetime.clone());
+                }
+                param.bounds = strip_lifetime_bounds(param.bounds, is_async_trait);
+                if param.bounds.is_empty() {
+                    param.colon_token = None;
+                }
+                Some(GenericParam::Lifetime(param))
+            }
+            GenericParam::Type(mut param) => {
+                param.bounds = strip_bounds(param.bounds, is_async_trait);
+                if param.bounds.is_empty() {
+                    param.colon_token = None;
+                }
+                Some(GenericParam::Type(param))
+            }
+            param => Some(param),
+        })
+        .collect();
+
+    if let Some(where_clause) = &mut sig.generics.where_clause {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::Lifetime(mut predicate) => {
+                    if predicate.bounds.iter().any(is_async_trait) {
+                        bounded.push(predicate.lifetime.clone());
+                    }
+                    predicate.bounds = strip_lifetime_bounds(predicate.bounds, is_async_trait);
+                    (!predicate.bounds.is_empty()).then_some(WherePredicate::Lifetime(predicate))
+                }
+                WherePredicate::Type(mut predicate) => {
+                    predicate.bounds = strip_bounds(predicate.bounds, is_async_trait);
+                    (!predicate.bounds.is_empty()).then_some(WherePredicate::Type(predicate))
+                }
+                predicate => Some(predicate),
+            })
+            .collect();
+        if where_clause.predicates.is_empty() {
+            sig.generics.where_clause = None;
+        }
+    }
+    bounded
+}
+
+fn strip_lifetime_bounds(
+    bounds: Punctuated<Lifetime, Token![+]>,
+    stripped: impl Fn(&Lifetime) -> bool,
+) -> Punctuated<Lifetime, Token![+]> {
+    bounds
+        .into_iter()
+        .filter(|bound| !stripped(bound))
+        .collect()
+}
+
+fn strip_bounds(
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+    stripped: impl Fn(&Lifetime) -> bool,
+) -> Punctuated<TypeParamBound, Token![+]> {
+    bounds
+        .into_iter()
+        .filter(|bound| !matches!(bound, TypeParamBound::Lifetime(bound) if stripped(bound)))
+        .collect()
+}
@@ -0,0 +1,68 @@
+use quote::quote;
+use syn::{fold::Fold, parse::Parser, punctuated::Punctuated, Expr, Macro, Token};
+
+/// Macros whose arguments are a comma separated list of expressions, so awaits inside them can be removed.
+const EXPR_MACROS: &[&str] = &[
+    // std
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "dbg",
+    "debug_assert",
+    "debug_assert_eq",
+    "debug_assert_ne",
+    "eprint",
+    "eprintln",
+    "format",
+    "format_args",
+    "panic",
+    "print",
+    "println",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+    // anyhow
+    "anyhow",
+    "bail",
+    "ensure",
+];
+
+/// Folds the arguments of a known macro, or of any macro if `exprs` is set (from `macro_exprs`). Unknown macros, and
+/// macros whose arguments don't parse as expressions (such as `vec![value; len]`), are left untouched, unless `tokens`
+/// is set (from `macro_tokens`): `async` and `.await` are then removed from their tokens instead. `macro_rules!`
+/// definitions are always left as they are.
+pub(crate) fn fold_macro<F: Fold>(
+    folder: &mut F,
+    mut mac: Macro,
+    exprs: bool,
+    tokens: bool,
+) -> Macro {
+    if mac.path.is_ident("macro_rules") {
+        return mac;
+    }
+    let known = exprs
+        || mac
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| EXPR_MACROS.iter().any(|name| segment.ident == name));
+    if known {
+        if let Ok(args) = Punctuated::<Expr, Token![,]>::parse_terminated.parse2(mac.tokens.clone())
+        {
+            let args = args.into_pairs().map(|pair| {
+                let (arg, comma) = pair.into_tuple();
+                let arg = folder.fold_expr(arg);
+                quote!(#arg #comma)
+            });
+            mac.tokens = quote!(#(#args)*);
+            return mac;
+        }
+    }
+    if tokens {
+        mac.tokens = crate::tokens::remove(mac.tokens, &[]);
+    }
+    mac
+}
@@ -0,0 +1,304 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token, Ident, Path, PathSegment, Token, UseGroup, UseName, UsePath, UseRename, UseTree,
+};
+
+/// A single `from = to` path rewrite.
+#[derive(Clone)]
+pub(crate) struct PathMapping {
+    from: Path,
+    to: Path,
+}
+
+impl PathMapping {
+    fn new(from: &str, to: &str) -> Self {
+        PathMapping {
+            from: syn::parse_str(from).unwrap(),
+            to: syn::parse_str(to).unwrap(),
+        }
+    }
+
+    /// Rewrites `path` if it starts with `from`, keeping any trailing segments (so `tokio::fs = std::fs` also maps
+    /// `tokio::fs::read`). Returns `None` if the mapping doesn't apply.
+    pub(crate) fn apply(&self, path: &Path) -> Option<Path> {
+        let len = self.from.segments.len();
+        if path.segments.len() < len
+            || !self
+                .from
+                .segments
+                .iter()
+                .zip(&path.segments)
+                .all(|(a, b)| a.ident == b.ident)
+        {
+            return None;
+        }
+
+        let mut segments = self.to.segments.clone();
+        // point at the replaced path rather than the mapping, so errors in the blocking item land on the right
+        // expression
+        let span = path.segments[len - 1].ident.span();
+        for segment in &mut segments {
+            segment.ident.set_span(span);
+        }
+        // keep generics written on the last replaced segment, e.g. `fetch_async::<T>`
+        if let Some(last) = segments.last_mut() {
+            if last.arguments.is_empty() {
+                last.arguments = path.segments[len - 1].arguments.clone();
+            }
+        }
+        segments.extend(path.segments.iter().skip(len).cloned());
+
+        Some(Path {
+            leading_colon: self.to.leading_colon,
+            segments,
+        })
+    }
+}
+
+impl Parse for PathMapping {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let from = input.call(Path::parse_mod_style)?;
+        input.parse::<Token![=]>()?;
+        let to = input.call(Path::parse_mod_style)?;
+        Ok(PathMapping { from, to })
+    }
+}
+
+/// Applies the first mapping that matches `path`, if any.
+pub(crate) fn map_path(mappings: &[PathMapping], path: &mut Path) {
+    if let Some(mapped) = mappings.iter().find_map(|mapping| mapping.apply(path)) {
+        *path = mapped;
+    }
+}
+
+/// An import in a `use` tree, flattened to its full path.
+enum Import {
+    Name(Path),
+    Rename(Path, Ident),
+    Glob(Path),
+}
+
+/// Flattens `tree` into its imports, with `prefix` being the path to it.
+fn flatten(tree: &UseTree, prefix: &Path, imports: &mut Vec<Import>) {
+    let join = |ident: &Ident| {
+        let mut path = prefix.clone();
+        path.segments.push(PathSegment::from(ident.clone()));
+        path
+    };
+    match tree {
+        UseTree::Path(tree) => flatten(&tree.tree, &join(&tree.ident), imports),
+        UseTree::Name(name) => imports.push(Import::Name(join(&name.ident))),
+        UseTree::Rename(rename) => {
+            imports.push(Import::Rename(join(&rename.ident), rename.rename.clone()))
+        }
+        UseTree::Glob(_) => imports.push(Import::Glob(prefix.clone())),
+        UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten(tree, prefix, imports);
+            }
+        }
+    }
+}
+
+/// Builds the tree importing `path`, with `leaf` at the end of it.
+fn use_tree(path: &Path, leaf: UseTree) -> UseTree {
+    path.segments.iter().rev().fold(leaf, |tree, segment| {
+        UseTree::Path(UsePath {
+            ident: segment.ident.clone(),
+            colon2_token: Default::default(),
+            tree: Box::new(tree),
+        })
+    })
+}
+
+/// Applies the mappings to the paths imported by a `use` tree, like `use tokio::{fs, time::sleep};`, which becomes
+/// `use {std::fs, std::thread::sleep};`. Imports whose name changes, like `use smol::io::AsyncReadExt;`, are renamed
+/// back to it, so the names used in the item still resolve.
+pub(crate) fn map_use<'a>(
+    mappings: impl Iterator<Item = &'a PathMapping> + Clone,
+    tree: &mut UseTree,
+) {
+    let mut imports = Vec::new();
+    flatten(
+        tree,
+        &Path {
+            leading_colon: None,
+            segments: Punctuated::new(),
+        },
+        &mut imports,
+    );
+    let mut mapped = false;
+    let mut map = |path: &Path| match mappings.clone().find_map(|mapping| mapping.apply(path)) {
+        Some(path) => {
+            mapped = true;
+            path
+        }
+        None => path.clone(),
+    };
+    let trees: Vec<_> = imports
+        .iter()
+        .map(|import| match import {
+            Import::Name(path) => {
+                let mapped = map(path);
+                let (mut parent, name) = (
+                    mapped.clone(),
+                    mapped.segments.last().unwrap().ident.clone(),
+                );
+                parent.segments.pop();
+                let ident = path.segments.last().unwrap().ident.clone();
+                let leaf = if ident == "self" {
+                    // `self` can only be imported in braces, like `use std::fs::{self};`
+                    UseTree::Group(UseGroup {
+                        brace_token: token::Brace::default(),
+                        items: Punctuated::from_iter([UseTree::Name(UseName { ident })]),
+                    })
+                } else if name == ident {
+                    UseTree::Name(UseName { ident })
+                } else {
+                    UseTree::Rename(UseRename {
+                        ident: name,
+                        as_token: Default::default(),
+                        rename: ident,
+                    })
+                };
+                use_tree(&parent, leaf)
+            }
+            Import::Rename(path, rename) => {
+                let mut parent = map(path);
+                let ident = parent.segments.pop().unwrap().into_value().ident;
+                use_tree(
+                    &parent,
+                    UseTree::Rename(UseRename {
+                        ident,
+                        as_token: Default::default(),
+                        rename: rename.clone(),
+                    }),
+                )
+            }
+            Import::Glob(path) => use_tree(
+                &map(path),
+                UseTree::Glob(syn::UseGlob {
+                    star_token: Default::default(),
+                }),
+            ),
+        })
+        .collect();
+    if !mapped {
+        return;
+    }
+    *tree = if trees.len() == 1 {
+        trees.into_iter().next().unwrap()
+    } else {
+        UseTree::Group(UseGroup {
+            brace_token: token::Brace::default(),
+            items: trees.into_iter().collect(),
+        })
+    };
+}
+
+/// embedded-hal-async's and embedded-io-async's traits, mapped to the blocking traits in embedded-hal and embedded-io,
+/// which have the same modules and methods.
+const EMBEDDED_HAL: &[(&str, &str)] = &[
+    ("embedded_hal_async", "embedded_hal"),
+    ("embedded_io_async", "embedded_io"),
+];
+
+/// reqwest's async API, mapped to `reqwest::blocking`.
+const REQWEST: &[(&str, &str)] = &[
+    ("reqwest::Body", "reqwest::blocking::Body"),
+    ("reqwest::Client", "reqwest::blocking::Client"),
+    ("reqwest::ClientBuilder", "reqwest::blocking::ClientBuilder"),
+    ("reqwest::Request", "reqwest::blocking::Request"),
+    (
+        "reqwest::RequestBuilder",
+        "reqwest::blocking::RequestBuilder",
+    ),
+    ("reqwest::Response", "reqwest::blocking::Response"),
+    ("reqwest::get", "reqwest::blocking::get"),
+    ("reqwest::multipart", "reqwest::blocking::multipart"),
+];
+
+/// async-std's modules that mirror std, mapped to std. The extension traits come first, since std's traits have the
+/// methods themselves. The profile also runs awaited tasks inline.
+const ASYNC_STD: &[(&str, &str)] = &[
+    ("async_std::task::sleep", "std::thread::sleep"),
+    ("async_std::task::yield_now", "std::thread::yield_now"),
+    ("async_std::io::ReadExt", "std::io::Read"),
+    ("async_std::io::WriteExt", "std::io::Write"),
+    ("async_std::io::SeekExt", "std::io::Seek"),
+    ("async_std::io::BufReadExt", "std::io::BufRead"),
+    ("async_std::fs", "std::fs"),
+    ("async_std::io", "std::io"),
+    ("async_std::net", "std::net"),
+    ("async_std::os", "std::os"),
+    ("async_std::path", "std::path"),
+];
+
+/// smol's timers and its modules that mirror std, mapped to std, along with the async-io, async-fs and async-net crates
+/// it reexports. Like `ASYNC_STD`, the profile also runs awaited tasks inline, including `unblock(f)`.
+const SMOL: &[(&str, &str)] = &[
+    ("smol::Timer::after", "std::thread::sleep"),
+    ("smol::future::yield_now", "std::thread::yield_now"),
+    ("smol::io::AsyncReadExt", "std::io::Read"),
+    ("smol::io::AsyncWriteExt", "std::io::Write"),
+    ("smol::io::AsyncSeekExt", "std::io::Seek"),
+    ("smol::io::AsyncBufReadExt", "std::io::BufRead"),
+    ("smol::fs", "std::fs"),
+    ("smol::io", "std::io"),
+    ("smol::net", "std::net"),
+    ("async_io::Timer::after", "std::thread::sleep"),
+    ("async_fs", "std::fs"),
+    ("async_net", "std::net"),
+];
+
+/// The timers and scheduling functions of async runtimes, mapped to remove-async-await-shim.
+const SHIM: &[(&str, &str)] = &[
+    ("tokio::time::sleep", "remove_async_await_shim::sleep"),
+    ("tokio::time::timeout", "remove_async_await_shim::timeout"),
+    (
+        "tokio::time::error::Elapsed",
+        "remove_async_await_shim::Elapsed",
+    ),
+    (
+        "tokio::task::yield_now",
+        "remove_async_await_shim::yield_now",
+    ),
+    ("async_std::task::sleep", "remove_async_await_shim::sleep"),
+    (
+        "async_std::future::timeout",
+        "remove_async_await_shim::timeout",
+    ),
+    (
+        "async_std::future::TimeoutError",
+        "remove_async_await_shim::Elapsed",
+    ),
+    (
+        "async_std::task::yield_now",
+        "remove_async_await_shim::yield_now",
+    ),
+];
+
+/// Looks up a built-in set of mappings by name, from `profile(...)`.
+pub(crate) fn profile(name: &Ident) -> syn::Result<Vec<PathMapping>> {
+    let mappings = match name.to_string().as_str() {
+        "async_std" => ASYNC_STD,
+        "embedded_hal" => EMBEDDED_HAL,
+        "reqwest" => REQWEST,
+        "shim" => SHIM,
+        "smol" => SMOL,
+        _ => {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "unknown profile `{}`, expected `async_std`, `embedded_hal`, `reqwest`, `shim` or `smol`",
+                    name
+                ),
+            ))
+        }
+    };
+    Ok(mappings
+        .iter()
+        .map(|(from, to)| PathMapping::new(from, to))
+        .collect())
+}
@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream;
+use syn::{
+    parse::{ParseStream, Parser},
+    parse_quote, Block, Expr, Ident, Item, LitStr, Macro, Stmt, Token,
+};
+
+use crate::keep;
+
+/// Which build the statements in a marker macro are for.
+enum Marker {
+    /// `async_only! { ... }`, which is dropped in the blocking build.
+    Async,
+    /// `sync_only! { ... }`, which is inlined in the blocking build.
+    Sync,
+}
+
+fn marker(mac: &Macro) -> Option<Marker> {
+    if is_macro(mac, "async_only") {
+        Some(Marker::Async)
+    } else if is_macro(mac, "sync_only") {
+        Some(Marker::Sync)
+    } else {
+        None
+    }
+}
+
+/// Whether `mac` is `remove_async_await::name!` or `name!`.
+fn is_macro(mac: &Macro, name: &str) -> bool {
+    let segments = &mac.path.segments;
+    match segments.len() {
+        1 => segments[0].ident == name,
+        2 => segments[0].ident == "remove_async_await" && segments[1].ident == name,
+        _ => false,
+    }
+}
+
+/// Returns the expression in `maybe_await!(expr)` or `maybe_await!(expr, feature = "...")`, which is awaited in the
+/// async build.
+pub(crate) fn maybe_awaited(mac: &Macro) -> Option<syn::Result<Expr>> {
+    if !is_macro(mac, "maybe_await") {
+        return None;
+    }
+    let parser = |input: ParseStream| {
+        let expr = input.parse()?;
+        // the feature only matters for the macro's own expansion
+        if input.parse::<Option<Token![,]>>()?.is_some() {
+            input.parse::<TokenStream>()?;
+        }
+        Ok(expr)
+    };
+    Some(parser.parse2(mac.tokens.clone()))
+}
+
+/// Returns the statements `stmt` becomes in the blocking build if it's a marker macro, like `sync_only! { ... }`, or has
+/// `#[remove_async_await::replace_with(...)]`. `last` is whether it's the last statement of its block.
+pub(crate) fn expand(stmt: &mut Stmt, last: bool) -> Option<syn::Result<Vec<Stmt>>> {
+    if let Some(replacement) = replacement(stmt, last) {
+        return Some(replacement);
+    }
+    let mac = match stmt {
+        Stmt::Item(Item::Macro(item)) if item.ident.is_none() => &item.mac,
+        Stmt::Macro(stmt) => &stmt.mac,
+        Stmt::Expr(Expr::Macro(e), _) => &e.mac,
+        _ => return None,
+    };
+    Some(match marker(mac)? {
+        Marker::Async => Ok(Vec::new()),
+        Marker::Sync => Block::parse_within.parse2(mac.tokens.clone()),
+    })
+}
+
+/// Returns the statements from `#[remove_async_await::replace_with(...)]` on `stmt`.
+fn replacement(stmt: &mut Stmt, last: bool) -> Option<syn::Result<Vec<Stmt>>> {
+    // only the tail expression of a block gives its value
+    let tail = last && matches!(stmt, Stmt::Expr(_, None));
+    let attrs = match stmt {
+        Stmt::Local(local) => &mut local.attrs,
+        Stmt::Item(item) => keep::item_attrs(item)?,
+        Stmt::Expr(e, _) => keep::expr_attrs(e)?,
+        Stmt::Macro(stmt) => &mut stmt.attrs,
+    };
+    let index = (attrs.iter()).position(|attr| keep::is_marker(attr.path(), "replace_with"))?;
+    let attr = attrs.remove(index);
+    Some(attr.parse_args_with(Block::parse_within).map(|mut stmts| {
+        if let (false, Some(Stmt::Expr(_, semi @ None))) = (tail, stmts.last_mut()) {
+            *semi = Some(Default::default());
+        }
+        stmts
+    }))
+}
+
+/// Returns what `spawn_or_call!(options, { ... })` runs in the blocking build: the block itself, or a thread running it
+/// with the `thread` option.
+pub(crate) fn spawned(mac: &Macro) -> Option<syn::Result<Expr>> {
+    if !is_macro(mac, "spawn_or_call") {
+        return None;
+    }
+    let parser = |input: ParseStream| {
+        let mut thread = false;
+        while !input.peek(syn::token::Brace) {
+            let option: Ident = input.parse()?;
+            if option == "thread" {
+                thread = true;
+            } else if option == "call" {
+                thread = false;
+            } else if option == "feature" {
+                // the feature only matters for the macro's own expansion
+                input.parse::<Token![=]>()?;
+                input.parse::<LitStr>()?;
+            } else {
+                return Err(syn::Error::new(
+                    option.span(),
+                    "expected `call`, `thread` or `feature = \"...\"`",
+                ));
+            }
+            input.parse::<Token![,]>()?;
+        }
+        let block: Block = input.parse()?;
+        Ok(if thread {
+            parse_quote!(::std::thread::spawn(move || #block))
+        } else {
+            let span = block.brace_token.span.join();
+            crate::blocks::in_closure(Some(Token![move](span)), span, block)
+        })
+    };
+    Some(parser.parse2(mac.tokens.clone()))
+}
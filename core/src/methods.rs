@@ -0,0 +1,140 @@
+use quote::ToTokens;
+use syn::{
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Expr, ExprMethodCall, FnArg, Ident, LitStr, Pat, Path, Signature, Token, Type,
+};
+
+/// The naming convention of an API's blocking methods, from `blocking_methods(suffix = "...")` or
+/// `blocking_methods(prefix = "...")`, like zbus' `call` and `call_blocking` or flume's `recv_async` and `recv`.
+/// Awaited method calls are renamed to follow it, optionally only on the receivers in `on(...)`.
+pub(crate) struct BlockingMethods {
+    prefix: String,
+    suffix: String,
+    /// Which async suffix to remove first, like `_async` in flume's `recv_async`.
+    strip: String,
+    /// The receivers the convention applies to, as expressions like `self.proxy` or types of the function's
+    /// parameters like `flume::Receiver`. Empty for every receiver.
+    on: Vec<Expr>,
+}
+
+impl Parse for BlockingMethods {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+        let mut methods = BlockingMethods {
+            prefix: String::new(),
+            suffix: String::new(),
+            strip: String::new(),
+            on: Vec::new(),
+        };
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            match key.to_string().as_str() {
+                "prefix" | "suffix" | "strip" => {
+                    content.parse::<Token![=]>()?;
+                    let value = content.parse::<LitStr>()?.value();
+                    match key.to_string().as_str() {
+                        "prefix" => methods.prefix = value,
+                        "suffix" => methods.suffix = value,
+                        _ => methods.strip = value,
+                    }
+                }
+                "on" => {
+                    let receivers;
+                    parenthesized!(receivers in content);
+                    methods
+                        .on
+                        .extend(Punctuated::<Expr, Token![,]>::parse_terminated(&receivers)?);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown blocking_methods argument `{}`, expected `prefix`, `suffix`, `strip` or `on`", key),
+                    ))
+                }
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+        Ok(methods)
+    }
+}
+
+impl BlockingMethods {
+    /// The parameters of a function whose type is one of the types in `on`, like `rx: &flume::Receiver<u32>`.
+    pub(crate) fn typed_receivers(&self, sig: &Signature) -> Vec<Ident> {
+        let types: Vec<&Path> = self
+            .on
+            .iter()
+            .filter_map(|receiver| match receiver {
+                Expr::Path(receiver) if receiver.qself.is_none() => Some(&receiver.path),
+                _ => None,
+            })
+            .collect();
+        sig.inputs
+            .iter()
+            .filter_map(|arg| {
+                let FnArg::Typed(arg) = arg else {
+                    return None;
+                };
+                let Pat::Ident(pat) = &*arg.pat else {
+                    return None;
+                };
+                let mut ty = &*arg.ty;
+                while let Type::Reference(reference) = ty {
+                    ty = &reference.elem;
+                }
+                let Type::Path(ty) = ty else {
+                    return None;
+                };
+                types
+                    .iter()
+                    .any(|path| same_path(path, &ty.path))
+                    .then(|| pat.ident.clone())
+            })
+            .collect()
+    }
+
+    /// Whether the convention applies to an awaited method call, with `receivers` from `typed_receivers`.
+    pub(crate) fn applies(&self, e: &ExprMethodCall, receivers: &[Ident]) -> bool {
+        if self.on.is_empty() {
+            return true;
+        }
+        let mut receiver = &*e.receiver;
+        while let Expr::Reference(reference) = receiver {
+            receiver = &reference.expr;
+        }
+        if matches!(receiver, Expr::Path(path) if receivers.iter().any(|ident| path.path.is_ident(ident)))
+        {
+            return true;
+        }
+        let tokens = receiver.to_token_stream().to_string();
+        self.on
+            .iter()
+            .any(|on| on.to_token_stream().to_string() == tokens)
+    }
+
+    /// The name of a method's blocking sibling, like `call_blocking` for `call`.
+    pub(crate) fn blocking_name(&self, method: &Ident) -> Ident {
+        let name = method.unraw().to_string();
+        let name = name.strip_suffix(self.strip.as_str()).unwrap_or(&name);
+        Ident::new(
+            &format!("{}{}{}", self.prefix, name, self.suffix),
+            method.span(),
+        )
+    }
+}
+
+/// Whether one of the paths ends with the other, like `flume::Receiver` and `Receiver`.
+fn same_path(a: &Path, b: &Path) -> bool {
+    a.segments
+        .iter()
+        .rev()
+        .zip(b.segments.iter().rev())
+        .all(|(a, b)| a.ident == b.ident)
+}
@@ -0,0 +1,102 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    fold::Fold,
+    parse::{ParseStream, Parser},
+    Ident, ItemMod, LitStr, Token,
+};
+
+use crate::{
+    args::{self, Args},
+    attrs, keep, strict, RemoveAsyncAwait,
+};
+
+/// Arguments passed to `#[remove_async_await::mirror(...)]`.
+struct MirrorArgs {
+    /// The name of the blocking module, from `name = "..."`.
+    name: Ident,
+    /// Everything else, which is passed on to `remove_async_await`.
+    args: Args,
+}
+
+impl MirrorArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut name = Ident::new("blocking", input.span());
+        let mut rest = TokenStream::new();
+
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                let key: Ident = input.fork().parse()?;
+                if key == "name" {
+                    input.parse::<Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    let lit: LitStr = input.parse()?;
+                    name = lit.parse().map_err(|_| {
+                        syn::Error::new(lit.span(), "the name should be a valid module name")
+                    })?;
+                    if !input.is_empty() {
+                        input.parse::<Token![,]>()?;
+                    }
+                    continue;
+                }
+            }
+            args::pass_through(input, &mut rest)?;
+        }
+
+        Ok(MirrorArgs {
+            name,
+            args: syn::parse2(rest)?,
+        })
+    }
+}
+
+/// Expands `#[remove_async_await::mirror]`: the module is emitted as is, along with a transformed copy named `name`
+/// (`blocking` by default).
+pub(crate) fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    let MirrorArgs { name, args } = match Parser::parse2(MirrorArgs::parse, args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let item = match syn::parse2::<ItemMod>(input) {
+        Ok(item) => item,
+        Err(_) => {
+            return quote! {
+                compile_error!("`mirror` only supports modules, like `mod name { ... }`.");
+            }
+        }
+    };
+    if item.content.is_none() {
+        return syn::Error::new_spanned(
+            &item,
+            "modules need to be inline to be mirrored, like `mod name { ... }`",
+        )
+        .to_compile_error();
+    }
+
+    if name == item.ident {
+        return syn::Error::new(
+            name.span(),
+            "the blocking module needs another name than the async one",
+        )
+        .to_compile_error();
+    }
+
+    let after = args.after.clone();
+    let strict = args.strict;
+    let warn_leaks = args.warn_leaks;
+    let mut blocking = item.clone();
+    blocking.ident = name;
+    blocking
+        .ident
+        .set_span(args.name_span.resolve(blocking.ident.span()));
+    if let Some(vis) = &args.vis {
+        blocking.vis = vis.clone();
+    }
+    let blocking = RemoveAsyncAwait::from_args(args)
+        .fold_item_mod(blocking)
+        .into_token_stream();
+    let blocking = strict::check(strict, warn_leaks, blocking);
+    let blocking = attrs::emit_after(&after, blocking);
+    let item = keep::strip(item.into_token_stream());
+    quote!(#item #blocking)
+}
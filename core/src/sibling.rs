@@ -0,0 +1,284 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    fold::Fold,
+    parse::{Parse, ParseStream},
+    Expr, Ident, ImplItem, Item, ItemImpl, LitStr, Path, ReturnType, Signature, Token,
+};
+
+use crate::{input::Input, mappings::PathMapping, types, RemoveAsyncAwait};
+
+/// How the blocking copy is named, from `sibling`, `sibling = "suffix"` or `rename = "template"`.
+#[derive(Clone)]
+pub(crate) struct Sibling {
+    /// The text before and after `{name}` in the template.
+    prefix: String,
+    suffix: String,
+    /// Where the new names are spanned, from `name_span = "..."`.
+    pub(crate) span: NameSpan,
+    /// The functions and methods of the annotated item that get blocking copies, whose awaited calls are renamed.
+    defined: Vec<Ident>,
+}
+
+/// Where generated names are spanned, from `name_span = "source"` or `name_span = "call_site"`.
+#[derive(Clone, Copy, Default)]
+pub(crate) enum NameSpan {
+    /// The span of the name they're generated from, so errors point at it and it keeps the hygiene of the macro that
+    /// wrote it, if any.
+    #[default]
+    Source,
+    /// The span of the attribute, so the names resolve like the ones the caller writes next to it.
+    CallSite,
+}
+
+impl Parse for NameSpan {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span: LitStr = input.parse()?;
+        match span.value().as_str() {
+            "source" => Ok(NameSpan::Source),
+            "call_site" => Ok(NameSpan::CallSite),
+            // items aren't hygienic with mixed-site spans, and def-site spans need nightly
+            _ => Err(syn::Error::new(
+                span.span(),
+                "expected `\"source\"` or `\"call_site\"`",
+            )),
+        }
+    }
+}
+
+impl NameSpan {
+    /// Returns the span of a name generated from one spanned with `source`.
+    pub(crate) fn resolve(self, source: Span) -> Span {
+        match self {
+            NameSpan::Source => source,
+            NameSpan::CallSite => Span::call_site(),
+        }
+    }
+}
+
+impl Parse for Sibling {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if !input.peek(Token![=]) {
+            return Ok(Sibling {
+                prefix: String::new(),
+                suffix: "_blocking".to_owned(),
+                span: NameSpan::default(),
+                defined: Vec::new(),
+            });
+        }
+        input.parse::<Token![=]>()?;
+        let suffix: LitStr = input.parse()?;
+        Ok(Sibling {
+            prefix: String::new(),
+            suffix: suffix.value(),
+            span: NameSpan::default(),
+            defined: Vec::new(),
+        })
+    }
+}
+
+impl Sibling {
+    /// Parses a template like `"blocking_{name}"` from `rename = "..."`.
+    pub(crate) fn template(template: &LitStr) -> syn::Result<Self> {
+        let value = template.value();
+        match value.split_once("{name}") {
+            Some((prefix, suffix)) if !suffix.contains("{name}") => Ok(Sibling {
+                prefix: prefix.to_owned(),
+                suffix: suffix.to_owned(),
+                span: NameSpan::default(),
+                defined: Vec::new(),
+            }),
+            _ => Err(syn::Error::new(
+                template.span(),
+                "the template should contain `{name}` once, like `\"{name}_blocking\"`",
+            )),
+        }
+    }
+
+    /// Whether the template keeps names as they are, like `"{name}"`.
+    pub(crate) fn is_identity(&self) -> bool {
+        self.prefix.is_empty() && self.suffix.is_empty()
+    }
+
+    /// Returns the name of the blocking copy of the function or module `ident`.
+    pub(crate) fn rename(&self, ident: &Ident) -> Ident {
+        let name = format!("{}{}{}", self.prefix, ident.unraw(), self.suffix);
+        new_ident(&name, self.span.resolve(ident.span()))
+    }
+
+    /// Returns the name of the blocking copy of the trait `ident`. The template is written for functions, so it's
+    /// converted to camel case: `"blocking_{name}"` turns `Client` into `BlockingClient`.
+    pub(crate) fn rename_type(&self, ident: &Ident) -> Ident {
+        let name = format!(
+            "{}{}{}",
+            camel_case(&self.prefix),
+            ident.unraw(),
+            camel_case(&self.suffix)
+        );
+        new_ident(&name, self.span.resolve(ident.span()))
+    }
+
+    /// Renames the function or method called by the awaited expression `e` if it's local, so the blocking copy calls
+    /// other blocking copies. Calls like `get()`, `Self::get()` and `self.get()` are local if `get` is copied along
+    /// with the annotated item, and paths like `self::get()` and `crate::get()` always are; anything else, like an
+    /// imported `sleep(d)`, and paths that are mapped, are left alone.
+    pub(crate) fn rename_awaited(&self, mappings: &[PathMapping], e: Expr) -> Expr {
+        match e {
+            Expr::Call(mut call) => {
+                if let Expr::Path(func) = &mut *call.func {
+                    if func.qself.is_none()
+                        && self.is_local(&func.path)
+                        && !is_mapped(mappings, &func.path)
+                    {
+                        let last = func.path.segments.last_mut().unwrap();
+                        last.ident = self.rename(&last.ident);
+                    }
+                }
+                Expr::Call(call)
+            }
+            Expr::MethodCall(mut call)
+                if matches!(&*call.receiver, Expr::Path(receiver) if receiver.path.is_ident("self"))
+                    && self.defined.contains(&call.method) =>
+            {
+                call.method = self.rename(&call.method);
+                Expr::MethodCall(call)
+            }
+            e => e,
+        }
+    }
+
+    pub(crate) fn is_local(&self, path: &Path) -> bool {
+        if path.leading_colon.is_some() {
+            return false;
+        }
+        let last = &path.segments.last().unwrap().ident;
+        match path.segments.len() {
+            1 => self.defined.contains(last),
+            2 if path.segments[0].ident == "Self" => self.defined.contains(last),
+            _ => ["self", "super", "crate"]
+                .iter()
+                .any(|root| path.segments[0].ident == root),
+        }
+    }
+}
+
+pub(crate) fn is_mapped(mappings: &[PathMapping], path: &Path) -> bool {
+    mappings.iter().any(|mapping| mapping.apply(path).is_some())
+}
+
+/// An identifier named `name`, which is raw if it's a keyword, like the blocking copy of `r#try` with `rename = "{name}"`.
+fn new_ident(name: &str, span: Span) -> Ident {
+    if syn::parse_str::<Ident>(name).is_ok() {
+        Ident::new(name, span)
+    } else {
+        Ident::new_raw(name, span)
+    }
+}
+
+fn camel_case(text: &str) -> String {
+    text.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the blocking copy of the item for `sibling` or `rename`. Functions are renamed, and so are the async methods
+/// and methods returning futures of inherent impls, which get a second impl. Traits and modules are renamed as a whole,
+/// keeping the names of their items, and trait impls implement the renamed trait.
+pub(crate) fn blocking_copy(
+    folder: &mut RemoveAsyncAwait,
+    sibling: &Sibling,
+    input: Input,
+) -> TokenStream {
+    if let Some(sibling) = &mut folder.args.sibling {
+        sibling.defined = copied(&input);
+    }
+    match input {
+        Input::Item(Item::Fn(mut item)) => {
+            item.sig.ident = sibling.rename(&item.sig.ident);
+            folder.fold_item_fn(item).into_token_stream()
+        }
+        Input::TraitMethod(mut item) => {
+            item.sig.ident = sibling.rename(&item.sig.ident);
+            folder.fold_trait_item_fn(item).into_token_stream()
+        }
+        Input::Item(Item::Trait(mut item)) => {
+            item.ident = sibling.rename_type(&item.ident);
+            without_renamed_calls(folder, |folder| folder.fold_item_trait(item)).into_token_stream()
+        }
+        Input::Item(Item::Impl(item)) => impl_copy(folder, sibling, item),
+        Input::Item(Item::Mod(mut item)) => {
+            if item.content.is_none() {
+                return syn::Error::new_spanned(
+                    &item,
+                    "modules need to be inline to be copied, like `mod name { ... }`",
+                )
+                .to_compile_error();
+            }
+            item.ident = sibling.rename(&item.ident);
+            without_renamed_calls(folder, |folder| folder.fold_item_mod(item)).into_token_stream()
+        }
+        _ => quote! {
+            compile_error!("`sibling` and `rename` currently only support functions, methods, traits, impls and modules.");
+        },
+    }
+}
+
+fn impl_copy(folder: &mut RemoveAsyncAwait, sibling: &Sibling, mut item: ItemImpl) -> TokenStream {
+    if let Some((_, path, _)) = &mut item.trait_ {
+        let last = path.segments.last_mut().unwrap();
+        last.ident = sibling.rename_type(&last.ident);
+        return without_renamed_calls(folder, |folder| folder.fold_item_impl(item))
+            .into_token_stream();
+    }
+    // everything else is already in the async impl
+    item.items
+        .retain(|item| matches!(item, ImplItem::Fn(method) if is_async(&method.sig)));
+    for item in &mut item.items {
+        if let ImplItem::Fn(method) = item {
+            method.sig.ident = sibling.rename(&method.sig.ident);
+        }
+    }
+    folder.fold_item_impl(item).into_token_stream()
+}
+
+/// The names of the functions that get blocking copies along with `input`: the function itself, or the async methods of
+/// an inherent impl.
+fn copied(input: &Input) -> Vec<Ident> {
+    match input {
+        Input::Item(Item::Fn(item)) => vec![item.sig.ident.clone()],
+        Input::TraitMethod(item) => vec![item.sig.ident.clone()],
+        Input::Item(Item::Impl(item)) if item.trait_.is_none() => item
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Fn(method) if is_async(&method.sig) => Some(method.sig.ident.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether the method is async or returns a future, so it needs a blocking copy.
+pub(crate) fn is_async(sig: &Signature) -> bool {
+    sig.asyncness.is_some()
+        || matches!(&sig.output, ReturnType::Type(_, ty) if types::future_output(ty).is_some())
+}
+
+/// Folds an item whose functions keep their names, so awaited calls aren't renamed either.
+fn without_renamed_calls<T>(
+    folder: &mut RemoveAsyncAwait,
+    fold: impl FnOnce(&mut RemoveAsyncAwait) -> T,
+) -> T {
+    let sibling = folder.args.sibling.take();
+    let item = fold(folder);
+    folder.args.sibling = sibling;
+    item
+}
@@ -0,0 +1,150 @@
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_quote, parse_quote_spanned, Expr, ExprCall, ExprMethodCall, FnArg, GenericArgument,
+    Ident, Pat, Path, PathArguments, Signature, TraitBound, Type, TypeParamBound, WherePredicate,
+};
+
+/// What `Sink` bounds are rewritten to, from `sink = closure` or `sink = path::to::Trait`.
+pub(crate) enum SinkTarget {
+    /// `S: Sink<Item, Error = E>` becomes `S: FnMut(Item) -> Result<(), E>`, and `sink.send(item)` becomes
+    /// `sink(item)`.
+    Closure,
+    /// `S: Sink<Item, Error = E>` becomes `S: Trait<Item, Error = E>`, and method calls are left alone.
+    Trait(Path),
+}
+
+impl Parse for SinkTarget {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.call(Path::parse_mod_style)?;
+        if path.is_ident("closure") {
+            Ok(SinkTarget::Closure)
+        } else {
+            Ok(SinkTarget::Trait(path))
+        }
+    }
+}
+
+/// If `path` is the `Sink` trait, returns its item and its `Error`, if it was given.
+fn sink_bound(path: &Path) -> Option<(Type, Option<Type>)> {
+    let last = path.segments.last()?;
+    if last.ident != "Sink" {
+        return None;
+    }
+    let args = match &last.arguments {
+        PathArguments::AngleBracketed(args) => &args.args,
+        _ => return None,
+    };
+    let item = args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    })?;
+    let error = args.iter().find_map(|arg| match arg {
+        GenericArgument::AssocType(binding) if binding.ident == "Error" => Some(binding.ty.clone()),
+        _ => None,
+    });
+    Some((item, error))
+}
+
+/// Rewrites `path` if it's the `Sink` trait. With `sink = closure`, bounds without an `Error` are left alone, since
+/// the closure's return type can't be named.
+pub(crate) fn rewrite_bound(target: &SinkTarget, path: &Path) -> Option<Path> {
+    let (item, error) = sink_bound(path)?;
+    match target {
+        SinkTarget::Closure => {
+            let error = error?;
+            let span = path.segments.last().unwrap().ident.span();
+            let bound: TraitBound =
+                parse_quote_spanned!(span=> FnMut(#item) -> ::std::result::Result<(), #error>);
+            Some(bound.path)
+        }
+        SinkTarget::Trait(target) => {
+            let mut target = target.clone();
+            target.segments.last_mut().unwrap().arguments =
+                path.segments.last().unwrap().arguments.clone();
+            Some(target)
+        }
+    }
+}
+
+/// Arguments of `sig` that are sinks, like `sink: S` or `sink: &mut impl Sink<T, Error = E>`, and their `Error`.
+/// Only sinks whose `Error` is given are included, like in `rewrite_bound`.
+pub(crate) fn sink_args(sig: &Signature) -> Vec<(Ident, Type)> {
+    let mut params = Vec::new();
+    for param in sig.generics.type_params() {
+        let error = sink_error(&param.bounds).or_else(|| {
+            let predicates = sig.generics.where_clause.iter().flat_map(|w| &w.predicates);
+            predicates
+                .filter_map(|predicate| match predicate {
+                    WherePredicate::Type(predicate) => match &predicate.bounded_ty {
+                        Type::Path(ty) if ty.qself.is_none() && ty.path.is_ident(&param.ident) => {
+                            sink_error(&predicate.bounds)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .next()
+        });
+        if let Some(error) = error {
+            params.push((param.ident.clone(), error));
+        }
+    }
+
+    let mut args = Vec::new();
+    for arg in &sig.inputs {
+        let arg = match arg {
+            FnArg::Typed(arg) => arg,
+            FnArg::Receiver(_) => continue,
+        };
+        let ident = match &*arg.pat {
+            Pat::Ident(pat) => &pat.ident,
+            _ => continue,
+        };
+        let mut ty = &*arg.ty;
+        while let Type::Reference(reference) = ty {
+            ty = &reference.elem;
+        }
+        let error = match ty {
+            Type::Path(ty) if ty.qself.is_none() => params
+                .iter()
+                .find(|(param, _)| ty.path.is_ident(param))
+                .map(|(_, error)| error.clone()),
+            Type::ImplTrait(ty) => sink_error(&ty.bounds),
+            _ => None,
+        };
+        if let Some(error) = error {
+            args.push((ident.clone(), error));
+        }
+    }
+    args
+}
+
+/// If one of `bounds` is the `Sink` trait with an `Error`, returns the `Error`.
+fn sink_error<'a>(bounds: impl IntoIterator<Item = &'a TypeParamBound>) -> Option<Type> {
+    bounds.into_iter().find_map(|bound| match bound {
+        TypeParamBound::Trait(bound) => sink_bound(&bound.path).and_then(|(_, error)| error),
+        _ => None,
+    })
+}
+
+/// If `e` calls a `SinkExt` method on one of `sinks`, returns the equivalent for a closure: `sink.send(item)` and
+/// `sink.feed(item)` become `sink(item)`, and `sink.flush()` and `sink.close()` become `Ok(())`.
+pub(crate) fn closure_call(sinks: &[(Ident, Type)], e: &ExprMethodCall) -> Option<Expr> {
+    let receiver = match &*e.receiver {
+        Expr::Path(receiver) if receiver.qself.is_none() => receiver,
+        _ => return None,
+    };
+    let (_, error) = sinks
+        .iter()
+        .find(|(sink, _)| receiver.path.is_ident(sink))?;
+    match (e.method.to_string().as_str(), e.args.len()) {
+        ("send" | "feed", 1) => Some(Expr::Call(ExprCall {
+            attrs: e.attrs.clone(),
+            func: e.receiver.clone(),
+            paren_token: e.paren_token,
+            args: e.args.clone(),
+        })),
+        ("flush" | "close", 0) => Some(parse_quote!(::std::result::Result::<(), #error>::Ok(()))),
+        _ => None,
+    }
+}
@@ -0,0 +1,285 @@
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    fold::{self, Fold},
+    parse_quote, parse_quote_spanned, Expr, ExprCall, ExprMethodCall, FnArg, GenericArgument, Ident,
+    Local, Pat, Path, PathArguments, PathSegment, Signature, Type, TypeParamBound, WherePredicate,
+};
+
+use crate::types;
+
+/// The crates whose `Stream` trait is rewritten when named by path, even without an `Item`.
+const CRATES: &[&str] = &["futures", "futures_core", "futures_util", "tokio_stream"];
+
+/// If `path` is the `Stream` trait, returns the equivalent `Iterator` bound, keeping its `Item`. Only paths into
+/// [`CRATES`] and `Stream<Item = T>` count, so other traits that happen to be called `Stream` are left alone.
+pub(crate) fn iterator_bound(path: &Path) -> Option<Path> {
+    let last = path.segments.last()?;
+    if last.ident != "Stream" {
+        return None;
+    }
+    let in_crate =
+        path.segments.len() > 1 && CRATES.iter().any(|name| path.segments[0].ident == name);
+    let has_item = match &last.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .any(|arg| matches!(arg, GenericArgument::AssocType(item) if item.ident == "Item")),
+        _ => false,
+    };
+    if !in_crate && !has_item {
+        return None;
+    }
+    Some(Path::from(PathSegment {
+        ident: Ident::new("Iterator", last.ident.span()),
+        arguments: last.arguments.clone(),
+    }))
+}
+
+/// Folds a call or method call, lowering stream constructors like `stream::iter(x)` and `StreamExt` methods without an
+/// `Iterator` equivalent. Anything else is folded as usual. `on_stream` is whether the receiver of a method call is known
+/// to be a stream.
+pub(crate) fn fold_stream_expr<F: Fold>(folder: &mut F, e: Expr, on_stream: bool) -> Expr {
+    match e {
+        Expr::Call(e) if constructor(&e).is_some() => {
+            let name = constructor(&e).unwrap().clone();
+            let mut args = fold::fold_expr_call(folder, e).args.into_iter();
+            match name.ident.to_string().as_str() {
+                // generics aren't kept, since `IntoIterator::into_iter` doesn't have any
+                "iter" => {
+                    let iter = args.next().unwrap();
+                    parse_quote!(::std::iter::IntoIterator::into_iter(#iter))
+                }
+                "unfold" => {
+                    let (init, f) = (args.next().unwrap(), args.next().unwrap());
+                    let state = Ident::new("state", Span::mixed_site());
+                    let next = Ident::new("next", Span::mixed_site());
+                    parse_quote!({
+                        let mut #state = ::std::option::Option::Some(#init);
+                        let mut #next = #f;
+                        ::std::iter::from_fn(move || {
+                            let (item, next_state) = #next(#state.take()?)?;
+                            #state = ::std::option::Option::Some(next_state);
+                            ::std::option::Option::Some(item)
+                        })
+                    })
+                }
+                _ => {
+                    let arguments = &name.arguments;
+                    let ident = &name.ident;
+                    parse_quote!(::std::iter::#ident #arguments(#(#args),*))
+                }
+            }
+        }
+        Expr::MethodCall(mut e) if is_stream_method(&e, on_stream) => {
+            let method = e.method.to_string();
+            match method.as_str() {
+                // there's nothing to run concurrently, so `.buffered(n)` is the stream itself
+                "buffered" | "buffer_unordered" => folder.fold_expr(*e.receiver),
+                "for_each_concurrent" => {
+                    e.method = Ident::new("for_each", e.method.span());
+                    e.args = e.args.into_iter().skip(1).collect();
+                    folder.fold_expr(Expr::MethodCall(e))
+                }
+                // the closure no longer returns a future, so `then` is just `map`
+                "then" => {
+                    e.method = Ident::new("map", e.method.span());
+                    folder.fold_expr(Expr::MethodCall(e))
+                }
+                "try_next" => {
+                    let receiver = folder.fold_expr(*e.receiver);
+                    parse_quote!(#receiver.next().transpose())
+                }
+                "try_collect" => {
+                    let receiver = folder.fold_expr(*e.receiver);
+                    let collection = match e.turbofish.as_ref().and_then(|t| t.args.first()) {
+                        Some(GenericArgument::Type(ty)) => quote!(#ty),
+                        _ => quote!(_),
+                    };
+                    parse_quote!(#receiver.collect::<::std::result::Result<#collection, _>>())
+                }
+                _ => unreachable!(),
+            }
+        }
+        e => fold::fold_expr(folder, e),
+    }
+}
+
+/// If `e` calls a stream constructor like `futures::stream::iter` or `tokio_stream::once`, returns its last segment.
+fn constructor(e: &ExprCall) -> Option<&PathSegment> {
+    let path = match &*e.func {
+        Expr::Path(func) if func.qself.is_none() => &func.path,
+        _ => return None,
+    };
+    let mut segments = path.segments.iter().rev();
+    let (last, module) = (segments.next()?, segments.next()?);
+    if module.ident != "stream" && module.ident != "tokio_stream" {
+        return None;
+    }
+    let arity = match last.ident.to_string().as_str() {
+        "empty" => 0,
+        "iter" | "once" | "repeat" | "repeat_with" => 1,
+        "unfold" => 2,
+        _ => return None,
+    };
+    (e.args.len() == arity).then_some(last)
+}
+
+/// Whether `e` calls a `StreamExt` or `TryStreamExt` method that `Iterator` doesn't have (or has with a different
+/// meaning). Other types can have methods with the same names, like a parser's `try_next`, so apart from `then` they only
+/// count `on_stream`, when the receiver is known to be a stream.
+fn is_stream_method(e: &ExprMethodCall, on_stream: bool) -> bool {
+    match e.method.to_string().as_str() {
+        "buffered" | "buffer_unordered" => on_stream && e.args.len() == 1,
+        "for_each_concurrent" => on_stream && e.args.len() == 2,
+        "try_next" | "try_collect" => on_stream && e.args.is_empty(),
+        "then" => is_async_then(e),
+        _ => false,
+    }
+}
+
+/// Whether `e` is `.then(|x| async { ... })`. `bool::then` also exists, so only closures returning an async block count.
+fn is_async_then(e: &ExprMethodCall) -> bool {
+    e.method == "then"
+        && matches!(
+            e.args.first(),
+            Some(Expr::Closure(closure)) if e.args.len() == 1 && matches!(*closure.body, Expr::Async(_))
+        )
+}
+
+/// Whether `e` is known to be a stream: one of the `streams` variables, or built from a stream constructor or
+/// `.then(|x| async { ... })`, like `stream::iter(x).map(f)`.
+pub(crate) fn is_stream(e: &Expr, streams: &[Ident]) -> bool {
+    match e {
+        Expr::Call(e) => constructor(e).is_some(),
+        Expr::MethodCall(e) => is_async_then(e) || is_stream(&e.receiver, streams),
+        Expr::Path(e) => e.path.get_ident().is_some_and(|ident| streams.contains(ident)),
+        Expr::Reference(e) => is_stream(&e.expr, streams),
+        Expr::Paren(e) => is_stream(&e.expr, streams),
+        _ => false,
+    }
+}
+
+/// If `local` binds a single variable, returns it along with whether it's a stream, from its type or its initializer.
+pub(crate) fn local_stream<'a>(local: &'a Local, streams: &[Ident]) -> Option<(&'a Ident, bool)> {
+    let (pat, ty) = match &local.pat {
+        Pat::Type(pat) => (&*pat.pat, Some(&*pat.ty)),
+        pat => (pat, None),
+    };
+    let Pat::Ident(pat) = pat else {
+        return None;
+    };
+    let stream = ty.is_some_and(|ty| boxed_iterator(ty).is_some())
+        || local.init.as_ref().is_some_and(|init| is_stream(&init.expr, streams));
+    Some((&pat.ident, stream))
+}
+
+/// The parameters of `sig` that are streams, like `stream: impl Stream<Item = u32>` or `stream: &mut S` with
+/// `S: Stream<Item = u32>`.
+pub(crate) fn stream_params(sig: &Signature) -> Vec<Ident> {
+    let is_stream_bound = |bound: &TypeParamBound| {
+        matches!(bound, TypeParamBound::Trait(bound) if iterator_bound(&bound.path).is_some())
+    };
+    let is_stream_param = |ident: &Ident| {
+        sig.generics.type_params().any(|param| {
+            param.ident == *ident && param.bounds.iter().any(is_stream_bound)
+        }) || sig.generics.where_clause.iter().flat_map(|clause| &clause.predicates).any(
+            |predicate| {
+                matches!(predicate, WherePredicate::Type(predicate)
+                    if matches!(&predicate.bounded_ty, Type::Path(ty) if ty.path.is_ident(ident))
+                        && predicate.bounds.iter().any(is_stream_bound))
+            },
+        )
+    };
+    sig.inputs
+        .iter()
+        .filter_map(|arg| {
+            let FnArg::Typed(arg) = arg else {
+                return None;
+            };
+            let Pat::Ident(pat) = &*arg.pat else {
+                return None;
+            };
+            let mut ty = &*arg.ty;
+            while let Type::Reference(reference) = ty {
+                ty = &reference.elem;
+            }
+            let stream = match ty {
+                Type::ImplTrait(ty) => ty.bounds.iter().any(is_stream_bound),
+                Type::Path(path) => {
+                    boxed_iterator(ty).is_some()
+                        || path.path.get_ident().is_some_and(is_stream_param)
+                }
+                _ => false,
+            };
+            stream.then(|| pat.ident.clone())
+        })
+        .collect()
+}
+
+/// If `ty` is a boxed stream like `BoxStream<'a, T>` or `Pin<Box<dyn Stream<Item = T> + Send + 'a>>`, returns the
+/// equivalent boxed iterator, `Box<dyn Iterator<Item = T> + 'a>`. `Send` is dropped, since most iterators aren't.
+pub(crate) fn boxed_iterator(ty: &Type) -> Option<Type> {
+    let last = match ty {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last()?,
+        _ => return None,
+    };
+    if last.ident == "BoxStream" || last.ident == "LocalBoxStream" {
+        let item = types::first_type_arg(&last.arguments)?;
+        let lifetime = match &last.arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Lifetime(lifetime) => Some(lifetime),
+                _ => None,
+            }),
+            _ => None,
+        };
+        let lifetime = lifetime.map(|lifetime| quote!(+ #lifetime));
+        return Some(
+            parse_quote_spanned!(last.ident.span()=> ::std::boxed::Box<dyn Iterator<Item = #item> #lifetime>),
+        );
+    }
+    if last.ident != "Pin" {
+        return None;
+    }
+    let span = last.ident.span();
+    let boxed = match types::first_type_arg(&last.arguments)? {
+        Type::Path(boxed) if boxed.qself.is_none() => boxed,
+        _ => return None,
+    };
+    let boxed = boxed.path.segments.last()?;
+    let object = match types::first_type_arg(&boxed.arguments)? {
+        Type::TraitObject(object) if boxed.ident == "Box" => object,
+        _ => return None,
+    };
+    // the bounds may already have been rewritten to `Iterator`
+    let is_stream = |path: &Path| {
+        iterator_bound(path).is_some() || path.segments.last().unwrap().ident == "Iterator"
+    };
+    if !object
+        .bounds
+        .iter()
+        .any(|bound| matches!(bound, TypeParamBound::Trait(bound) if is_stream(&bound.path)))
+    {
+        return None;
+    }
+    let bounds = object.bounds.into_iter().filter_map(|bound| match bound {
+        TypeParamBound::Trait(bound) if bound.path.is_ident("Send") => None,
+        TypeParamBound::Trait(mut bound) => {
+            if let Some(path) = iterator_bound(&bound.path) {
+                bound.path = path;
+            }
+            Some(TypeParamBound::Trait(bound))
+        }
+        bound => Some(bound),
+    });
+    Some(parse_quote_spanned!(span=> ::std::boxed::Box<dyn #(#bounds)+*>))
+}
+
+/// Whether `e` is built from a stream constructor, like `stream::iter(x).map(f)`.
+pub(crate) fn is_constructed(e: &Expr) -> bool {
+    match e {
+        Expr::Call(e) => constructor(e).is_some(),
+        Expr::MethodCall(e) => is_constructed(&e.receiver),
+        _ => false,
+    }
+}
@@ -0,0 +1,176 @@
+use proc_macro2::{TokenStream, TokenTree};
+use syn::{
+    visit::{self, Visit},
+    ExprAsync, ExprAwait, ExprCall, ExprClosure, ImplItemFn, ItemFn, Macro, Path, TraitBound,
+    TraitItemFn, TypePath,
+};
+
+use crate::{leaks, tasks};
+
+/// Adds an error for every `.await` left in the blocking item with `strict`, such as ones inside macros that aren't
+/// known to take expressions, and for constructs without a blocking equivalent, like `select!`, tasks that weren't
+/// inlined or `JsFuture`s without a bridge. Async code that was kept on purpose, like a function marked with `keep`,
+/// is fine. Without `strict`, functions that still use async crates or types get a warning instead, with `warn_leaks`.
+pub(crate) fn check(strict: bool, warn_leaks: bool, mut output: TokenStream) -> TokenStream {
+    if strict {
+        let errors = errors(&output);
+        output.extend(errors);
+        output
+    } else if warn_leaks {
+        leaks::warn(output)
+    } else {
+        output
+    }
+}
+
+fn errors(output: &TokenStream) -> TokenStream {
+    let mut checker = Checker::default();
+    if let Ok(file) = syn::parse2::<syn::File>(output.clone()) {
+        checker.visit_file(&file);
+    } else if let Ok(method) = syn::parse2::<TraitItemFn>(output.clone()) {
+        checker.visit_trait_item_fn(&method);
+    }
+    checker
+        .errors
+        .into_iter()
+        .map(|err| err.to_compile_error())
+        .collect()
+}
+
+const MESSAGE: &str = "`.await` is left after the transform; if it's in a macro, use `sync_only!`, `replace_with` or `keep`";
+
+/// Async types and traits, which the transform rewrites where it can.
+pub(crate) const ASYNC_TYPES: &[&str] = &[
+    "Future",
+    "IntoFuture",
+    "BoxFuture",
+    "LocalBoxFuture",
+    "Stream",
+    "TryStream",
+    "BoxStream",
+    "LocalBoxStream",
+    "Sink",
+    "AsyncRead",
+    "AsyncWrite",
+    "AsyncBufRead",
+    "AsyncSeek",
+];
+
+#[derive(Default)]
+struct Checker {
+    errors: Vec<syn::Error>,
+}
+
+impl Checker {
+    fn check_type(&mut self, path: &Path) {
+        if let Some(last) = path.segments.last() {
+            if ASYNC_TYPES.iter().any(|name| last.ident == name) {
+                self.errors.push(syn::Error::new_spanned(
+                    path,
+                    format!("`{}` is left after the transform; rewrite it with `types(...)`, or use `keep` if it should stay async", last.ident),
+                ));
+            }
+        }
+    }
+
+    /// Finds awaits in the tokens of a macro, which aren't parsed.
+    fn check_tokens(&mut self, tokens: TokenStream) {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(dot) if dot.as_char() == '.' => {
+                    if let Some(TokenTree::Ident(ident)) = tokens.peek() {
+                        if ident == "await" {
+                            self.errors.push(syn::Error::new(ident.span(), MESSAGE));
+                        }
+                    }
+                }
+                TokenTree::Group(group) => self.check_tokens(group.stream()),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Checker {
+    // async code that's left is kept on purpose
+    fn visit_item_fn(&mut self, i: &'ast ItemFn) {
+        if i.sig.asyncness.is_none() {
+            visit::visit_item_fn(self, i);
+        }
+    }
+
+    fn visit_impl_item_fn(&mut self, i: &'ast ImplItemFn) {
+        if i.sig.asyncness.is_none() {
+            visit::visit_impl_item_fn(self, i);
+        }
+    }
+
+    fn visit_trait_item_fn(&mut self, i: &'ast TraitItemFn) {
+        if i.sig.asyncness.is_none() {
+            visit::visit_trait_item_fn(self, i);
+        }
+    }
+
+    fn visit_expr_async(&mut self, _: &'ast ExprAsync) {}
+
+    fn visit_expr_closure(&mut self, i: &'ast ExprClosure) {
+        if i.asyncness.is_none() {
+            visit::visit_expr_closure(self, i);
+        }
+    }
+
+    fn visit_expr_await(&mut self, i: &'ast ExprAwait) {
+        self.errors
+            .push(syn::Error::new(i.await_token.span, MESSAGE));
+        visit::visit_expr_await(self, i);
+    }
+
+    fn visit_expr_call(&mut self, i: &'ast ExprCall) {
+        if tasks::is_spawn(i) {
+            self.errors.push(syn::Error::new_spanned(
+                &i.func,
+                "tasks are only inlined in functions returning a `JoinHandle`; map `spawn` with `map(...)`, or use `replace_with` or `keep`",
+            ));
+        }
+        // promises only resolve on wasm, so the blocking code needs a bridge
+        if crate::calls(i, &["JsFuture", "from"]) {
+            self.errors.push(syn::Error::new_spanned(
+                &i.func,
+                "`JsFuture` only resolves promises on wasm; give a blocking bridge with `wasm_bindgen(js_future = path)`, or use `keep`",
+            ));
+        }
+        visit::visit_expr_call(self, i);
+    }
+
+    fn visit_type_path(&mut self, i: &'ast TypePath) {
+        self.check_type(&i.path);
+        visit::visit_type_path(self, i);
+    }
+
+    fn visit_trait_bound(&mut self, i: &'ast TraitBound) {
+        self.check_type(&i.path);
+        visit::visit_trait_bound(self, i);
+    }
+
+    fn visit_macro(&mut self, i: &'ast Macro) {
+        // the rules of `macro_rules!` definitions aren't code yet
+        if i.path.is_ident("macro_rules") {
+            return;
+        }
+        if let Some(last) = i.path.segments.last() {
+            let name = last.ident.to_string();
+            if matches!(
+                name.as_str(),
+                "select" | "select_biased" | "join" | "try_join"
+            ) {
+                self.errors.push(syn::Error::new_spanned(
+                    &i.path,
+                    format!("`{}!` has no blocking equivalent; use `sync_only!` or `replace_with` for the blocking code, or `keep`", name),
+                ));
+                return;
+            }
+        }
+        self.check_tokens(i.tokens.clone());
+    }
+}
@@ -0,0 +1,121 @@
+use syn::{
+    fold::Fold,
+    visit::{self, Visit},
+    Block, Expr, ExprCall, Item, Stmt, Type,
+};
+
+use crate::types;
+
+/// If `ty` is a task handle like tokio's `JoinHandle<T>`, returns `T`. `std::thread::JoinHandle` is left alone when it's
+/// named through `thread`, see [`returns_task`] for the other cases.
+pub(crate) fn join_handle_output(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => {
+            let segments = &ty.path.segments;
+            let last = segments.last()?;
+            if last.ident != "JoinHandle"
+                || segments.iter().any(|segment| segment.ident == "thread")
+            {
+                return None;
+            }
+            types::first_type_arg(&last.arguments)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a function returning `ty` with `body` returns a task handle, so it should return the task's output instead. A
+/// `JoinHandle` that isn't named through tokio, async-std or smol, like an imported one, only counts if the body spawns a
+/// task, since it could be `std::thread::JoinHandle`.
+pub(crate) fn returns_task(ty: &Type, body: Option<&Block>) -> bool {
+    if join_handle_output(ty).is_none() {
+        return false;
+    }
+    let named = matches!(ty, Type::Path(ty) if ty.path.segments.iter().any(|segment| {
+        segment.ident == "tokio" || segment.ident == "async_std" || segment.ident == "smol"
+    }));
+    named
+        || body.is_some_and(|body| {
+            let mut finder = SpawnFinder(false);
+            finder.visit_block(body);
+            finder.0
+        })
+}
+
+/// Looks for a call spawning a task, outside of nested items.
+struct SpawnFinder(bool);
+
+impl Visit<'_> for SpawnFinder {
+    fn visit_expr_call(&mut self, e: &ExprCall) {
+        self.0 |= is_spawn(e);
+        visit::visit_expr_call(self, e);
+    }
+
+    fn visit_item(&mut self, _: &Item) {}
+}
+
+/// The functions that spawn tasks, which can also be called through an import, like `task::spawn` or `spawn`.
+const SPAWNS: &[&[&str]] = &[
+    &["tokio", "spawn"],
+    &["tokio", "task", "spawn"],
+    &["tokio", "task", "spawn_local"],
+    &["tokio", "task", "spawn_blocking"],
+    &["async_std", "task", "spawn"],
+    &["async_std", "task", "spawn_local"],
+    &["async_std", "task", "spawn_blocking"],
+    &["smol", "spawn"],
+    &["smol", "unblock"],
+    &["blocking", "unblock"],
+];
+
+/// Whether `e` spawns a task, like `tokio::spawn(future)`, `tokio::task::spawn_blocking(f)` or `smol::unblock(f)`.
+/// Threads and other pools, like `std::thread::spawn` and `rayon::spawn`, aren't tasks.
+pub(crate) fn is_spawn(e: &ExprCall) -> bool {
+    let path = match &*e.func {
+        Expr::Path(func) if func.qself.is_none() && e.args.len() == 1 => &func.path,
+        _ => return false,
+    };
+    let len = path.segments.len();
+    SPAWNS.iter().any(|spawn| {
+        // `::tokio::spawn` names the crate, so it can't be an import
+        len <= spawn.len()
+            && (path.leading_colon.is_none() || len == spawn.len())
+            && crate::path_ends_with(path, &spawn[spawn.len() - len..])
+    })
+}
+
+/// Inlines a task spawned by `e`, so `spawn(future)` becomes the future's output and `spawn_blocking(f)` (or
+/// `unblock(f)`) becomes `f()`.
+pub(crate) fn inline_spawn<F: Fold>(folder: &mut F, e: ExprCall) -> Expr {
+    let blocking = crate::calls(&e, &["spawn_blocking"]) || crate::calls(&e, &["unblock"]);
+    let task = e.args.into_iter().next().unwrap();
+    if !blocking {
+        return folder.fold_expr(task);
+    }
+    match task {
+        // `spawn_blocking(move || value)` becomes just `value`
+        Expr::Closure(closure) if closure.inputs.is_empty() => {
+            let span = closure.or1_token.span;
+            let body = match *closure.body {
+                Expr::Block(mut body)
+                    if body.attrs.is_empty()
+                        && body.label.is_none()
+                        && matches!(body.block.stmts.as_slice(), [Stmt::Expr(_, None)]) =>
+                {
+                    match body.block.stmts.pop() {
+                        Some(Stmt::Expr(body, None)) => body,
+                        _ => unreachable!(),
+                    }
+                }
+                body => body,
+            };
+            crate::blocks::parenthesized(folder.fold_expr(body), span)
+        }
+        f => Expr::Call(ExprCall {
+            attrs: e.attrs,
+            func: Box::new(folder.fold_expr(f)),
+            paren_token: e.paren_token,
+            args: Default::default(),
+        }),
+    }
+}
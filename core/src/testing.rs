@@ -0,0 +1,29 @@
+//! Helpers for snapshot testing what items expand to.
+//!
+//! ```rs
+//! #[test]
+//! fn get() {
+//!     let output = remove_async_await_core::testing::expand(
+//!         quote!(map(tokio::time::sleep = std::thread::sleep)),
+//!         quote! {
+//!             pub async fn wait() {
+//!                 tokio::time::sleep(DURATION).await;
+//!             }
+//!         },
+//!     );
+//!     assert_eq!(output, "pub fn wait() {\n    std::thread::sleep(DURATION);\n}\n");
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+
+/// Returns what `#[remove_async_await(args)]` expands `input` to, formatted with prettyplease.
+pub fn expand(args: TokenStream, input: TokenStream) -> String {
+    format(&crate::expand(args, input))
+}
+
+/// Formats the output of any of the `expand` functions, like [`expand_dual`](crate::expand_dual), with prettyplease.
+/// Output that can't be parsed as a file, like a trait method, is returned as plain tokens.
+pub fn format(output: &TokenStream) -> String {
+    crate::format::format(output)
+}
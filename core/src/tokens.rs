@@ -0,0 +1,110 @@
+//! Removing `async` and `.await` by walking the tokens, without parsing them. This is all that's built without the
+//! `full` feature, and it's what remove-async-await uses for `remove_async_await_string`, its lightweight mode and
+//! `macro_tokens`, along with the expansions it shares with the transform, like [`in_closure`].
+
+use proc_macro2::{token_stream, Delimiter, Group, Ident, Span, TokenStream, TokenTree};
+use quote::quote_spanned;
+use std::iter::Peekable;
+
+/// Runs `block` in a closure called right away, with `capture` (`move` or nothing) before it, so its `return` and `?`
+/// only leave the block. The closure is passed to a function instead of being called where it's declared, which
+/// clippy would flag. Hygiene doesn't apply to items, so the function's name can't be one the block could use.
+pub fn in_closure(capture: TokenStream, span: Span, block: TokenStream) -> TokenStream {
+    let call = Ident::new("__remove_async_await_call", Span::mixed_site());
+    quote_spanned! {span=> {
+        fn #call<T>(f: impl ::core::ops::FnOnce() -> T) -> T {
+            f()
+        }
+        #call(#capture || #block)
+    }}
+}
+
+/// Removes `async` (along with `move` before a block, so `async move { ... }` becomes a block) and `.await` from the
+/// tokens. Literals (and so doc comments), names containing "async", raw identifiers like `r#async` and `macro_rules!`
+/// definitions are left alone, and the tokens that are left keep their spans. The paths in `kept`, by segment, are
+/// left as they are too, along with the call or macro input right after them and an `.await` on it.
+pub fn remove(input: TokenStream, kept: &[Vec<String>]) -> TokenStream {
+    let mut output = Vec::new();
+    let mut tokens = input.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "async" => {
+                let mut ahead = tokens.clone();
+                if let (Some(TokenTree::Ident(keyword)), Some(TokenTree::Group(group))) =
+                    (ahead.next(), ahead.next())
+                {
+                    if keyword == "move" && group.delimiter() == Delimiter::Brace {
+                        tokens.next();
+                    }
+                }
+            }
+            TokenTree::Punct(dot)
+                if dot.as_char() == '.'
+                    && matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == "await") =>
+            {
+                tokens.next();
+            }
+            // `macro_rules!` definitions, whose rules can mention `async` and `.await` as they like
+            TokenTree::Ident(ident) if ident == "macro_rules" => {
+                output.push(TokenTree::Ident(ident));
+                output.extend(tokens.next_if(
+                    |token| matches!(token, TokenTree::Punct(bang) if bang.as_char() == '!'),
+                ));
+                output.extend(tokens.next_if(|token| matches!(token, TokenTree::Ident(_))));
+                output.extend(tokens.next_if(|token| matches!(token, TokenTree::Group(_))));
+            }
+            TokenTree::Ident(ident) => {
+                let path = kept.iter().find(|path| is_kept(&ident, &tokens, path));
+                output.push(TokenTree::Ident(ident));
+                if let Some(path) = path {
+                    keep(&mut tokens, &mut output, path.len());
+                }
+            }
+            TokenTree::Group(group) => {
+                let mut removed = Group::new(group.delimiter(), remove(group.stream(), kept));
+                removed.set_span(group.span());
+                output.push(TokenTree::Group(removed));
+            }
+            token => output.push(token),
+        }
+    }
+    output.into_iter().collect()
+}
+
+/// Whether `path` starts at `first`, followed by `tokens`.
+fn is_kept(first: &Ident, tokens: &Peekable<token_stream::IntoIter>, path: &[String]) -> bool {
+    let mut tokens = tokens.clone();
+    let mut segments = path.iter();
+    if segments.next().is_none_or(|segment| first != segment) {
+        return false;
+    }
+    segments.all(|segment| {
+        matches!(
+            (tokens.next(), tokens.next(), tokens.next()),
+            (Some(TokenTree::Punct(first)), Some(TokenTree::Punct(second)), Some(TokenTree::Ident(ident)))
+                if first.as_char() == ':' && second.as_char() == ':' && ident == segment
+        )
+    })
+}
+
+/// Copies the rest of a kept path with `segments` segments as it is, along with the call or macro input after it and an
+/// `.await` on it.
+fn keep(
+    tokens: &mut Peekable<token_stream::IntoIter>,
+    output: &mut Vec<TokenTree>,
+    segments: usize,
+) {
+    // each segment after the first is `::` and an ident
+    output.extend(tokens.by_ref().take((segments - 1) * 3));
+    output.extend(
+        tokens.next_if(|token| matches!(token, TokenTree::Punct(bang) if bang.as_char() == '!')),
+    );
+    output.extend(tokens.next_if(|token| matches!(token, TokenTree::Group(_))));
+    let mut ahead = tokens.clone();
+    if matches!(
+        (ahead.next(), ahead.next()),
+        (Some(TokenTree::Punct(dot)), Some(TokenTree::Ident(ident))) if dot.as_char() == '.' && ident == "await"
+    ) {
+        output.extend(tokens.take(2));
+    }
+}
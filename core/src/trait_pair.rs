@@ -0,0 +1,174 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    fold::Fold,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_quote, Expr, FnArg, Ident, Item, ItemTrait, LitStr, Pat, PatIdent, ReturnType, Token,
+    TraitItem,
+};
+
+use crate::{
+    input::Input,
+    keep,
+    sibling::{NameSpan, Sibling},
+    strict, types, RemoveAsyncAwait,
+};
+
+/// Options for `trait_pair` or `trait_pair(sync_name = "...", async_name = "...", bridge = executor)`.
+pub(crate) struct TraitPair {
+    sync_name: Sibling,
+    async_name: Sibling,
+    /// The executor used to implement the sync trait for every implementor of the async one.
+    bridge: Option<TokenStream>,
+}
+
+impl Parse for TraitPair {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut pair = TraitPair {
+            sync_name: Sibling::template(&LitStr::new("{name}", Span::call_site()))?,
+            async_name: Sibling::template(&LitStr::new("{name}Async", Span::call_site()))?,
+            bridge: None,
+        };
+        if !input.peek(syn::token::Paren) {
+            return Ok(pair);
+        }
+        let content;
+        parenthesized!(content in input);
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "sync_name" => pair.sync_name = Sibling::template(&content.parse()?)?,
+                "async_name" => pair.async_name = Sibling::template(&content.parse()?)?,
+                "bridge" => pair.bridge = Some(content.parse::<Expr>()?.into_token_stream()),
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("unknown trait_pair argument `{}`", key),
+                    ))
+                }
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+        Ok(pair)
+    }
+}
+
+impl TraitPair {
+    /// Spans the names of both traits with `span`, from `name_span = "..."`.
+    pub(crate) fn set_name_span(&mut self, span: NameSpan) {
+        self.sync_name.span = span;
+        self.async_name.span = span;
+    }
+
+    /// Emits the untouched async trait and the blocking one from the same definition, and the bridging impl if
+    /// `bridge` was given.
+    pub(crate) fn expand(&self, folder: &mut RemoveAsyncAwait, input: Input) -> TokenStream {
+        let item = match input {
+            Input::Item(Item::Trait(item)) => item,
+            _ => return quote!(compile_error!("`trait_pair` can only be used on traits.");),
+        };
+        let mut async_trait = item.clone();
+        async_trait.ident = self.async_name.rename_type(&item.ident);
+        let mut sync_trait = item;
+        sync_trait.ident = self.sync_name.rename_type(&sync_trait.ident);
+        if sync_trait.ident == async_trait.ident {
+            return syn::Error::new(
+                sync_trait.ident.span(),
+                "`sync_name` and `async_name` give both traits the same name",
+            )
+            .to_compile_error();
+        }
+        if let Some(vis) = &folder.args.vis {
+            sync_trait.vis = vis.clone();
+        }
+        let sync_trait = folder.fold_item_trait(sync_trait);
+        let bridge = self
+            .bridge
+            .as_ref()
+            .map(|executor| bridge(executor, &async_trait, &sync_trait));
+        let async_trait = keep::strip(async_trait.into_token_stream());
+        let sync_trait = strict::check(
+            folder.args.strict,
+            folder.args.warn_leaks,
+            sync_trait.into_token_stream(),
+        );
+        quote!(#async_trait #sync_trait #bridge)
+    }
+}
+
+/// Implements `sync_trait` for every implementor of `async_trait`, running the async methods with `executor`.
+fn bridge(executor: &TokenStream, async_trait: &ItemTrait, sync_trait: &ItemTrait) -> TokenStream {
+    let implementor = Ident::new("Implementor", Span::mixed_site());
+    let async_ident = &async_trait.ident;
+    let sync_ident = &sync_trait.ident;
+    let (_, ty_generics, _) = async_trait.generics.split_for_impl();
+    let async_path = quote!(#async_ident #ty_generics);
+    let supertraits = &sync_trait.supertraits;
+    let plus = (!supertraits.is_empty()).then(|| quote!(+));
+
+    let mut generics = sync_trait.generics.clone();
+    generics
+        .params
+        .push(parse_quote!(#implementor: #async_path #plus #supertraits));
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, sync_generics, _) = sync_trait.generics.split_for_impl();
+
+    let items = sync_trait.items.iter().filter_map(|item| match item {
+        TraitItem::Fn(method) => {
+            let was_async = async_trait.items.iter().any(|item| match item {
+                TraitItem::Fn(original) if original.sig.ident == method.sig.ident => {
+                    original.sig.asyncness.is_some()
+                        || matches!(&original.sig.output, ReturnType::Type(_, ty) if types::future_output(ty).is_some())
+                }
+                _ => false,
+            });
+            let mut sig = method.sig.clone();
+            let mut args = Vec::new();
+            for (i, arg) in sig.inputs.iter_mut().enumerate() {
+                match arg {
+                    FnArg::Receiver(_) => args.push(quote!(self)),
+                    FnArg::Typed(arg) => {
+                        let ident = Ident::new(&format!("arg{}", i), Span::mixed_site());
+                        *arg.pat = Pat::Ident(PatIdent {
+                            attrs: Vec::new(),
+                            by_ref: None,
+                            mutability: None,
+                            ident: ident.clone(),
+                            subpat: None,
+                        });
+                        args.push(quote!(#ident));
+                    }
+                }
+            }
+            let name = &sig.ident;
+            let call = quote!(<#implementor as #async_path>::#name(#(#args),*));
+            let body = if was_async {
+                quote!(#executor(#call))
+            } else {
+                call
+            };
+            Some(quote!(#sig { #body }))
+        }
+        TraitItem::Type(ty) if ty.generics.params.is_empty() => {
+            let name = &ty.ident;
+            Some(quote!(type #name = <#implementor as #async_path>::#name;))
+        }
+        TraitItem::Const(constant) => {
+            let name = &constant.ident;
+            let ty = &constant.ty;
+            Some(quote!(const #name: #ty = <#implementor as #async_path>::#name;))
+        }
+        _ => None,
+    });
+
+    quote! {
+        impl #impl_generics #sync_ident #sync_generics for #implementor #where_clause {
+            #(#items)*
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use syn::{GenericArgument, Path, PathArguments, Type, TypeParamBound, TypeTuple};
+
+/// If `ty` is a future, returns the type it resolves to. For example, `impl Future<Output = T> + Send`, `Ready<T>`,
+/// `BoxFuture<'a, T>`, `LocalBoxFuture<'a, T>`, remove-async-await-shim's `MaybeFuture<'a, T>` and
+/// `Pin<Box<dyn Future<Output = T> + Send + 'a>>` resolve to `T`.
+pub(crate) fn future_output(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Path(ty) if ty.qself.is_none() => {
+            let last = ty.path.segments.last()?;
+            if ["BoxFuture", "LocalBoxFuture", "MaybeFuture", "Ready"]
+                .iter()
+                .any(|name| last.ident == name)
+            {
+                first_type_arg(&last.arguments)
+            } else if last.ident == "Pin" {
+                // `Pin<Box<dyn Future<Output = T>>>`
+                match first_type_arg(&last.arguments)? {
+                    Type::Path(boxed) if boxed.qself.is_none() => {
+                        let last = boxed.path.segments.last()?;
+                        if last.ident != "Box" {
+                            return None;
+                        }
+                        match first_type_arg(&last.arguments)? {
+                            Type::TraitObject(ty) => future_bounds_output(&ty.bounds),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        }
+        Type::ImplTrait(ty) => future_bounds_output(&ty.bounds),
+        Type::Paren(ty) => future_output(&ty.elem),
+        Type::Group(ty) => future_output(&ty.elem),
+        _ => None,
+    }
+}
+
+/// If one of `bounds` is the `Future` trait, returns its `Output`.
+pub(crate) fn future_bounds_output<'a>(
+    bounds: impl IntoIterator<Item = &'a TypeParamBound>,
+) -> Option<Type> {
+    bounds.into_iter().find_map(|bound| match bound {
+        TypeParamBound::Trait(bound) => future_trait_output(&bound.path),
+        _ => None,
+    })
+}
+
+/// If `path` is the `Future` (or `IntoFuture`) trait, returns its `Output`. A missing `Output` is treated as `()`.
+fn future_trait_output(path: &Path) -> Option<Type> {
+    let last = path.segments.last()?;
+    if last.ident != "Future" && last.ident != "IntoFuture" {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::None => Some(unit()),
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(binding) if binding.ident == "Output" => {
+                Some(binding.ty.clone())
+            }
+            _ => None,
+        }),
+        PathArguments::Parenthesized(_) => None,
+    }
+}
+
+/// Whether `ty` is a named future, such as `BoxFuture<'a, T>`, `LocalBoxFuture<'a, T>` or `Ready<T>`.
+pub(crate) fn is_boxed_future(ty: &Type) -> bool {
+    !matches!(ty, Type::ImplTrait(_)) && future_output(ty).is_some()
+}
+
+/// Returns the first type in `<...>`, skipping lifetimes.
+pub(crate) fn first_type_arg(arguments: &PathArguments) -> Option<Type> {
+    match arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn unit() -> Type {
+    Type::Tuple(TypeTuple {
+        paren_token: Default::default(),
+        elems: Default::default(),
+    })
+}
+
+/// Whether `ty` is `()`.
+pub(crate) fn is_unit(ty: &Type) -> bool {
+    matches!(ty, Type::Tuple(ty) if ty.elems.is_empty())
+}
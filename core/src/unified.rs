@@ -0,0 +1,212 @@
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    fold::Fold,
+    parse::{ParseStream, Parser},
+    ItemFn, LitStr, Meta, Token,
+};
+
+use crate::{
+    args::{self, Args},
+    cfgs, keep, strict, RemoveAsyncAwait,
+};
+
+/// The `remove_async_await` arguments that change which items are emitted or how, which `test` and `main` don't support
+/// since they emit both variants themselves.
+const UNSUPPORTED: &[&str] = &[
+    "before",
+    "after",
+    "sibling",
+    "rename",
+    "name_span",
+    "trait_pair",
+    "deny_noop",
+    "vis",
+    "sync_body",
+];
+
+/// Arguments passed to `#[remove_async_await::test(...)]` and `#[remove_async_await::main(...)]`.
+struct UnifiedArgs {
+    /// The feature that enables the async variant, from `feature = "..."`.
+    feature: LitStr,
+    /// The attribute used for the async variant, from `runtime = ...`.
+    runtime: Meta,
+    /// Everything else, which is passed on to `remove_async_await`.
+    args: Args,
+}
+
+impl UnifiedArgs {
+    fn parse(input: ParseStream, name: &str, mut runtime: Meta) -> syn::Result<Self> {
+        let mut feature = LitStr::new("async", input.span());
+        let mut rest = TokenStream::new();
+
+        while !input.is_empty() {
+            if input.peek(syn::Ident) {
+                let key: syn::Ident = input.fork().parse()?;
+                if UNSUPPORTED.iter().any(|unsupported| key == unsupported) {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("`{key}` can't be used with `remove_async_await::{name}`"),
+                    ));
+                }
+            }
+            if input.peek(syn::Ident) && input.peek2(Token![=]) {
+                let fork = input.fork();
+                let key: syn::Ident = fork.parse()?;
+                if key == "feature" || key == "runtime" {
+                    input.parse::<syn::Ident>()?;
+                    input.parse::<Token![=]>()?;
+                    if key == "feature" {
+                        feature = input.parse()?;
+                    } else {
+                        runtime = input.parse()?;
+                    }
+                    if !input.is_empty() {
+                        input.parse::<Token![,]>()?;
+                    }
+                    continue;
+                }
+            }
+            args::pass_through(input, &mut rest)?;
+        }
+
+        Ok(UnifiedArgs {
+            feature,
+            runtime,
+            args: syn::parse2(rest)?,
+        })
+    }
+}
+
+/// Expands `#[remove_async_await::test]`: the function is emitted twice, once as is with the runtime's attribute (like
+/// `#[tokio::test]`) when the feature is enabled, and once transformed with `#[test]` when it isn't.
+pub(crate) fn test(args: TokenStream, input: TokenStream) -> TokenStream {
+    unified(
+        args,
+        input,
+        "test",
+        syn::parse_quote!(tokio::test),
+        quote!(#[test]),
+    )
+}
+
+/// Expands `#[remove_async_await::main]`, which is the same as `test` except that the blocking `main` doesn't need an
+/// attribute.
+pub(crate) fn main(args: TokenStream, input: TokenStream) -> TokenStream {
+    unified(
+        args,
+        input,
+        "main",
+        syn::parse_quote!(tokio::main),
+        TokenStream::new(),
+    )
+}
+
+fn unified(
+    args: TokenStream,
+    input: TokenStream,
+    name: &str,
+    runtime: Meta,
+    blocking_attr: TokenStream,
+) -> TokenStream {
+    let parser = |input: ParseStream| UnifiedArgs::parse(input, name, runtime);
+    let args = match Parser::parse2(parser, args) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error(),
+    };
+    let item = match syn::parse2::<ItemFn>(input) {
+        Ok(item) => item,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let UnifiedArgs {
+        feature,
+        runtime,
+        mut args,
+    } = args;
+    // `when(...)` and `unless_feature` take precedence over `feature`
+    let predicate = args.cfg.take().unwrap_or(quote!(feature = #feature));
+    let doc_cfg = args.doc_cfg;
+    let strict = args.strict;
+    let warn_leaks = args.warn_leaks;
+    let blocking = RemoveAsyncAwait::from_args(args).fold_item_fn(item.clone());
+    let blocking = strict::check(strict, warn_leaks, blocking.into_token_stream());
+    cfg_variants(
+        &predicate,
+        doc_cfg,
+        quote!(#[#runtime] #item),
+        quote!(#blocking_attr #blocking),
+    )
+}
+
+/// Emits the async item when `predicate` holds and the blocking one when it doesn't, resolving the cfgs in them that
+/// test `predicate`. With `doc_cfg`, docs.rs shows the predicate on each of them.
+pub(crate) fn cfg_variants(
+    predicate: &TokenStream,
+    doc_cfg: bool,
+    async_item: TokenStream,
+    blocking_item: TokenStream,
+) -> TokenStream {
+    let async_item = cfgs::resolve(keep::strip(async_item), predicate, true);
+    let blocking_item = cfgs::resolve(blocking_item, predicate, false);
+    let (async_doc, blocking_doc) = if doc_cfg {
+        (
+            quote!(#[cfg_attr(docsrs, doc(cfg(#predicate)))]),
+            quote!(#[cfg_attr(docsrs, doc(cfg(not(#predicate))))]),
+        )
+    } else {
+        Default::default()
+    };
+    // a copy that's never compiled, like the blocking copy of an item gated on `predicate`, is left out with its gate
+    let async_item = (!async_item.is_empty()).then(|| {
+        quote! {
+            #[cfg(#predicate)]
+            #async_doc
+            #async_item
+        }
+    });
+    let blocking_item = (!blocking_item.is_empty()).then(|| {
+        quote! {
+            #[cfg(not(#predicate))]
+            #blocking_doc
+            #blocking_item
+        }
+    });
+    quote!(#async_item #blocking_item)
+}
+
+/// Turns the arguments of `#[remove_async_await::dual(...)]` into arguments for `remove_async_await`: `feature = "..."`
+/// becomes `unless_feature = "..."`, and `unless_feature = "async"` is used if no feature or `when(...)` was given.
+pub(crate) fn dual_args(args: TokenStream) -> TokenStream {
+    let mut gated = false;
+    let mut tokens = args.into_iter().peekable();
+    let mut output = TokenStream::new();
+    // only check for keys at the start of an argument, so `feature` in `when(...)` and paths is left alone
+    let mut at_key = true;
+    while let Some(token) = tokens.next() {
+        let token = match token {
+            TokenTree::Ident(key) if at_key => {
+                let is_assign = matches!(tokens.peek(), Some(TokenTree::Punct(punct)) if punct.as_char() == '=');
+                if key == "when" || key == "unless_feature" {
+                    gated = true;
+                }
+                if key == "feature" && is_assign {
+                    gated = true;
+                    TokenTree::Ident(Ident::new("unless_feature", key.span()))
+                } else {
+                    TokenTree::Ident(key)
+                }
+            }
+            token => token,
+        };
+        at_key = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ',');
+        output.extend([token]);
+    }
+    if !gated {
+        if !output.is_empty() && !at_key {
+            output.extend(quote!(,));
+        }
+        output.extend(quote!(unless_feature = "async"));
+    }
+    output
+}
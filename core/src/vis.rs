@@ -0,0 +1,37 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{ImplItem, Item, Visibility};
+
+/// Gives the blocking item the visibility from `vis = "..."`. Inherent impls give it to each of their items, and
+/// anything that can't have a visibility, like a trait impl, is left alone.
+pub(crate) fn apply(vis: &Visibility, tokens: TokenStream) -> TokenStream {
+    let mut item = match syn::parse2::<Item>(tokens.clone()) {
+        Ok(item) => item,
+        Err(_) => return tokens,
+    };
+    let item_vis = match &mut item {
+        Item::Const(item) => &mut item.vis,
+        Item::Enum(item) => &mut item.vis,
+        Item::Fn(item) => &mut item.vis,
+        Item::Mod(item) => &mut item.vis,
+        Item::Static(item) => &mut item.vis,
+        Item::Struct(item) => &mut item.vis,
+        Item::Trait(item) => &mut item.vis,
+        Item::Type(item) => &mut item.vis,
+        Item::Union(item) => &mut item.vis,
+        Item::Impl(item) if item.trait_.is_none() => {
+            for item in &mut item.items {
+                match item {
+                    ImplItem::Const(item) => item.vis = vis.clone(),
+                    ImplItem::Fn(item) => item.vis = vis.clone(),
+                    ImplItem::Type(item) => item.vis = vis.clone(),
+                    _ => {}
+                }
+            }
+            return item.into_token_stream();
+        }
+        _ => return tokens,
+    };
+    *item_vis = vis.clone();
+    item.into_token_stream()
+}
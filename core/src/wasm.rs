@@ -0,0 +1,68 @@
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    parse_quote_spanned,
+    spanned::Spanned,
+    Attribute, Expr, ExprCall, Ident, Path, Token,
+};
+
+/// Options for crates that export async functions with wasm-bindgen, from `wasm_bindgen` or
+/// `wasm_bindgen(js_future = path)`.
+#[derive(Default)]
+pub(crate) struct WasmBindgen {
+    /// What `JsFuture::from(promise)` is rewritten to, like `native::resolve(promise)`.
+    js_future: Option<Path>,
+}
+
+impl Parse for WasmBindgen {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut wasm_bindgen = WasmBindgen::default();
+        if !input.peek(syn::token::Paren) {
+            return Ok(wasm_bindgen);
+        }
+        let content;
+        parenthesized!(content in input);
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            if key != "js_future" {
+                return Err(syn::Error::new(
+                    key.span(),
+                    format!("unknown wasm_bindgen argument `{}`", key),
+                ));
+            }
+            content.parse::<Token![=]>()?;
+            wasm_bindgen.js_future = Some(content.call(Path::parse_mod_style)?);
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+        Ok(wasm_bindgen)
+    }
+}
+
+impl WasmBindgen {
+    /// Removes `#[wasm_bindgen]` attributes (including `#[wasm_bindgen(start)]`), since the blocking item is for native
+    /// targets and wasm-bindgen can't export it the same way.
+    pub(crate) fn strip_attrs(&self, attrs: &mut Vec<Attribute>) {
+        attrs.retain(|attr| !is_wasm_bindgen(attr));
+    }
+
+    /// If `e` is `JsFuture::from(promise)` and `js_future` was given, returns the call to `js_future` instead. The
+    /// argument should already be folded.
+    pub(crate) fn js_future_call(&self, e: &ExprCall) -> Option<Expr> {
+        let js_future = self.js_future.as_ref()?;
+        if !crate::calls(e, &["JsFuture", "from"]) {
+            return None;
+        }
+        let promise = &e.args[0];
+        Some(parse_quote_spanned!(e.func.span()=> #js_future(#promise)))
+    }
+}
+
+fn is_wasm_bindgen(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "wasm_bindgen")
+}
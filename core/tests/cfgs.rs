@@ -0,0 +1,67 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+#[test]
+fn cfgs() {
+    let output = remove_async_await_core::expand_dual(
+        quote!(),
+        quote! {
+            #[cfg_attr(feature = "async", doc = "Async.")]
+            #[cfg_attr(not(feature = "async"), doc = "Blocking.")]
+            pub async fn get() -> u32 {
+                #[cfg(feature = "async")]
+                let value = async_value().await;
+                #[cfg(not(feature = "async"))]
+                let value = blocking_value();
+                #[cfg(all(feature = "async", unix))]
+                println!("async on unix");
+                #[cfg(any(feature = "async", unix))]
+                println!("async or unix");
+                value
+            }
+        },
+    );
+    let expected = quote! {
+        #[cfg(feature = "async")]
+        #[doc = "Async."]
+        pub async fn get() -> u32 {
+            let value = async_value().await;
+            #[cfg(all(feature = "async", unix))]
+            println!("async on unix");
+            println!("async or unix");
+            value
+        }
+
+        #[cfg(not(feature = "async"))]
+        #[doc = "Blocking."]
+        pub fn get() -> u32 {
+            let value = blocking_value();
+            #[cfg(any(feature = "async", unix))]
+            println!("async or unix");
+            value
+        }
+    };
+    assert_eq!(output.to_string(), expected.to_string());
+}
+
+#[test]
+fn cfgs_item() {
+    // the blocking copy of an item that only exists with the feature is never compiled
+    let output = remove_async_await_core::expand_dual(
+        quote!(),
+        quote! {
+            #[cfg(feature = "async")]
+            pub async fn get() -> u32 {
+                1
+            }
+        },
+    );
+    let expected = quote! {
+        #[cfg(feature = "async")]
+        pub async fn get() -> u32 {
+            1
+        }
+    };
+    assert_eq!(output.to_string(), expected.to_string());
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+#[test]
+fn doc_cfg() {
+    let output = remove_async_await_core::expand_dual(
+        quote!(doc_cfg),
+        quote! {
+            pub async fn get() -> u32 {
+                1
+            }
+        },
+    );
+    let expected = quote! {
+        #[cfg(feature = "async")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+        pub async fn get() -> u32 {
+            1
+        }
+
+        #[cfg(not(feature = "async"))]
+        #[cfg_attr(docsrs, doc(cfg(not(feature = "async"))))]
+        pub fn get() -> u32 {
+            1
+        }
+    };
+    assert_eq!(output.to_string(), expected.to_string());
+}
@@ -0,0 +1,305 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+#[test]
+fn parse_error() {
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            async fn get() -> u32 {
+                let = 1;
+            }
+        },
+    );
+    let output = output.to_string();
+    assert!(output.contains("the input looks like a function, but it couldn't be parsed as one"));
+    assert!(output.contains("- as a trait method: "));
+    // followed by the error from syn, pointing at `=`
+    assert!(output.contains("compile_error ! { \"expected one of"));
+}
+
+#[test]
+fn unsupported() {
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            enum Value {}
+        },
+    );
+    let output = output.to_string();
+    assert!(output.contains("remove_async_await currently only supports functions"));
+    assert!(output.contains("but the input is an enum"));
+    assert!(output.contains("- as a struct: expected `struct`"));
+}
+
+#[test]
+fn strict() {
+    let output = remove_async_await_core::expand(
+        quote!(strict),
+        quote! {
+            async fn get() -> u32 {
+                let value = get_value().await;
+                #[remove_async_await::keep]
+                async fn callback() -> u32 {
+                    get_value().await
+                }
+                log!("{}", get_value().await);
+                value
+            }
+        },
+    );
+    let output = output.to_string();
+    let error = "`.await` is left after the transform";
+    // only the await inside the unknown macro
+    assert_eq!(output.matches(error).count(), 1);
+}
+
+#[test]
+fn strict_constructs() {
+    let output = remove_async_await_core::expand(
+        quote!(strict),
+        quote! {
+            async fn run(stream: impl futures::TryStream<Ok = u32>) {
+                tokio::spawn(work());
+                std::thread::spawn(|| {});
+                tokio::select! {
+                    _ = work() => {}
+                }
+            }
+        },
+    );
+    let output = output.to_string();
+    assert!(output.contains("`TryStream` is left after the transform"));
+    assert!(output.contains("tasks are only inlined in functions returning a `JoinHandle`"));
+    assert!(output.contains("`select!` has no blocking equivalent"));
+    assert_eq!(output.matches("tasks are only inlined").count(), 1);
+}
+
+#[test]
+fn strict_js_future() {
+    let input = quote! {
+        async fn fetch(promise: Promise) -> Result<JsValue, JsValue> {
+            wasm_bindgen_futures::JsFuture::from(promise).await
+        }
+    };
+    let output = remove_async_await_core::expand(quote!(strict, wasm_bindgen), input.clone());
+    assert!(output
+        .to_string()
+        .contains("`JsFuture` only resolves promises on wasm"));
+    // the bridge replaces it
+    let output = remove_async_await_core::expand(
+        quote!(strict, wasm_bindgen(js_future = native::resolve)),
+        input,
+    );
+    assert!(!output.to_string().contains("compile_error"));
+}
+
+#[test]
+fn deny_noop() {
+    let noop = remove_async_await_core::expand(
+        quote!(deny_noop),
+        quote! {
+            fn get() -> u32 {
+                1
+            }
+        },
+    );
+    assert!(noop
+        .to_string()
+        .contains("this item has nothing async to remove"));
+    let output = remove_async_await_core::expand(
+        quote!(deny_noop),
+        quote! {
+            async fn get() -> u32 {
+                1
+            }
+        },
+    );
+    assert_eq!(
+        output.to_string(),
+        quote!(
+            fn get() -> u32 {
+                1
+            }
+        )
+        .to_string()
+    );
+}
+
+#[test]
+fn unknown_argument() {
+    let args = |args| {
+        remove_async_await_core::RemoveAsyncAwait::with_args(args)
+            .err()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(
+        args(quote!(renme = "{name}_sync")),
+        "unknown argument `renme`, did you mean `rename`?"
+    );
+    assert_eq!(
+        args(quote!(strip_bound)),
+        "unknown argument `strip_bound`, did you mean `strip_bounds`?"
+    );
+    assert_eq!(args(quote!(tokio)), "unknown argument `tokio`");
+    assert_eq!(args(quote!(map(a))), "expected `=`");
+    assert_eq!(
+        args(quote!(race = first, race = second)),
+        "duplicate argument"
+    );
+    assert_eq!(
+        args(quote!(strict, hoist_awaits, strict)),
+        "duplicate argument"
+    );
+    assert!(
+        remove_async_await_core::RemoveAsyncAwait::with_args(quote!(map(a = b), map(c = d)))
+            .is_ok()
+    );
+}
+
+#[test]
+fn unified_unsupported() {
+    let input = quote! {
+        async fn get() {}
+    };
+    let output = remove_async_await_core::expand_test(quote!(vis = "pub"), input.clone());
+    assert_eq!(
+        output.to_string(),
+        quote!(::core::compile_error! { "`vis` can't be used with `remove_async_await::test`" })
+            .to_string()
+    );
+    let output = remove_async_await_core::expand_main(quote!(strict, after(inline)), input.clone());
+    assert!(output
+        .to_string()
+        .contains("`after` can't be used with `remove_async_await::main`"));
+    // keys inside other arguments aren't checked
+    let output = remove_async_await_core::expand_test(quote!(when(feature = "vis")), input);
+    assert!(!output.to_string().contains("compile_error"));
+}
+
+#[test]
+fn async_leaks() {
+    let output = remove_async_await_core::expand(
+        quote!(warn_leaks),
+        quote! {
+            async fn run(rx: tokio::sync::mpsc::Receiver<u32>) {
+                tokio::spawn(work());
+                tokio::spawn(work());
+                futures::executor::block_on(async { tokio::task::yield_now().await });
+                fn nested() -> impl futures::Stream<Item = u32> {
+                    futures::stream::pending()
+                }
+            }
+        },
+    );
+    let output = output.to_string();
+    // one warning for each function, listing each path once
+    assert_eq!(output.matches("# [deprecated").count(), 2);
+    assert!(output.contains(
+        "`run` still uses async code after the transform: `tokio::sync::mpsc::Receiver`, `tokio::spawn`, `tokio::task::yield_now`;"
+    ));
+    assert!(output.contains(
+        "`nested` still uses async code after the transform: `futures::stream::pending`;"
+    ));
+}
+
+#[test]
+fn no_async_leaks() {
+    let input = quote! {
+        async fn get(value: futures::future::Ready<u32>) -> u32 {
+            value.await
+        }
+    };
+    let output = remove_async_await_core::expand(
+        quote!(types(futures::future::Ready<u32> = u32), warn_leaks),
+        input,
+    );
+    assert!(!output.to_string().contains("deprecated"));
+    // the warning is opt-in, since it breaks crates that deny warnings
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            async fn run() {
+                tokio::spawn(work());
+            }
+        },
+    );
+    assert!(!output.to_string().contains("deprecated"));
+}
+
+#[test]
+fn const_awaits() {
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            async fn run() -> [u8; get().await] {
+                enum Kind {
+                    Fast = get().await,
+                }
+                let values = [0; get().await];
+                let value = const { get().await };
+                // futures made at compile time can await
+                let future = const { async { get().await } };
+                future.await
+            }
+        },
+    );
+    let output = output.to_string();
+    assert_eq!(
+        output
+            .matches("`.await` can't be used here, since it's evaluated at compile time")
+            .count(),
+        4
+    );
+}
+
+#[test]
+fn name_collisions() {
+    let output = remove_async_await_core::expand(
+        quote!(rename = "{name}"),
+        quote! {
+            async fn get() -> u32 {
+                1
+            }
+        },
+    );
+    assert!(output
+        .to_string()
+        .contains("the template gives the blocking copy the same name as the async item"));
+
+    let output = remove_async_await_core::expand(
+        quote!(trait_pair(async_name = "{name}")),
+        quote! {
+            trait Get {
+                async fn get(&self) -> u32;
+            }
+        },
+    );
+    assert!(output
+        .to_string()
+        .contains("`sync_name` and `async_name` give both traits the same name"));
+
+    let output = remove_async_await_core::expand_mirror(
+        quote!(name = "client"),
+        quote! {
+            mod client {}
+        },
+    );
+    assert!(output
+        .to_string()
+        .contains("the blocking module needs another name than the async one"));
+
+    let output = remove_async_await_core::expand(
+        quote!(sibling, name_span = "def_site"),
+        quote! {
+            async fn get() -> u32 {
+                1
+            }
+        },
+    );
+    assert!(output
+        .to_string()
+        .contains(r#"expected `\"source\"` or `\"call_site\"`"#));
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+#[test]
+fn untouched() {
+    let input = quote! {
+        pub fn get(values: &[u32]) -> u32 {
+            values.iter().sum()
+        }
+    };
+    let output = remove_async_await_core::expand(quote!(), input.clone());
+    assert_eq!(output.to_string(), input.to_string());
+}
+
+#[test]
+fn futures_without_async() {
+    // there's no `async` or `.await`, but the future still has to be rewritten
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            pub fn get() -> impl std::future::Future<Output = u32> {
+                std::future::ready(1)
+            }
+        },
+    );
+    assert_eq!(
+        output.to_string(),
+        quote!(
+            pub fn get() -> u32 {
+                1
+            }
+        )
+        .to_string()
+    );
+}
+
+#[test]
+fn unsupported() {
+    // the fast path doesn't skip the error for items that aren't supported
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote!(
+            enum Value {}
+        ),
+    );
+    assert!(output.to_string().contains("compile_error"));
+}
+
+#[test]
+fn ready() {
+    // `Ready` is a future too, even without `async` or `Future` in the item
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            fn get() -> Ready<u32> {
+                value()
+            }
+        },
+    );
+    assert_eq!(
+        output.to_string(),
+        quote!(
+            fn get() -> u32 {
+                value()
+            }
+        )
+        .to_string()
+    );
+}
+
+#[test]
+fn unparsed() {
+    // the tokens are checked before the item is parsed, so an untouched item with a syntax error is left for rustc
+    let input = quote! {
+        fn get() -> u32 {
+            let = 1;
+        }
+    };
+    let output = remove_async_await_core::expand(quote!(), input.clone());
+    assert_eq!(output.to_string(), input.to_string());
+}
+
+#[test]
+fn foreign_items() {
+    // `extern` is only skipped in front of a function
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote!(
+            extern "C" {
+                fn get() -> u32;
+            }
+        ),
+    );
+    assert!(output.to_string().contains("compile_error"));
+}
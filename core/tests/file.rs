@@ -0,0 +1,62 @@
+#![cfg(feature = "full")]
+
+use quote::{quote, ToTokens};
+use syn::fold::Fold;
+
+#[test]
+fn file() {
+    let file: syn::File = syn::parse_quote! {
+        async fn get() -> u32 {
+            1
+        }
+
+        pub async fn get_twice() -> u32 {
+            get().await * 2
+        }
+    };
+    let expected = quote! {
+        fn get() -> u32 {
+            1
+        }
+
+        pub fn get_twice() -> u32 {
+            get() * 2
+        }
+    };
+    let file = remove_async_await_core::remove_async_await(file);
+    assert_eq!(file.into_token_stream().to_string(), expected.to_string());
+}
+
+#[test]
+fn folds() {
+    let mut folder = remove_async_await_core::RemoveAsyncAwait::with_args(quote!(map(
+        tokio::time::sleep = std::thread::sleep
+    )))
+    .unwrap();
+    let expr = folder.fold_expr(syn::parse_quote!(tokio::time::sleep(duration).await));
+    assert_eq!(
+        expr.into_token_stream().to_string(),
+        quote!(std::thread::sleep(duration)).to_string()
+    );
+    let sig = folder.fold_signature(syn::parse_quote!(
+        fn get() -> impl std::future::Future<Output = u32>
+    ));
+    assert_eq!(
+        sig.into_token_stream().to_string(),
+        quote!(fn get() -> u32).to_string()
+    );
+    let item = folder.fold_item_fn(syn::parse_quote!(
+        async fn get(value: impl std::future::Future<Output = u32>) -> u32 {
+            value.await
+        }
+    ));
+    assert_eq!(
+        item.into_token_stream().to_string(),
+        quote!(
+            fn get(value: u32) -> u32 {
+                value
+            }
+        )
+        .to_string()
+    );
+}
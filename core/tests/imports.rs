@@ -0,0 +1,27 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+#[test]
+fn imports() {
+    let output = remove_async_await_core::expand(
+        quote!(profile(smol), map(tokio::time::sleep = std::thread::sleep)),
+        quote! {
+            async fn get() {
+                use tokio::time::{sleep, Instant};
+                use smol::{fs::{self, File}, io::AsyncReadExt, net::*};
+                use smol::Timer::after as wait;
+                use std::time::Duration;
+            }
+        },
+    );
+    let expected = quote! {
+        fn get() {
+            use {std::thread::sleep, tokio::time::Instant};
+            use {std::fs::{self}, std::fs::File, std::io::Read as AsyncReadExt, std::net::*};
+            use std::thread::sleep as wait;
+            use std::time::Duration;
+        }
+    };
+    assert_eq!(output.to_string(), expected.to_string());
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+#[test]
+fn inner_attributes() {
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            async fn get() -> u32 {
+                #![allow(unused_mut)]
+                let mut value = 1;
+                value
+            }
+        },
+    );
+    let expected = quote! {
+        fn get() -> u32 {
+            #![allow(unused_mut)]
+            let mut value = 1;
+            value
+        }
+    };
+    assert_eq!(output.to_string(), expected.to_string());
+}
+
+#[test]
+fn trait_method() {
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            async fn get(&self) -> u32;
+        },
+    );
+    assert_eq!(
+        output.to_string(),
+        quote!(
+            fn get(&self) -> u32;
+        )
+        .to_string()
+    );
+}
+
+#[test]
+fn visible_method_without_body() {
+    let output = remove_async_await_core::expand(
+        quote!(),
+        quote! {
+            pub async fn get(&self) -> u32;
+        },
+    );
+    let output = output.to_string();
+    assert!(output.contains("remove_async_await currently only supports functions"));
+    assert!(output.contains("- as a trait method: "));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "full")]
+
+use std::fs;
+
+#[test]
+fn mirror() {
+    let dir =
+        std::env::temp_dir().join(format!("remove-async-await-mirror-{}", std::process::id()));
+    let source = dir.join("async_impl");
+    fs::create_dir_all(source.join("net")).unwrap();
+    fs::write(
+        source.join("mod.rs"),
+        "//! Async implementation.\nmod net;\n#[path = \"../other.rs\"]\nmod other;\npub async fn get() -> u32 { net::fetch().await }",
+    )
+    .unwrap();
+    fs::write(
+        source.join("net.rs"),
+        "mod port;\npub async fn fetch() -> u32 { port::get().await }",
+    )
+    .unwrap();
+    fs::write(
+        source.join("net").join("port.rs"),
+        "pub async fn get() -> u32 { 80 }",
+    )
+    .unwrap();
+    fs::write(dir.join("other.rs"), "pub fn other() {}").unwrap();
+
+    let output = dir.join("blocking.rs");
+    remove_async_await_core::build::mirror_to(&source, &output).unwrap();
+    let expected = "mod net { mod port { pub fn get () -> u32 { 80 } } pub fn fetch () -> u32 { port :: get () } } mod other { pub fn other () { } } pub fn get () -> u32 { net :: fetch () }";
+    assert_eq!(fs::read_to_string(&output).unwrap(), expected);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn operands() {
+    // printing the file keeps the precedence of values that replace awaited expressions
+    let dir = std::env::temp_dir().join(format!(
+        "remove-async-await-mirror-operands-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("async_impl.rs");
+    fs::write(
+        &source,
+        "pub async fn get() -> u32 { let x = async { 1 + 2 }.await * 3; x }",
+    )
+    .unwrap();
+
+    let output = dir.join("blocking.rs");
+    remove_async_await_core::build::mirror_to(&source, &output).unwrap();
+    let expected = "pub fn get () -> u32 { let x = (1 + 2) * 3 ; x }";
+    assert_eq!(fs::read_to_string(&output).unwrap(), expected);
+    fs::remove_dir_all(dir).unwrap();
+}
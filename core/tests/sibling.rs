@@ -0,0 +1,40 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+fn expand(input: proc_macro2::TokenStream) -> String {
+    remove_async_await_core::expand(quote!(sibling), input).to_string()
+}
+
+#[test]
+fn imported_calls() {
+    let output = expand(quote! {
+        async fn wait(delay: u64) -> u32 {
+            sleep(delay).await;
+            self::get().await + wait(delay - 1).await
+        }
+    });
+    let blocking = output.split("fn wait_blocking").nth(1).unwrap();
+    // `sleep` is imported, so it doesn't have a blocking copy
+    assert!(blocking.contains("sleep (delay) ;"));
+    assert!(blocking.contains("self :: get_blocking ()"));
+    assert!(blocking.contains("wait_blocking (delay - 1)"));
+}
+
+#[test]
+fn foreign_methods() {
+    let output = expand(quote! {
+        impl Client {
+            async fn get(&self) -> u32 {
+                self.send().await + self.flush().await
+            }
+
+            async fn send(&self) -> u32 {
+                1
+            }
+        }
+    });
+    let blocking = output.split("fn get_blocking").nth(1).unwrap();
+    // `flush` comes from another impl or a trait, which isn't copied here
+    assert!(blocking.contains("self . send_blocking () + self . flush ()"));
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "full")]
+
+use proc_macro2::{LineColumn, TokenStream, TokenTree};
+
+/// Parses `source` so its tokens carry line and column information, like the tokens a user wrote.
+fn parse(source: &str) -> TokenStream {
+    source.parse().unwrap()
+}
+
+/// Finds where the first `ident` in `tokens` starts.
+fn find(tokens: TokenStream, ident: &str) -> Option<LineColumn> {
+    tokens.into_iter().find_map(|tree| match tree {
+        TokenTree::Ident(found) if found == ident => Some(found.span().start()),
+        TokenTree::Group(group) => find(group.stream(), ident),
+        _ => None,
+    })
+}
+
+#[test]
+fn mapped_path() {
+    let input = parse("async fn wait() {\n    tokio::time::sleep(DURATION).await;\n}");
+    let sleep = find(input.clone(), "sleep").unwrap();
+    let output = remove_async_await_core::expand(
+        "map(tokio::time::sleep = std::thread::sleep)"
+            .parse()
+            .unwrap(),
+        input,
+    );
+    // both segments of the replacement point at the path that was replaced, not at the `map(...)` argument
+    assert_eq!(find(output.clone(), "thread"), Some(sleep));
+    assert_eq!(find(output, "sleep"), Some(sleep));
+}
+
+#[test]
+fn io_bound() {
+    let input = parse("async fn read<R: futures::io::AsyncRead + Unpin>(reader: R) {}");
+    let bound = find(input.clone(), "AsyncRead").unwrap();
+    let output = remove_async_await_core::expand(TokenStream::new(), input);
+    // the whole `::std::io::Read` path points at the bound, so an error about it isn't reported on the attribute
+    assert_eq!(find(output, "std"), Some(bound));
+}
+
+#[test]
+fn boxed_stream() {
+    let input = parse(
+        "fn numbers() -> Pin<Box<dyn futures::Stream<Item = u32>>> {\n    Box::pin(futures::stream::iter(vec![1]))\n}",
+    );
+    let pin = find(input.clone(), "Pin").unwrap();
+    let output = remove_async_await_core::expand(TokenStream::new(), input);
+    // `Pin<Box<dyn Stream>>` becomes `Box<dyn Iterator>`, still pointing at the return type the user wrote
+    assert_eq!(find(output.clone(), "Box"), Some(pin));
+}
@@ -0,0 +1,171 @@
+#![cfg(feature = "full")]
+
+use quote::quote;
+
+fn expand(input: proc_macro2::TokenStream) -> String {
+    remove_async_await_core::expand(quote!(), input).to_string()
+}
+
+#[test]
+fn let_chains() {
+    let output = expand(quote! {
+        async fn get() -> u32 {
+            if let Some(a) = first().await && let Some(b) = second(a).await {
+                return a + b;
+            }
+            0
+        }
+    });
+    let expected = quote! {
+        fn get() -> u32 {
+            if let Some(a) = first() && let Some(b) = second(a) {
+                return a + b;
+            }
+            0
+        }
+    };
+    assert_eq!(output, expected.to_string());
+}
+
+#[test]
+fn c_string_literals() {
+    let output = expand(quote! {
+        async fn open() -> i32 {
+            open_file(c"file.txt").await
+        }
+    });
+    assert_eq!(
+        output,
+        quote!(
+            fn open() -> i32 {
+                open_file(c"file.txt")
+            }
+        )
+        .to_string()
+    );
+}
+
+#[test]
+fn return_position_impl_trait() {
+    let output = expand(quote! {
+        fn get(&self) -> impl std::future::Future<Output = u32> + Send;
+    });
+    assert_eq!(
+        output,
+        quote!(
+            fn get(&self) -> u32;
+        )
+        .to_string()
+    );
+}
+
+#[test]
+fn async_block_returns() {
+    let output = expand(quote! {
+        async fn get() -> Option<u32> {
+            let value = async move {
+                let value = first()?;
+                Some(value)
+            }
+            .await;
+            value.map(|value| value + 1)
+        }
+    });
+    let expected = quote! {
+        fn get() -> Option<u32> {
+            let value = {
+                fn __remove_async_await_call<T>(f: impl ::core::ops::FnOnce() -> T) -> T {
+                    f()
+                }
+                __remove_async_await_call(move | | {
+                    let value = first()?;
+                    Some(value)
+                })
+            };
+            value.map(|value| value + 1)
+        }
+    };
+    assert_eq!(output, expected.to_string());
+
+    // a block returned from a function returning a future returns from the function either way
+    let output = expand(quote! {
+        fn get() -> impl Future<Output = Option<u32>> {
+            async move {
+                let value = first()?;
+                Some(value)
+            }
+        }
+    });
+    let expected = quote! {
+        fn get() -> Option<u32> {
+            {
+                let value = first()?;
+                Some(value)
+            }
+        }
+    };
+    assert_eq!(output, expected.to_string());
+}
+
+#[test]
+fn operands() {
+    // the values that replace awaited expressions keep their precedence once the tokens are printed
+    let output = remove_async_await_core::expand(
+        quote!(profile(smol)),
+        quote! {
+            async fn get(a: u32, b: u32) -> u32 {
+                let x = async { a + b }.await * 3;
+                let y = smol::unblock(move || a - b).await * 2;
+                let z = future::or(async { a + 1 }, async { b }).await * 4;
+                x + y + z
+            }
+        },
+    )
+    .to_string();
+    let expected = quote! {
+        fn get(a: u32, b: u32) -> u32 {
+            let x = (a + b) * 3;
+            let y = (a - b) * 2;
+            let z = (a + 1) * 4;
+            x + y + z
+        }
+    };
+    assert_eq!(output, expected.to_string());
+}
+
+#[test]
+fn imported_join_handle() {
+    // an imported `JoinHandle` counts as a task's when a task is spawned, and as a thread's otherwise
+    let task = expand(quote! {
+        fn task(v: u32) -> JoinHandle<u32> {
+            tokio::spawn(async move { v })
+        }
+    });
+    assert_eq!(
+        task,
+        quote!(
+            fn task(v: u32) -> u32 {
+                v
+            }
+        )
+        .to_string()
+    );
+    let thread = quote! {
+        fn thread(f: fn() -> u32) -> JoinHandle<u32> {
+            std::thread::spawn(f)
+        }
+    };
+    assert_eq!(expand(thread.clone()), thread.to_string());
+}
+
+#[test]
+fn stream_into_future() {
+    // `StreamExt::into_future` resolves to the next item and the rest of the stream, so it isn't removed
+    let output = expand(quote! {
+        async fn split() -> u32 {
+            let (first, _) = futures::stream::iter(vec![1]).into_future().await;
+            first.unwrap()
+        }
+    });
+    assert!(output.contains(". into_future ()"));
+}
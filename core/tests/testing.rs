@@ -0,0 +1,35 @@
+#![cfg(feature = "testing")]
+
+use quote::quote;
+
+#[test]
+fn expand() {
+    let output = remove_async_await_core::testing::expand(
+        quote!(map(tokio::time::sleep = std::thread::sleep)),
+        quote! {
+            pub async fn wait() {
+                tokio::time::sleep(DURATION).await;
+            }
+        },
+    );
+    assert_eq!(
+        output,
+        "pub fn wait() {\n    std::thread::sleep(DURATION);\n}\n"
+    );
+}
+
+#[test]
+fn format() {
+    let output = remove_async_await_core::expand_dual(
+        quote!(),
+        quote! {
+            pub async fn get() -> u32 {
+                1
+            }
+        },
+    );
+    assert_eq!(
+        remove_async_await_core::testing::format(&output),
+        "#[cfg(feature = \"async\")]\npub async fn get() -> u32 {\n    1\n}\n#[cfg(not(feature = \"async\"))]\npub fn get() -> u32 {\n    1\n}\n"
+    );
+}
@@ -0,0 +1,24 @@
+use quote::quote;
+
+#[test]
+fn remove() {
+    let output = remove_async_await_core::tokens::remove(
+        quote! {
+            async fn get() -> u32 {
+                let value = async move { runtime::spawn(fetch()).await }.await;
+                value + fetch().await
+            }
+        },
+        &[vec!["runtime".into(), "spawn".into()]],
+    );
+    assert_eq!(
+        output.to_string(),
+        quote! {
+            fn get() -> u32 {
+                let value = { runtime::spawn(fetch()).await };
+                value + fetch()
+            }
+        }
+        .to_string()
+    );
+}
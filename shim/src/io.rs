@@ -0,0 +1,64 @@
+use std::{
+    future::poll_fn,
+    io::{self, Read, Seek, SeekFrom, Write},
+    pin::Pin,
+};
+
+use futures_io::{AsyncRead, AsyncSeek, AsyncWrite};
+
+use crate::block_on::block_on;
+
+/// Implements `std::io::Read`, `Write` and `Seek` over a value implementing futures' `AsyncRead`, `AsyncWrite` and
+/// `AsyncSeek`, blocking the thread on every call, so async IO objects can be passed to the blocking build. tokio's IO
+/// objects can be turned into futures' with tokio-util's `compat()`.
+///
+/// ```rs
+/// let reader = BlockingIo::new(futures::io::Cursor::new(b"hello"));
+/// read_all(reader)?; // `fn read_all<R: std::io::Read>(reader: R)` in the blocking build
+/// ```
+#[derive(Debug, Default)]
+pub struct BlockingIo<T>(T);
+
+impl<T> BlockingIo<T> {
+    /// Wraps the async IO object.
+    pub fn new(io: T) -> Self {
+        BlockingIo(io)
+    }
+
+    /// Returns the async IO object.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Returns a reference to the async IO object.
+    pub fn get_ref(&self) -> &T {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the async IO object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsyncRead + Unpin> Read for BlockingIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        block_on(poll_fn(|cx| Pin::new(&mut self.0).poll_read(cx, buf)))
+    }
+}
+
+impl<T: AsyncWrite + Unpin> Write for BlockingIo<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        block_on(poll_fn(|cx| Pin::new(&mut self.0).poll_write(cx, buf)))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        block_on(poll_fn(|cx| Pin::new(&mut self.0).poll_flush(cx)))
+    }
+}
+
+impl<T: AsyncSeek + Unpin> Seek for BlockingIo<T> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        block_on(poll_fn(|cx| Pin::new(&mut self.0).poll_seek(cx, pos)))
+    }
+}
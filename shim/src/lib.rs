@@ -0,0 +1,62 @@
+//! Blocking stand-ins for the timer and scheduling functions of async runtimes, implemented with `std`, so code made
+//! blocking by [remove-async-await](https://docs.rs/remove-async-await) doesn't need the runtime. Its `profile(shim)`
+//! maps tokio's and async-std's functions to the ones here:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(profile(shim)))]
+//! async fn wait() {
+//!     tokio::time::sleep(std::time::Duration::from_millis(10)).await; // `remove_async_await_shim::sleep(...)`
+//! }
+//! ```
+//!
+//! Streams become iterators in the blocking build, and [`BlockingStream`] gives code that names stream types one type
+//! to use for all of them.
+//!
+//! With the `futures-io` feature, [`BlockingIo`] implements `std::io::Read`, `Write` and `Seek` over async IO objects,
+//! for passing them to functions whose IO bounds were rewritten.
+//!
+//! [`MaybeFuture`] is a future in the async build and the value itself in the blocking one (with the `blocking`
+//! feature), for signatures shared by both.
+
+mod block_on;
+#[cfg(feature = "futures-io")]
+mod io;
+mod maybe_future;
+mod stream;
+
+use std::{error::Error, fmt, time::Duration};
+
+#[cfg(feature = "futures-io")]
+pub use io::BlockingIo;
+pub use maybe_future::MaybeFuture;
+pub use stream::{BlockingStream, IteratorExt};
+
+/// Blocks the thread for `duration`, like `tokio::time::sleep`.
+pub fn sleep(duration: Duration) {
+    std::thread::sleep(duration)
+}
+
+/// Stands in for `tokio::time::timeout`. The blocking code it wraps has already run to completion by the time it's
+/// passed in, so there's nothing left to time out, and the value is always returned.
+pub fn timeout<T>(duration: Duration, value: T) -> Result<T, Elapsed> {
+    let _ = duration;
+    Ok(value)
+}
+
+/// Lets other threads run, like `tokio::task::yield_now` does for other tasks.
+pub fn yield_now() {
+    std::thread::yield_now()
+}
+
+/// The error of [`timeout`], like `tokio::time::error::Elapsed`. It's never returned, but code matching on it still
+/// compiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed(());
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {}
@@ -0,0 +1,89 @@
+use std::future::Future;
+#[cfg(feature = "blocking")]
+use std::{future::IntoFuture, marker::PhantomData};
+#[cfg(not(feature = "blocking"))]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A value that's awaited in the async build and used as is in the blocking one, for APIs that return something
+/// awaitable with the same signature in both builds, like a trait implemented by hand for each of them. It's a boxed
+/// future, unless the `blocking` feature is enabled, which makes it a wrapper around the value:
+///
+/// ```rs
+/// fn get(&self) -> MaybeFuture<'_, u32>;
+///
+/// let value = client.get().await; // async build
+/// let value = client.get().into_inner(); // blocking build
+/// ```
+///
+/// [`new`](MaybeFuture::new) takes a future and [`ready`](MaybeFuture::ready) a value in both builds, so the feature
+/// only changes what `MaybeFuture` holds. In the items remove-async-await transforms, it's rewritten like other
+/// futures: a return type of `MaybeFuture<'a, T>` becomes `T`, `MaybeFuture::new(future)` becomes the future's output
+/// and `MaybeFuture::ready(value)` becomes `value`.
+#[cfg(not(feature = "blocking"))]
+pub struct MaybeFuture<'a, T>(Pin<Box<dyn Future<Output = T> + Send + 'a>>);
+
+#[cfg(not(feature = "blocking"))]
+impl<'a, T> MaybeFuture<'a, T> {
+    /// Boxes the future.
+    pub fn new(future: impl Future<Output = T> + Send + 'a) -> Self {
+        MaybeFuture(Box::pin(future))
+    }
+
+    /// Boxes a future that's ready with `value` right away.
+    pub fn ready(value: T) -> Self
+    where
+        T: Send + 'a,
+    {
+        MaybeFuture::new(std::future::ready(value))
+    }
+
+    /// Blocks the thread until the future is ready, returning its output.
+    pub fn into_inner(self) -> T {
+        crate::block_on::block_on(self.0)
+    }
+}
+
+#[cfg(not(feature = "blocking"))]
+impl<T> Future for MaybeFuture<'_, T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+/// A value that's awaited in the async build and used as is in the blocking one. With the `blocking` feature, it wraps
+/// the value itself, which [`into_inner`](MaybeFuture::into_inner) returns, and awaiting it is still possible.
+#[cfg(feature = "blocking")]
+pub struct MaybeFuture<'a, T>(T, PhantomData<&'a ()>);
+
+#[cfg(feature = "blocking")]
+impl<'a, T> MaybeFuture<'a, T> {
+    /// Blocks the thread until the future is ready, wrapping its output.
+    pub fn new(future: impl Future<Output = T> + Send + 'a) -> Self {
+        MaybeFuture::ready(crate::block_on::block_on(future))
+    }
+
+    /// Wraps the value.
+    pub fn ready(value: T) -> Self {
+        MaybeFuture(value, PhantomData)
+    }
+
+    /// Returns the value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T> IntoFuture for MaybeFuture<'_, T> {
+    type Output = T;
+    type IntoFuture = std::future::Ready<T>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.0)
+    }
+}
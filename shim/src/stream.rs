@@ -0,0 +1,51 @@
+/// A stream in the blocking build: a plain iterator, wrapped so code that names a stream type has one type to name for
+/// every stream, like `BlockingStream<std::vec::IntoIter<u32>>` where the async build has a stream struct.
+///
+/// ```rs
+/// #[cfg(not(feature = "async"))]
+/// type Numbers = remove_async_await_shim::BlockingStream<std::vec::IntoIter<u32>>;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockingStream<I>(I);
+
+impl<I: Iterator> BlockingStream<I> {
+    /// Wraps anything that can be iterated over, like `futures::stream::iter`.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        BlockingStream(iter.into_iter())
+    }
+
+    /// Returns the iterator it wraps.
+    pub fn into_inner(self) -> I {
+        self.0
+    }
+}
+
+impl<I: Iterator> Iterator for BlockingStream<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<I: DoubleEndedIterator> DoubleEndedIterator for BlockingStream<I> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<I: ExactSizeIterator> ExactSizeIterator for BlockingStream<I> {}
+
+/// Turns iterators into [`BlockingStream`]s.
+pub trait IteratorExt: Iterator + Sized {
+    /// Wraps the iterator in a [`BlockingStream`].
+    fn into_stream(self) -> BlockingStream<Self> {
+        BlockingStream(self)
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
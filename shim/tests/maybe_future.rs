@@ -0,0 +1,30 @@
+#![cfg(feature = "blocking")]
+
+use std::{
+    future::{Future, IntoFuture},
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use remove_async_await_shim::MaybeFuture;
+
+fn get() -> MaybeFuture<'static, u32> {
+    MaybeFuture::ready(1)
+}
+
+fn computed() -> MaybeFuture<'static, u32> {
+    // run right away with the `blocking` feature
+    MaybeFuture::new(async { 2 })
+}
+
+#[test]
+fn maybe_future() {
+    assert_eq!(get().into_inner(), 1);
+    assert_eq!(computed().into_inner(), 2);
+    // it can still be awaited, and is ready right away
+    let mut future = pin!(get().into_future());
+    let poll = future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()));
+    assert_eq!(poll, Poll::Ready(1));
+}
@@ -0,0 +1,27 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+
+/// Like maybe-async, items are only made blocking when the `is_sync` feature is enabled.
+pub(crate) const IS_SYNC: bool = cfg!(feature = "is_sync");
+
+/// Removes maybe-async's `?Send` and `AFIT` from the arguments of `#[maybe_async(...)]`, since they only choose how
+/// async traits are written. Everything else is passed on to `remove_async_await`.
+pub(crate) fn remove_async_await_args(args: TokenStream) -> TokenStream {
+    let mut kept = Vec::new();
+    let mut arg = TokenStream::new();
+    let mut tokens = args.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        let comma = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == ',');
+        if !comma {
+            arg.extend([token]);
+        }
+        if comma || tokens.peek().is_none() {
+            let text = arg.to_string().replace(' ', "");
+            if text != "?Send" && text != "AFIT" {
+                kept.push(std::mem::take(&mut arg));
+            }
+            arg = TokenStream::new();
+        }
+    }
+    quote!(#(#kept),*)
+}
@@ -12,6 +12,37 @@
 //! remove-async-await = "1.0"
 //! ```
 //!
+//! ### Lightweight mode
+//!
+//! Everything below needs [syn](https://docs.rs/syn) with its full parser, which is most of the build time. If removing `async` and `.await` is all you need, turning off the default
+//! `full` feature drops it:
+//!
+//! ```toml
+//! [dependencies]
+//! remove-async-await = { version = "1.0", default-features = false }
+//! ```
+//!
+//! `remove_async_await` then walks the tokens instead of parsing them: it removes every `async` (and the `move` of `async move { ... }` blocks) and every `.await`, including inside macros, but
+//! leaves string literals and names containing "async" alone. Return types like `impl Future<Output = T>` aren't rewritten and no arguments are supported, and the other attributes like
+//! `#[remove_async_await::test]` and `#[remove_async_await::mirror]` need `full` (or `wasm`, below), as do the markers like `keep` and `sync_only!`.
+//!
+//! `full` stays on by default, even though the token walk builds faster: the crate has always parsed its input, and turning it off by default would silently stop
+//! rewriting the types and calls that existing users rely on.
+//!
+//! ### Precompiled mode
+//!
+//! The `wasm` feature keeps all of that without building syn: the transform comes precompiled to WebAssembly and runs through [watt](https://docs.rs/watt), so a cold build only compiles watt's
+//! small interpreter. Turn off `full` to use it:
+//!
+//! ```toml
+//! [dependencies]
+//! remove-async-await = { version = "1.0", default-features = false, features = ["wasm"] }
+//! ```
+//!
+//! Everything works like with `full`, with two differences: the macros take longer to expand, since the wasm is interpreted, and the spans of the input are lost on the way in, so errors point at the
+//! attribute instead of the code they're about. With both features on, `full` is used. The blob is built by `wasm/build.sh` in the repository, and
+//! CI checks that it's up to date with the core crate.
+//!
 //! ## Example
 //!
 //! This example assumes you want to keep your async API behind an optional feature called `async`.
@@ -46,21 +77,891 @@
 //!
 //! You can find more examples in the [`tests/` directory](https://github.com/naturecodevoid/remove-async-await/tree/main/tests).
 //!
+//! ## Feature gating
+//!
+//! Instead of wrapping every use in `#[cfg_attr(not(feature = "async"), ...)]`, you can pass `unless_feature = "async"`. The macro then emits both versions itself: the async item as is when the feature
+//! is enabled, and the blocking item when it isn't:
+//!
+//! ```rs
+//! #[remove_async_await::remove_async_await(unless_feature = "async")]
+//! pub async fn print() {
+//!     let string = get_string().await;
+//!     println!("{}", string);
+//! }
+//! ```
+//!
+//! For anything other than a feature, `when(...)` takes a full cfg predicate that decides when the async item is kept, like `when(target_arch = "wasm32")` or
+//! `when(all(feature = "net", not(feature = "blocking")))`. `#[remove_async_await::test]` and `#[remove_async_await::main]` accept both arguments too, in place of `feature = "..."`.
+//!
+//! `#[remove_async_await::dual]` is a shorthand for this. It uses `unless_feature = "async"` by default, `feature = "..."` to pick another feature, or `when(...)`, and takes any other argument too:
+//!
+//! ```rs
+//! #[remove_async_await::dual(feature = "tokio", map(tokio::time::sleep = std::thread::sleep))]
+//! pub async fn wait() {
+//!     tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+//! }
+//! ```
+//!
+//! With `doc_cfg`, both versions also get `#[cfg_attr(docsrs, doc(cfg(...)))]`, so docs.rs shows which one exists under which features. This needs
+//! `#![cfg_attr(docsrs, feature(doc_cfg))]` in your crate root.
+//!
+//! `#[cfg]` and `#[cfg_attr]` attributes in the item that test the same predicate are resolved in each version: in the blocking item, code under `#[cfg(feature = "async")]` is removed and
+//! `#[cfg(not(feature = "async"))]` is dropped from the code under it, and the other way around in the async item, so each version only has the code it compiles. An item that's gated
+//! on the predicate itself only gets the version it can be compiled in.
+//!
+//! ## Sibling functions
+//!
+//! To have both APIs at the same time instead of switching with a feature, pass `sibling`. The async item is kept as is, and a blocking copy named with the `_blocking` suffix (or the one given with
+//! `sibling = "_sync"`) is emitted next to it:
+//!
+//! ```rs
+//! #[remove_async_await::remove_async_await(sibling)]
+//! pub async fn get_value() -> u32 {
+//!     10
+//! }
+//!
+//! #[remove_async_await::remove_async_await(sibling)]
+//! pub async fn print() {
+//!     // `self::get_value_blocking()` in `print_blocking`
+//!     println!("{}", self::get_value().await);
+//! }
+//! ```
+//!
+//! Inherent impls get a second impl with blocking copies of their async methods and methods returning futures, so both sets of methods exist on the same type. Everything else is only in
+//! the original impl. Awaited calls to functions and methods copied along with the item, like `get()`, `Self::get()` and `self.get()`, are renamed too, and so are paths into the crate like
+//! `self::get()` and `crate::get()`, unless they're mapped with `map(...)`. Other calls, like an imported `sleep(d)`, are left alone, so functions with their own `sibling` elsewhere in the
+//! module are called through `self::`:
+//!
+//! ```rs
+//! #[remove_async_await::remove_async_await(sibling)]
+//! impl Client {
+//!     pub fn new() -> Self {
+//!         Client
+//!     }
+//!
+//!     pub async fn get(&self, path: &str) -> Response {
+//!         self.send(Request::get(path)).await
+//!     }
+//!
+//!     // `client.send_blocking(request)`, which `get_blocking` calls too
+//!     pub async fn send(&self, request: Request) -> Response {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! Traits and inline modules are copied as a whole, so only their own name changes, and trait impls implement the renamed trait. To name copies differently, pass a template like
+//! `rename = "blocking_{name}"` or `rename = "{name}_sync"` (which also turns on `sibling`). For traits, the rest of the template is converted to camel case, so `"blocking_{name}"` turns `Client` into
+//! `BlockingClient`:
+//!
+//! ```rs
+//! #[remove_async_await::remove_async_await(rename = "blocking_{name}")]
+//! pub trait Client {
+//!     async fn get(&self) -> u32;
+//! }
+//!
+//! #[remove_async_await::remove_async_await(rename = "blocking_{name}")]
+//! impl Client for MyClient {
+//!     async fn get(&self) -> u32 {
+//!         10
+//!     }
+//! }
+//! ```
+//!
+//! The blocking copy keeps the visibility of the async item, unless `vis = "..."` gives it another one, like `vis = "pub(crate)"`. This works with `mirror`, `trait_pair` and feature gating
+//! too, and for inherent impls it applies to each of their items.
+//!
+//! The new names are spanned like the names they come from, so errors and lints point at the async item, and names written by a `macro_rules!` macro keep its hygiene. With
+//! `name_span = "call_site"`, they're spanned at the attribute instead, like the names a macro writes next to it. Attributes like `#[allow(...)]` are copied along with the item, so they
+//! cover the copy too, and a template that would give the copy the name of the async item is an error. Variables the macro introduces, like the ones from `hoist_awaits`, are hygienic,
+//! so they can't clash with the ones in the item.
+//!
+//! ## Mirrored modules
+//!
+//! `#[remove_async_await::mirror]` does the same for a whole module: the module is kept as is, and a transformed copy is emitted next to it, named `blocking` or whatever `name = "..."`
+//! says. Any other argument is passed on to the transform:
+//!
+//! ```rs
+//! #[remove_async_await::mirror(name = "blocking", map(tokio::time::sleep = std::thread::sleep))]
+//! pub mod async_impl {
+//!     pub async fn wait() {
+//!         tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+//!     }
+//! }
+//!
+//! // async_impl::wait().await, or blocking::wait()
+//! ```
+//!
+//! The module has to be inline. For modules in their own files, see [Build scripts](#build-scripts).
+//!
+//! ## Wrapping with an executor
+//!
+//! Some bodies can't be made blocking, like ones using `select!` or running futures concurrently. With `block_on`, functions and methods keep their body as is and run it with
+//! `futures::executor::block_on` instead, so only the signature becomes blocking. Another executor can be given with `block_on = ...`, like `block_on = tokio::runtime::Handle::current().block_on`:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(block_on))]
+//! pub async fn get_both() -> (u32, u32) {
+//!     futures::join!(get_value(1), get_value(2))
+//! }
+//! ```
+//!
+//! In this example, if the `async` feature is not used, it would expand to this:
+//!
+//! ```rs
+//! pub fn get_both() -> (u32, u32) {
+//!     ::futures::executor::block_on(async move { futures::join!(get_value(1), get_value(2)) })
+//! }
+//! ```
+//!
+//! ## Mapping paths
+//!
+//! Some async APIs have a blocking equivalent with a different path. You can tell `remove_async_await` to rewrite paths used in expressions with `map(from = to, ...)`:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(map(tokio::time::sleep = std::thread::sleep)))]
+//! async fn wait() {
+//!     tokio::time::sleep(Duration::from_secs(1)).await;
+//! }
+//! ```
+//!
+//! Mappings also match the start of longer paths, so `map(tokio::fs = std::fs)` will rewrite `tokio::fs::read` to `std::fs::read`. Mappings, including the ones from `types(...)` and
+//! profiles, also rewrite `use` items, which keep the name they import: `use tokio::time::sleep;` becomes `use std::thread::sleep;`, and `use smol::io::AsyncReadExt;` becomes
+//! `use std::io::Read as AsyncReadExt;` with `profile(smol)`.
+//!
+//! ### Types
+//!
+//! `map(...)` only rewrites paths in expressions. To rewrite types, such as parameter and return types, struct fields, trait bounds and where clauses, use `types(from = to, ...)`:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(types(tokio::net::TcpStream = std::net::TcpStream)))]
+//! async fn peer(stream: &tokio::net::TcpStream) -> std::io::Result<SocketAddr> {
+//!     stream.peer_addr()
+//! }
+//! ```
+//!
+//! ### Method chains
+//!
+//! Some APIs build a value with a chain of blocking calls and only await its last method, like sqlx's queries. `chains(root.method = hook, ...)` rewrites awaiting `method` at the end of a chain
+//! that starts with calling `root` to a call to `hook`, which gets the rest of the chain and the method's arguments, so the blocking data-access code can share the query building:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(chains(sqlx::query.fetch_one = db::fetch_one, sqlx::query!.execute = self.db.execute)))]
+//! impl Users {
+//!     async fn name(&self, id: u32) -> sqlx::Result<SqliteRow> {
+//!         // becomes `db::fetch_one(sqlx::query("...").bind(id), &self.pool)`
+//!         sqlx::query("SELECT name FROM users WHERE id = ?").bind(id).fetch_one(&self.pool).await
+//!     }
+//! }
+//! ```
+//!
+//! The root can be a function or a macro (written with `!`), and it matches shorter paths when it's imported, like `query(sql)`. The hook is a function or a method, like `self.db.execute`,
+//! which is called with the chain as the first argument.
+//!
+//! ### Blocking methods
+//!
+//! Many crates with both APIs name their blocking methods after the async ones, like zbus' `call_blocking` next to `call`, or flume's `recv` next to `recv_async`. With
+//! `blocking_methods(...)`, awaited method calls are renamed to follow the convention instead of only losing the `.await`: `prefix = "..."` and `suffix = "..."` are added to the name, after
+//! removing `strip = "..."` from its end. `on(...)` limits it to some receivers, written either as expressions like `self.proxy` or as the types of the function's parameters like
+//! `flume::Receiver`:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(blocking_methods(strip = "_async", on(flume::Receiver))))]
+//! async fn next(rx: &flume::Receiver<u32>) -> Option<u32> {
+//!     // becomes `rx.recv().ok()`
+//!     rx.recv_async().await.ok()
+//! }
+//! ```
+//!
+//! ### Profiles
+//!
+//! For popular crates with a separate blocking API, you can use a built-in set of mappings with `profile(...)` instead of writing them yourself. Profiles also rewrite types, such as parameter and return types.
+//!
+//! | Profile        | Maps                                                                                                                                                                                                            |
+//! | -------------- | --------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------- |
+//! | `async_std`    | `async_std::{fs, io, net, os, path}` and `async_std::task::{sleep, yield_now}` to the equivalents in `std`, and runs awaited tasks like `task::spawn(future).await` inline                                      |
+//! | `embedded_hal` | `embedded_hal_async` and `embedded_io_async` to `embedded_hal` and `embedded_io`, whose traits have the same methods, like `SpiBus::transfer`, `I2c::write` and `DelayNs::delay_ms`                             |
+//! | `reqwest`      | `reqwest::{Client, ClientBuilder, Request, RequestBuilder, Response, Body, get, multipart}` to the equivalents in `reqwest::blocking`                                                                           |
+//! | `shim`         | `tokio::time::{sleep, timeout, error::Elapsed}`, `tokio::task::yield_now` and the same in async-std to the equivalents in `remove_async_await_shim`                                                             |
+//! | `smol`         | `smol::{fs, io, net}`, `smol::Timer::after`, `smol::future::yield_now` and the async-io, async-fs and async-net crates to the equivalents in `std`, and runs awaited tasks like `smol::unblock(f).await` inline |
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(profile(reqwest)))]
+//! async fn get_text(client: &reqwest::Client, url: &str) -> reqwest::Result<String> {
+//!     client.get(url).send().await?.text().await
+//! }
+//! ```
+//!
+//! The `shim` profile maps runtime functions to [remove-async-await-shim](https://docs.rs/remove-async-await-shim), which has blocking versions of them written with `std`, so the
+//! blocking build doesn't need the runtime. Add it as a dependency for the blocking build. Its `timeout` always returns the value, since the code it wraps has already run by then.
+//!
+//! The `embedded_hal` profile is for drivers with an async and a blocking API, which take the async traits in one build and the blocking ones in the other:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(profile(embedded_hal)))]
+//! impl<I: embedded_hal_async::i2c::I2c> Sensor<I> {
+//!     async fn reset(&mut self) -> Result<(), I::Error> {
+//!         self.i2c.write(ADDRESS, &[RESET]).await
+//!     }
+//! }
+//! ```
+//!
+//! `embedded_hal_async::digital::Wait` doesn't have a blocking equivalent, so code waiting on pins needs [`async_only!` and `sync_only!`](#build-specific-code).
+//!
+//! ### Delays
+//!
+//! embassy-time's timers have no blocking equivalent that works on every target, so the blocking delay to use is given with `delay = ...`. It's called with a `core::time::Duration` instead of
+//! awaiting `Timer::after(duration)`, `Timer::after_millis(ms)` (and the other units) and `Delay.delay_ms(ms)` (and `delay_us` and `delay_ns`), and it can be a function like
+//! `std::thread::sleep` or a closure, which is written out where it's called:
+//!
+//! ```rs
+//! #[cfg_attr(
+//!     not(feature = "async"),
+//!     remove_async_await::remove_async_await(delay = |duration: Duration| self.delay.delay_us(duration.as_micros() as u32))
+//! )]
+//! impl<I: I2c> Sensor<I> {
+//!     async fn reset(&mut self) -> Result<(), I::Error> {
+//!         self.i2c.write(ADDRESS, &[RESET]).await?;
+//!         embassy_time::Timer::after_millis(10).await;
+//!         Ok(())
+//!     }
+//! }
+//! ```
+//!
+//! ## Functions returning futures
+//!
+//! Functions that aren't `async` but return a future are also supported. The return type is rewritten to the future's output, and the `async` block in the body is removed like any other:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! fn get_string() -> impl Future<Output = String> {
+//!     async move { "hello world".to_owned() }
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! fn get_string() -> String {
+//!     "hello world".to_owned()
+//! }
+//! ```
+//!
+//! Boxed futures like `BoxFuture<'a, T>`, `LocalBoxFuture<'a, T>` and `Pin<Box<dyn Future<Output = T> + Send + 'a>>` are rewritten to `T` as well, and the `Box::pin(...)`, `.boxed()` or
+//! `.boxed_local()` that created them is removed. This happens wherever the type is used, such as in local variables and struct fields (so `Option<Pin<Box<dyn Future<Output = T>>>>` becomes
+//! `Option<T>`), and also works on type aliases and associated types. Only futures lose their box: async blocks, calls to functions the macro knows are rewritten (async functions
+//! and functions returning futures in the same item, like a recursive `Box::pin(compute(n - 1))`, calls between `sibling` copies and mapped paths), and anything boxed in a function
+//! returning a boxed future. Values like `Box::pin(node)`, `Box::pin(make_buf())` or `Builder::new().boxed()` are left alone, so a future from another item is boxed through an async
+//! block, like `Box::pin(async move { compute(n).await })`:
+//!
+//! ```rs
+//! impl Task for Count {
+//!     #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//!     type Output = LocalBoxFuture<'static, u32>;
+//! }
+//! ```
+//!
+//! `std::future::ready(value)` (and `futures::future::ready`) is replaced with just `value`.
+//!
+//! Futures that are awaited in place, like `(&mut future).await`, `future.as_mut().await` and `Pin::new(&mut future).await`, become just `future`.
+//!
+//! Function pointers and callbacks returning futures are rewritten too, so `fn(Request) -> BoxFuture<'static, Response>` becomes `fn(Request) -> Response` and
+//! `Box<dyn Fn(Request) -> Pin<Box<dyn Future<Output = Response>>>>` becomes `Box<dyn Fn(Request) -> Response>`. This is useful for statics and consts holding handlers, like routing tables.
+//!
+//! Functions returning a task handle, like tokio's `JoinHandle<T>`, return `T` instead, and the task they spawn is run inline: `tokio::spawn(future)` becomes the future's output and
+//! `spawn_blocking(f)` becomes `f()`. Keep in mind that awaiting a `JoinHandle` gives a `Result`, so callers that unwrap it need to be handled separately. `std::thread::JoinHandle` isn't affected
+//! (a `JoinHandle` that isn't named through `tokio`, `async_std` or `smol`, like an imported one, is only rewritten when the function spawns a task),
+//! and neither are threads or other pools like `std::thread::spawn` and `rayon::spawn`: only tokio's, async-std's and smol's spawning functions (or imports of them, like `task::spawn`) count.
+//!
+//! For signatures that have to stay the same in both builds, like a trait implemented by hand for each of them, remove-async-await-shim's `MaybeFuture<'a, T>` is a boxed future, or
+//! the value itself with the shim's `blocking` feature. `.into_inner()` gives the value in both builds, blocking on the future in the async one. In the items the macro transforms, it's
+//! rewritten like other futures: `MaybeFuture<'a, T>` becomes `T`, `MaybeFuture::new(future)` becomes the future's output and `MaybeFuture::ready(value)` becomes `value`. Both
+//! constructors exist in both builds; with `blocking`, `new` blocks on the future right away.
+//!
+//! ## Future parameters
+//!
+//! Generic parameters bounded by `Future` are removed, and arguments using them (or using `impl Future` directly) take the future's output instead:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! async fn run<F: Future<Output = u32>>(future: F) -> u32 {
+//!     future.await + 1
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! fn run(future: u32) -> u32 {
+//!     future + 1
+//! }
+//! ```
+//!
+//! If the future shouldn't run before the function is called, use `closure(F)` to turn the parameter into a closure instead. The example above would become `fn run<F: FnOnce() -> u32>(future: F) -> u32`, and
+//! `future.await` would become `future()`. For `impl Future` arguments, pass the name of the argument instead, like `closure(future)`.
+//!
+//! `Future` bounds in where clauses work the same way. When a parameter is removed, the rest of its where clause predicates (like `F: Send`) are removed with it.
+//!
+//! `IntoFuture<Output = T>` bounds are treated the same as `Future<Output = T>`, and `value.into_future()` (or `IntoFuture::into_future(value)`) is replaced with just `value`. The
+//! `.into_future()` of a known stream is `StreamExt::into_future`, so it's left alone.
+//!
+//! ## Async blocks
+//!
+//! Async blocks become plain blocks. A `return` or `?` in an async block only leaves the block, though, while in a plain block it would leave the function, so blocks with one of their own
+//! are run in a closure instead, which keeps the same meaning:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! async fn double_or_zero(input: &str) -> u32 {
+//!     let doubled: Result<u32, ParseIntError> = async move {
+//!         let value: u32 = input.parse()?;
+//!         Ok(value * 2)
+//!     }
+//!     .await;
+//!     doubled.unwrap_or(0)
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! fn double_or_zero(input: &str) -> u32 {
+//!     let doubled: Result<u32, ParseIntError> = {
+//!         fn __remove_async_await_call<T>(f: impl FnOnce() -> T) -> T {
+//!             f()
+//!         }
+//!         __remove_async_await_call(move || {
+//!             let value: u32 = input.parse()?;
+//!             Ok(value * 2)
+//!         })
+//!     };
+//!     doubled.unwrap_or(0)
+//! }
+//! ```
+//!
+//! The async block a function or closure returning a future ends with is flattened without a closure, since leaving it and leaving the function are the same. `break` and `continue` can't leave
+//! an async block, so they're unaffected.
+//!
+//! ## Async closures
+//!
+//! `AsyncFn`, `AsyncFnMut` and `AsyncFnOnce` bounds are rewritten to `Fn`, `FnMut` and `FnOnce`, so `F: AsyncFnMut(u32) -> Result<()>` becomes `F: FnMut(u32) -> Result<()>` and `f(x).await` becomes
+//! `f(x)`. Async closures like `async |x| ...` and `async move || ...` become regular closures.
+//!
+//! ## Sinks
+//!
+//! `Sink` bounds don't have a blocking equivalent in `std`, so you choose what they become with `sink = ...`:
+//!
+//! -   `sink = closure` rewrites `S: Sink<T, Error = E>` to `S: FnMut(T) -> Result<(), E>`. For arguments using it, `sink.send(item).await` and `sink.feed(item).await` become `sink(item)`, and
+//!     `sink.flush().await` and `sink.close().await` become `Ok(())`. The `Error` has to be given, since it's used in the closure's return type
+//! -   `sink = path::to::Trait` rewrites `S: Sink<T, Error = E>` to `S: path::to::Trait<T, Error = E>`, leaving method calls alone, so your trait should have the methods you use (like `send`)
+//!
+//! ## Traits and impls
+//!
+//! `remove_async_await` can also be used on a whole trait or impl, which transforms all of its methods. This includes `async fn` in traits and methods returning `impl Future<Output = T>` (in both the trait
+//! and its impls, which don't have to pick the same style), and default method bodies.
+//!
+//! If the trait or impl also has an `#[async_trait]` (or `#[async_trait::async_trait]`) attribute after `remove_async_await`, it is removed, so a single `cfg_attr` is enough:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! #[async_trait]
+//! trait Greet {
+//!     async fn greet(&self) -> String;
+//! }
+//! ```
+//!
+//! Traits written with associated futures, like `type Fut: Future<Output = T>`, have the associated type removed and `Self::Fut` rewritten to `T`:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! trait Service {
+//!     type Fut: Future<Output = u32>;
+//!
+//!     fn call(&self, n: u32) -> Self::Fut;
+//! }
+//!
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! impl Service for Double {
+//!     type Fut = Ready<u32>;
+//!
+//!     fn call(&self, n: u32) -> Self::Fut {
+//!         std::future::ready(n * 2)
+//!     }
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! trait Service {
+//!     fn call(&self, n: u32) -> u32;
+//! }
+//!
+//! impl Service for Double {
+//!     fn call(&self, n: u32) -> u32 {
+//!         n * 2
+//!     }
+//! }
+//! ```
+//!
+//! In impls, associated types are removed if they are a future that `remove_async_await` recognizes, like `Ready<T>`, `BoxFuture<'a, T>` or `Pin<Box<dyn Future<Output = T>>>`.
+//!
+//! Generic associated types like `type Fut<'a>: Future<Output = &'a str> + 'a` are removed the same way, with their parameters replaced by the arguments they're used with, so `Self::Fut<'b>` becomes
+//! `&'b str`. Lifetime parameters that were only needed for the returned future (like the `'a` in `fn get<'a>(&'a self) -> Self::Fut<'a>`) are elided.
+//!
+//! Methods written like `async_trait`'s expansion, with an explicit `'async_trait` lifetime and `-> Pin<Box<dyn Future<Output = T> + Send + 'async_trait>>`, are supported too. The `'async_trait`
+//! lifetime and the bounds using it (like `'life0: 'async_trait` and `Self: 'async_trait`) are removed, and lifetimes like `'life0` are elided if they aren't needed anymore.
+//!
+//! ### Trait pairs
+//!
+//! `trait_pair` emits both traits from one definition: the async trait as is, named `{name}Async`, and the blocking trait, which keeps the name. The names can be changed with templates, like
+//! `trait_pair(sync_name = "Blocking{name}", async_name = "{name}")`. With `bridge = executor`, the blocking trait is also implemented for every implementor of the async one, running its async
+//! methods with the executor:
+//!
+//! ```rs
+//! #[remove_async_await::remove_async_await(trait_pair(bridge = futures::executor::block_on))]
+//! pub trait Store {
+//!     async fn get(&self, key: u32) -> u32;
+//! }
+//!
+//! // `MyStore` implements `StoreAsync`, and gets `Store` from the bridge
+//! impl StoreAsync for MyStore {
+//!     async fn get(&self, key: u32) -> u32 {
+//!         key
+//!     }
+//! }
+//! ```
+//!
+//! Since the bridge is a blanket impl, types implementing the async trait can't implement the blocking one themselves.
+//!
+//! ## Stripping bounds
+//!
+//! Async code often needs `Send`, `Sync` and `'static` bounds because values are held across `.await` points or spawned onto other threads. Blocking code usually doesn't, so you can remove them with
+//! `strip_bounds`:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(strip_bounds))]
+//! async fn call<F: Fn() + Send + Sync + 'static>(f: F) {
+//!     f()
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! fn call<F: Fn()>(f: F) {
+//!     f()
+//! }
+//! ```
+//!
+//! Bounds are removed from generic parameters, where clauses, `impl Trait` and `dyn Trait`. To pick which bounds are removed, list them like `strip_bounds(Send, 'static)`.
+//!
+//! `Unpin` bounds (like `T: Unpin` or `where Self: Unpin`) usually only exist because of pinning, so they can be removed with `strip_unpin`, which is the same as `strip_bounds(Unpin)`. Supertraits of traits
+//! are stripped as well.
+//!
+//! ## Combinators
+//!
+//! Awaiting futures-lite's `zip`, `or` and `race` (either `future::zip(a, b)` or `a.zip(b)`) is lowered to something that makes sense when blocking:
+//!
+//! -   `zip(a, b)` becomes `(a, b)`, so both run one after the other
+//! -   `or(a, b)` becomes `a`, since the first future is preferred
+//! -   `race(a, b)` becomes `a` by default. Pass `race = second` to keep `b` instead, or `race = error` to get a compile error
+//!
+//! ## Streams
+//!
+//! Streams become iterators. `Stream<Item = T>` bounds, such as in `impl Stream<Item = T>` return types, are rewritten to `Iterator<Item = T>`, and the stream is built with `std::iter` instead.
+//! Without an `Item`, only paths into `futures`, `futures_core`, `futures_util` or `tokio_stream` are rewritten, so your own traits called `Stream` are left alone:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! fn numbers() -> impl Stream<Item = u32> {
+//!     stream::iter(vec![1, 2, 3])
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! fn numbers() -> impl Iterator<Item = u32> {
+//!     IntoIterator::into_iter(vec![1, 2, 3])
+//! }
+//! ```
+//!
+//! `stream::iter`, `once`, `empty`, `repeat`, `repeat_with` and `unfold` (from `futures` or `tokio_stream`) are supported. Most `StreamExt` methods have an `Iterator` method with the same name, and the ones that don't
+//! are lowered: `.then(|x| async { ... })` becomes `.map(...)`, `.buffered(n)` and `.buffer_unordered(n)` are removed, `.for_each_concurrent(n, f)` becomes `.for_each(f)`, `.try_next()` becomes
+//! `.next().transpose()` and `.try_collect()` becomes `.collect::<Result<_, _>>()`. Other types can have methods with these names, so apart from `.then(...)` they're only lowered on
+//! known streams: ones built from a constructor or `.then(...)`, variables bound to them, parameters typed as streams and anything in a function returning a boxed stream.
+//!
+//! Boxed streams like `BoxStream<'a, T>`, `LocalBoxStream<'a, T>` and `Pin<Box<dyn Stream<Item = T> + Send + 'a>>` become `Box<dyn Iterator<Item = T> + 'a>`, wherever they are (including struct fields).
+//! `Send` is dropped, since most iterators aren't `Send`. The `.boxed()` or `Box::pin(...)` that created them becomes `Box::new(...)`.
+//!
+//! Code that names a stream type, like an associated type bounded by `Stream`, can name `remove_async_await_shim::BlockingStream<I>` in the blocking build instead, which wraps any
+//! iterator `I`. `IteratorExt::into_stream` and `BlockingStream::new` build one.
+//!
+//! ## IO traits
+//!
+//! Bounds on tokio's and futures' `AsyncRead`, `AsyncWrite`, `AsyncSeek` and `AsyncBufRead` are rewritten to `std::io::Read`, `Write`, `Seek` and `BufRead`, in generic parameters, where clauses,
+//! `impl Trait` and `dyn Trait`. The `Unpin` next to them is removed too:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! async fn read_all<R: AsyncRead + Unpin>(mut reader: R) -> std::io::Result<Vec<u8>> {
+//!     let mut buf = Vec::new();
+//!     reader.read_to_end(&mut buf).await?;
+//!     Ok(buf)
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```rs
+//! fn read_all<R: std::io::Read>(mut reader: R) -> std::io::Result<Vec<u8>> {
+//!     let mut buf = Vec::new();
+//!     reader.read_to_end(&mut buf)?;
+//!     Ok(buf)
+//! }
+//! ```
+//!
+//! Most methods of `AsyncReadExt` and `AsyncWriteExt`, like `read_to_end` and `write_all`, have the same name in `std::io`, so they work as is. `shutdown` and `close` don't have an equivalent and aren't
+//! rewritten.
+//!
+//! futures' extension traits (from futures, futures-util, futures-io and futures-lite) are rewritten to the `std::io` traits too, wherever they're named: `use futures::io::{AsyncReadExt, Cursor};`
+//! becomes `use {futures::io::Cursor, std::io::Read as AsyncReadExt};`, so their methods are called on the blocking IO objects, and `futures::io::AsyncReadExt::read_to_end(&mut reader, &mut buf)`
+//! becomes `::std::io::Read::read_to_end(&mut reader, &mut buf)`.
+//!
+//! To pass an async IO object to the blocking version, wrap it in `remove_async_await_shim::BlockingIo` (with the shim's `futures-io` feature), which implements `Read`, `Write` and `Seek`
+//! over futures' IO traits by blocking on each call. tokio's IO objects can be converted to futures' with tokio-util's `compat()`.
+//!
+//! ## Build-specific code
+//!
+//! For the few lines that differ between the builds, `remove_async_await::async_only! { ... }` is dropped from the blocking build, and `remove_async_await::sync_only! { ... }` is only
+//! kept in the blocking build:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! pub async fn connect() -> Connection {
+//!     let connection = Connection::open().await;
+//!     remove_async_await::async_only! {
+//!         connection.spawn_keepalive();
+//!     }
+//!     remove_async_await::sync_only! {
+//!         connection.set_timeout(TIMEOUT);
+//!     }
+//!     connection
+//! }
+//! ```
+//!
+//! They can only be used as statements, and are best written with their full path, since an import would be unused in the blocking build.
+//!
+//! For code outside of the transformed items, like in another macro, `remove_async_await::maybe_await!(expr)` is `expr.await` when the `async` feature of your crate is enabled and `expr` when
+//! it isn't. Pass `feature = "..."` for another feature, like `maybe_await!(get(), feature = "tokio")`. In the items the macro transforms, it's removed like `.await`, whatever the features are:
+//!
+//! ```rs
+//! macro_rules! fetch {
+//!     ($client:expr) => {
+//!         remove_async_await::maybe_await!($client.fetch())
+//!     };
+//! }
+//! ```
+//!
+//! `remove_async_await::spawn_or_call!({ ... })` is the same for spawning: it's `tokio::spawn(async move { ... })` when the feature is enabled, and calls the block right away when it isn't,
+//! or runs it with `std::thread::spawn(move || { ... })` with `spawn_or_call!(thread, { ... })`. It takes `feature = "..."` too, and the transform turns it into the blocking version:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! pub async fn start(connection: Connection) {
+//!     remove_async_await::spawn_or_call!(thread, {
+//!         connection.keepalive().await;
+//!     });
+//! }
+//! ```
+//!
+//! The handles they return differ between the builds, so code that joins the task is best kept in the functions returning it; see [Functions returning futures](#functions-returning-futures) for how functions returning a `JoinHandle` are rewritten.
+//!
+//! When a single statement has no mechanical translation, `#[remove_async_await::replace_with(...)]` swaps it for the given statements in the blocking build:
+//!
+//! ```rs
+//! #[remove_async_await::dual]
+//! pub async fn wait(duration: Duration) {
+//!     #[remove_async_await::replace_with(std::thread::sleep(duration))]
+//!     async_io::Timer::after(duration).await;
+//! }
+//! ```
+//!
+//! Like `keep`, this can only be used on statements when the macro runs in the async build too.
+//!
+//! And when the whole blocking implementation is different, `sync_body = { ... }` keeps the transformed signature with the given body, so the two stay next to each other:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(sync_body = { blocking::resolve(host) }))]
+//! pub async fn resolve(host: &str) -> IpAddr {
+//!     async_dns::resolve(host).await
+//! }
+//! ```
+//!
+//! ## Keeping code as is
+//!
+//! Items, statements and expressions marked with `#[remove_async_await::keep]` are left alone, which is useful for async code that should stay async in the blocking build, like a callback
+//! given to an executor:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+//! pub async fn run() -> u32 {
+//!     #[remove_async_await::keep]
+//!     async fn callback() -> u32 {
+//!         1
+//!     }
+//!     futures::executor::block_on(callback())
+//! }
+//! ```
+//!
+//! Rust doesn't allow attribute macros on statements and expressions, so those can only be marked when the macro runs in the async build too, such as with `dual`, `unless_feature`,
+//! `sibling` or `mirror`. The attribute is removed from the async item in those cases.
+//!
+//! ## wasm-bindgen
+//!
+//! Crates that export async functions to JavaScript on wasm usually want the blocking version on native targets. With `wasm_bindgen`, `#[wasm_bindgen]` attributes (including
+//! `#[wasm_bindgen(start)]`) are removed from the transformed items. `wasm_bindgen(js_future = path)` also rewrites `JsFuture::from(promise)` to `path(promise)`, so awaiting a promise calls your native
+//! equivalent:
+//!
+//! ```rs
+//! #[cfg_attr(not(target_arch = "wasm32"), remove_async_await::remove_async_await(wasm_bindgen(js_future = crate::native::resolve)))]
+//! #[wasm_bindgen]
+//! pub async fn fetch(url: String) -> Result<JsValue, JsValue> {
+//!     let promise = start_fetch(&url);
+//!     JsFuture::from(promise).await
+//! }
+//! ```
+//!
+//! Without a bridge, `JsFuture::from(promise)` is left in the blocking item, and wasm_bindgen_futures only resolves promises on wasm. The blocking function gets a warning for paths under `wasm_bindgen_futures`, and with
+//! `strict`, any `JsFuture::from` call is an error pointing at it.
+//!
+//! ## Other attributes
+//!
+//! Some attributes from other crates only make sense on async items, so `remove_async_await` removes or replaces them when they come after it:
+//!
+//! -   `#[async_trait]` is removed (see [Traits and impls](#traits-and-impls))
+//! -   `#[async_recursion]` (including `#[async_recursion(?Send)]`) is removed, since blocking functions can recurse without boxing
+//! -   `#[tokio::main]` (including arguments like `flavor` and `worker_threads`), `#[async_std::main]`, `#[actix_rt::main]` and `#[actix_web::main]` are removed, so `async fn main` becomes a plain
+//!     `fn main`
+//! -   `#[tokio::test]` and `#[async_std::test]` (and the `actix_rt` and `actix_web` equivalents) are replaced with `#[test]`, so one annotated test works either way. Test attributes that wrap them, like
+//!     `#[test_log::test(tokio::test)]`, lose the runtime's attribute so they use `#[test]` (`#[test_log::test]`), and attributes that sit next to them, like `#[rstest]`, are kept
+//! -   `#[tracing::instrument]` (or `#[instrument]`) is moved after the other attributes, so it instruments the blocking function. Arguments that were futures are removed from `skip(...)`, since
+//!     they're plain values now (so their type has to implement `Debug`). `err` and `ret` are kept, since they record the future's output on an async function and the returned value on a
+//!     blocking one, which is the same value
+//! -   `#[automock]` from mockall is moved after the other attributes, so it mocks the blocking trait. Expectations are the same in both builds, since mockall's expectations for async methods
+//!     already return the output
+//!
+//! ### Attribute order
+//!
+//! Attributes that come after `remove_async_await` are re-emitted in the same order, so they expand once the item has been transformed. `before(...)` lists attribute macros that should see the async item:
+//! they're moved in front of a new copy of the attribute, with the path it was written with (like `#[raa::remove_async_await(...)]` for a renamed dependency, or `#[maybe_async]`), so they
+//! expand first. `after(...)` lists attributes that should be re-emitted last, after all others:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(before(my_macros::register), after(tracing::instrument)))]
+//! #[tracing::instrument]
+//! #[my_macros::register]
+//! #[my_macros::retry]
+//! async fn get_value() -> u32 {
+//!     10
+//! }
+//! ```
+//!
+//! ## Awaits in macros
+//!
+//! The arguments of standard library macros that take expressions, like `println!` and `assert_eq!`, are folded like the rest of the item, but other macros are left as they are, since their
+//! tokens can mean anything. With `macro_tokens`, `async` and `.await` are removed from the tokens of those macros instead, like [`remove_async_await_string`](#remove_async_await_string) does,
+//! while the rest of the item still goes through the transform:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(macro_tokens))]
+//! pub async fn print() {
+//!     my_macros::log!("{}", get_string().await); // `.await` **will** be removed
+//! }
+//! ```
+//!
+//! With `macro_exprs`, the arguments of every macro are folded when they parse as a comma separated list of expressions, which covers most macros from other crates, such as
+//! `log::info!`, without listing them. Macros whose arguments don't parse are still left as they are, or go through `macro_tokens` if it's passed too. Macros that don't evaluate their
+//! arguments, like `stringify!`, see the folded arguments as well.
+//!
+//! `macro_rules!` definitions in the item are always left as they are, and `strict` doesn't check their rules.
+//!
+//! ## Drop order
+//!
+//! Temporaries in an awaited expression live until the end of the statement, like the guard in `let value = cache.lock().unwrap().get(key).await + other().await;`, which is still locked while
+//! `other()` runs. With `hoist_awaits`, the awaited expressions are bound with `let` in a block around their statement, so their temporaries are dropped right after them instead:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(hoist_awaits))]
+//! pub async fn total(cache: &Mutex<Cache>) -> u32 {
+//!     // blocking: let value = { let __awaited_0 = cache.lock().unwrap().get(key); let __awaited_1 = other(); __awaited_0 + __awaited_1 };
+//!     let value = cache.lock().unwrap().get(key).await + other().await;
+//!     value
+//! }
+//! ```
+//!
+//! The values the awaits resolve to are still dropped at the end of the statement, like in the async function, so a guard from `*mutex.lock().await += 1;` is unlocked before the next
+//! statement. Nothing runs in a different order either: only the awaits that run first in their statement are hoisted, so in `record() + other().await`, `other()` stays where it is since
+//! `record()` runs before it. Awaits that only run sometimes, like the ones in `if` branches, loops, closures and the right side of `&&`, stay where they are too, and so do
+//! borrowed ones like `let name = &name().await;`, since the borrow keeps the value alive past the statement.
+//!
+//! ## Strict mode
+//!
+//! Awaits inside macros that aren't known to take expressions, like a custom `log!`, are left as they are, which usually shows up as a confusing type error in the blocking build. With
+//! `strict`, every `.await` left in the blocking item is an error pointing at it instead:
+//!
+//! ```rs
+//! #[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await(strict))]
+//! pub async fn print() {
+//!     // error: `.await` is left after the transform
+//!     my_macros::log!("{}", get_string().await);
+//! }
+//! ```
+//!
+//! Awaits in async code that's kept on purpose, like a function marked with `keep`, are fine. Awaits in code evaluated at compile time, like const blocks, array lengths and enum discriminants (which can come
+//! from macros), are always errors, with or without `strict`: the async build rejects them, and removing them would make the blocking build accept them.
+//!
+//! It also points out constructs without a blocking equivalent, with what to use instead: `select!` and `join!`, tasks spawned outside of functions returning a `JoinHandle` (which aren't
+//! inlined), `JsFuture::from(promise)` without a bridge from `wasm_bindgen(js_future = path)`, and types like `Stream` or `AsyncRead` that couldn't be rewritten.
+//!
+//! Without `strict`, a blocking function that still uses async crates or types, like `tokio::spawn` or `futures::channel::mpsc::Receiver`, compiles but may need a runtime after all. With
+//! `warn_leaks`, it gets a warning listing them. This works by using a deprecated constant, so `#[allow(deprecated)]` on the function silences it when the code doesn't need a runtime, and
+//! crates that deny warnings (or `deprecated`) should only turn it on while migrating. Paths under `futures::executor` and `tokio::runtime`, which run futures from blocking code, are fine.
+//!
+//! Proc macros can't emit warnings on stable, so an attribute left on an item that isn't async anymore does nothing silently. With `deny_noop`, that's an error instead, which can be
+//! combined with `strict` like `remove_async_await(strict, deny_noop)`.
+//!
+//! ## Tests
+//!
+//! Instead of pairing `#[cfg_attr]` with `#[tokio::test]`, a test can use `#[remove_async_await::test]`. It expands to `#[tokio::test] async fn ...` when the `async` feature is enabled and to
+//! `#[test] fn ...` (transformed) otherwise:
+//!
+//! ```rs
+//! #[remove_async_await::test]
+//! async fn get_value_test() {
+//!     assert_eq!(get_value().await, 10);
+//! }
+//! ```
+//!
+//! The feature and the async runtime's attribute can be changed with `feature = "..."` and `runtime = ...`. Any other arguments are passed on to `remove_async_await`, apart from the
+//! ones that change which items are emitted or how (`before`, `after`, `sibling`, `rename`, `name_span`, `trait_pair`, `deny_noop`, `vis` and `sync_body`), which are errors:
+//!
+//! ```rs
+//! #[remove_async_await::test(feature = "tokio", runtime = tokio::test(flavor = "multi_thread"), map(tokio::time::sleep = std::thread::sleep))]
+//! async fn sleep_test() {
+//!     tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+//! }
+//! ```
+//!
+//! ## Binaries
+//!
+//! `#[remove_async_await::main]` is the same, but for `main`. It expands to `#[tokio::main] async fn main` when the `async` feature is enabled and to a transformed `fn main` otherwise:
+//!
+//! ```rs
+//! #[remove_async_await::main]
+//! async fn main() {
+//!     println!("{}", get_value().await);
+//! }
+//! ```
+//!
+//! It takes the same arguments as `#[remove_async_await::test]`, like `runtime = async_std::main`.
+//!
+//! ## maybe-async compatibility
+//!
+//! With the `compat` feature, the attributes from [maybe-async](https://docs.rs/maybe-async) are available under the same names, so a crate can switch by renaming the dependency:
+//!
+//! ```toml
+//! [dependencies]
+//! maybe-async = { package = "remove-async-await", version = "1.0", features = ["compat"] }
+//! ```
+//!
+//! Like maybe-async, the `is_sync` feature decides which version is compiled:
+//!
+//! -   `#[maybe_async]` is the same as `#[remove_async_await]` with `is_sync`, and does nothing without it. maybe-async's `?Send` and `AFIT` arguments are ignored, and any other arguments are passed
+//!     on to `remove_async_await`
+//! -   `#[must_be_sync]` is always the same as `#[remove_async_await]`, and `#[must_be_async]` never changes the item
+//! -   `#[sync_impl]` items are only kept with `is_sync`, and `#[async_impl]` items are only kept without it
+//!
+//! Unlike maybe-async, async traits are left as is instead of getting `#[async_trait]`, so add it yourself if you need it.
+//!
 //! ## `remove_async_await_string`
 //!
 //! There are 2 macros this library provides:
 //!
-//! 1. `remove_async_await`: The one you should almost always use. Uses `syn` to parse rust code and remove async from functions and await from expressions. Currently, it can only take a function as an
-//!    input.
+//! 1. `remove_async_await`: The one you should almost always use. Uses `syn` to parse rust code and remove async from functions and await from expressions. Currently, it can take a function, trait method,
+//!    type alias (including associated types), struct, trait, impl, static or const as an input.
 //! 2. `remove_async_await_string`: You should only use this one if `remove_async_await` doesn't work for your use case. This is the "dumb macro"; it
-//! [literally just removes all occurrences of `async` and `.await` from the string representation of the input](https://github.com/naturecodevoid/remove-async-await/blob/main/src/lib.rs#L192). This
-//! means that while it might work with things other than functions, **you shouldn't use it because if a function or variable name contains "async" or ".await", your code will break.**
+//!    [just removes every `async` keyword and `.await` from the tokens of the input](https://github.com/naturecodevoid/remove-async-await/blob/main/src/tokens.rs), wherever they are (including
+//!    inside macros), and the tokens that are left keep their spans. Names like `async_client` and raw identifiers like `r#async` are left alone, and so are literals, doc comments and
+//!    `macro_rules!` definitions. This means that while it works with anything, **nothing else is rewritten**, so return types like `impl Future<Output = T>` and arguments like `map(...)` aren't
+//!    supported.
+//!
+//!    The one argument it takes is `keep(...)`, with paths that are left as they are along with the call or macro input right after them (and an `.await` on it), for code
+//!    that has to stay async:
+//!
+//!    ```rs
+//!    #[remove_async_await::remove_async_await_string(keep(futures::executor::block_on, spawn_async))]
+//!    async fn run() -> u32 {
+//!        // the async block and `.await` in it are kept
+//!        futures::executor::block_on(async { spawn_async().await }) + get().await
+//!    }
+//!    ```
+//!
+//! ## Debugging
+//!
+//! With the `debug` feature, the macro prints its input and output, and writes the output formatted with [prettyplease](https://docs.rs/prettyplease) to
+//! `target/remove-async-await/<item>.rs`, named after the item. This makes it easy to diff the blocking code against what you expect.
+//!
+//! ## Using the transform as a library
+//!
+//! The transform itself lives in [remove-async-await-core](https://docs.rs/remove-async-await-core), which isn't a proc-macro crate, so it can be used in code generators, build scripts and tests
+//! without going through macro expansion:
+//!
+//! ```rs
+//! let file: syn::File = syn::parse_str(source)?;
+//! let file = remove_async_await_core::remove_async_await(file);
+//! ```
+//!
+//! `RemoveAsyncAwait::with_args` takes the same arguments as the attribute, and since it implements `syn::fold::Fold` (from syn 2), it can transform single items and expressions too.
+//!
+//! For snapshot tests of what your items expand to, the `testing` feature of remove-async-await-core adds `testing::expand(args, item)`, which returns the expansion formatted
+//! with prettyplease, and `testing::format` for the output of the other `expand` functions like `expand_dual`:
+//!
+//! ```rs
+//! // with remove-async-await-core = { version = "...", features = ["testing"] } in [dev-dependencies]
+//! let output = remove_async_await_core::testing::expand(quote!(map(tokio::time::sleep = std::thread::sleep)), quote! {
+//!     pub async fn wait() {
+//!         tokio::time::sleep(DURATION).await;
+//!     }
+//! });
+//! assert_eq!(output, "pub fn wait() {\n    std::thread::sleep(DURATION);\n}\n");
+//! ```
+//!
+//! ### Build scripts
+//!
+//! `remove_async_await_core::build::mirror` transforms a whole module from a build script, for when putting the attribute on every item isn't practical. It reads the module (inlining any
+//! `mod name;` submodules), transforms it and writes it to `OUT_DIR`:
+//!
+//! ```rs
+//! // build.rs, with remove-async-await-core in [build-dependencies]
+//! fn main() {
+//!     remove_async_await_core::build::mirror("src/async_impl", "blocking").unwrap();
+//! }
+//!
+//! // src/lib.rs
+//! mod async_impl;
+//! mod blocking {
+//!     include!(concat!(env!("OUT_DIR"), "/blocking.rs"));
+//! }
+//! ```
 //!
 //! ## Known issues
 //!
 //! Here is a list of known issues/limitations that I probably won't fix (PRs are welcome!):
 //!
-//! -   **Issue**: `.await` is not removed when calling a macro
+//! -   **Issue**: `.await` is not removed when calling a macro, unless it is a standard library macro that takes expressions (such as `println!`, `format!`, `write!` and
+//!     `assert_eq!`) or one of `anyhow`'s `anyhow!`, `bail!` and `ensure!`
 //!
 //!     **Workarounds**:
 //!
@@ -71,16 +972,18 @@
 //!         ```rs
 //!         #[remove_async_await::remove_async_await)]
 //!         async fn issue() {
-//!             println!("{}", get_string().await); // `.await` will not be removed
+//!             my_macro!(get_string().await); // `.await` will not be removed
 //!         }
 //!
 //!         #[remove_async_await::remove_async_await)]
 //!         async fn workaround() {
 //!             let string = get_string().await; // `.await` **will** be removed
-//!             println!("{}", string);
+//!             my_macro!(string);
 //!         }
 //!         ```
 //!
+//!     -   Pass [`macro_tokens`](#awaits-in-macros), which removes `.await` from the tokens of every macro
+//!
 //!     -   Use [`remove_async_await_string`](#remove_async_await_string) (read docs for more info, such as potential bad side effects)
 //!
 //!         Example:
@@ -88,116 +991,204 @@
 //!         ```rs
 //!         #[remove_async_await::remove_async_await)]
 //!         async fn issue() {
-//!             println!("{}", get_string().await); // `.await` will not be removed
+//!             my_macro!(get_string().await); // `.await` will not be removed
 //!         }
 //!
 //!         #[remove_async_await::remove_async_await_string)]
 //!         async fn workaround() {
-//!             println!("{}", get_string().await); // `.await` **will** be removed
+//!             my_macro!(get_string().await); // `.await` **will** be removed
 //!         }
 //!         ```
 //!
 //! If you want me to add an issue to this list (or fix the issue), please [create a GitHub issue](https://github.com/naturecodevoid/remove-async-await/issues/new)!
 
+#[cfg(feature = "compat")]
+mod compat;
+mod maybe_await;
+mod spawn_or_call;
+mod tokens;
+#[cfg(all(feature = "wasm", not(feature = "full")))]
+mod wasm;
+
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
-use syn::{
-    fold::{self, Fold},
-    Expr, ExprBlock, ItemFn, TraitItemMethod,
-};
-
-struct RemoveAsyncAwait;
-
-impl Fold for RemoveAsyncAwait {
-    fn fold_item_fn(&mut self, mut i: ItemFn) -> ItemFn {
-        // remove async functions
-        i.sig.asyncness = None;
-        fold::fold_item_fn(self, i)
-    }
 
-    fn fold_trait_item_method(&mut self, mut i: TraitItemMethod) -> TraitItemMethod {
-        // remove async trait methods
-        i.sig.asyncness = None;
-        fold::fold_trait_item_method(self, i)
-    }
+/// Runs one of the core crate's `expand` functions, or the same function in the precompiled blob without `full`.
+#[cfg(feature = "full")]
+macro_rules! expand {
+    ($expand:ident, $args:expr, $input:expr) => {
+        TokenStream::from(remove_async_await_core::$expand(
+            $args.into(),
+            $input.into(),
+        ))
+    };
+}
 
-    fn fold_expr(&mut self, e: Expr) -> Expr {
-        match e {
-            // remove await
-            Expr::Await(e) => self.fold_expr(*e.base),
-            // remove async blocks
-            Expr::Async(e) => self.fold_expr(Expr::Block(ExprBlock {
-                attrs: e.attrs,
-                label: None,
-                block: e.block,
-            })),
-            _ => fold::fold_expr(self, e),
-        }
-    }
+#[cfg(all(feature = "wasm", not(feature = "full")))]
+macro_rules! expand {
+    ($expand:ident, $args:expr, $input:expr) => {
+        wasm::expand(stringify!($expand), $args, $input)
+    };
+}
+
+/// Walks the tokens instead, which only `remove_async_await` supports.
+#[cfg(not(any(feature = "full", feature = "wasm")))]
+macro_rules! expand {
+    (expand, $args:expr, $input:expr) => {
+        TokenStream::from(tokens::expand($args.into(), $input.into()))
+    };
 }
 
 #[proc_macro_attribute]
 /// Please see crate level documentation for usage and examples.
-pub fn remove_async_await(_args: TokenStream, input: TokenStream) -> TokenStream {
+pub fn remove_async_await(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand!(expand, args, input)
+}
+
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `remove_async_await_string` section)
+pub fn remove_async_await_string(args: TokenStream, input: TokenStream) -> TokenStream {
     #[cfg(feature = "debug")]
     {
         println!();
-        println!("Input: {}", input.to_string());
+        println!("Input: {}", input);
     }
 
-    macro_rules! to_token_stream {
-        ($input: expr) => {{
-            #[cfg(feature = "debug")]
-            {
-                println!();
-                println!("Parsed input: {:#?}", input);
-                println!();
-            }
-            TokenStream::from($input.to_token_stream())
-        }};
-    }
-
-    // Attempt to parse as ItemFn, then TraitItemMethod, and finally fail
-    let output = match syn::parse::<ItemFn>(input.clone()) {
-        Ok(item) => to_token_stream!(RemoveAsyncAwait.fold_item_fn(item)),
-        Err(_) => match syn::parse::<TraitItemMethod>(input.clone()) {
-            Ok(item) => to_token_stream!(RemoveAsyncAwait.fold_trait_item_method(item)),
-            Err(_) => TokenStream::from(quote! {
-                compile_error!("remove_async_await currently only supports functions and trait methods. if you are using it on a supported type, parsing probably failed; please ensure the input is valid Rust.")
-            }),
-        },
-    };
+    let output = TokenStream::from(tokens::expand_string(args.into(), input.into()));
 
     #[cfg(feature = "debug")]
     {
         println!();
-        println!("Output: {}", output.to_string());
+        println!("Output: {}", output);
         println!();
     }
 
     output
 }
 
+#[cfg(any(feature = "full", feature = "wasm"))]
 #[proc_macro_attribute]
-/// Please see crate level documentation for usage and examples. (Specifically the `remove_async_await_string` section)
-pub fn remove_async_await_string(_args: TokenStream, input: TokenStream) -> TokenStream {
-    #[cfg(feature = "debug")]
-    {
-        println!();
-        println!("Input: {}", input.to_string());
+/// Please see crate level documentation for usage and examples. (Specifically the `Tests` section)
+pub fn test(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand!(expand_test, args, input)
+}
+
+#[cfg(any(feature = "full", feature = "wasm"))]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `Binaries` section)
+pub fn main(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand!(expand_main, args, input)
+}
+
+#[cfg(feature = "compat")]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `maybe-async compatibility` section)
+pub fn maybe_async(args: TokenStream, input: TokenStream) -> TokenStream {
+    if compat::IS_SYNC {
+        must_be_sync(args, input)
+    } else {
+        input
     }
+}
 
-    let input = input.to_string();
+#[cfg(feature = "compat")]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `maybe-async compatibility` section)
+pub fn must_be_sync(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = compat::remove_async_await_args(args.into());
+    remove_async_await(args.into(), input)
+}
 
-    let output = input.replace("async", "").replace(".await", "");
-    let output: TokenStream = output.parse().unwrap();
+#[cfg(feature = "compat")]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `maybe-async compatibility` section)
+pub fn must_be_async(_args: TokenStream, input: TokenStream) -> TokenStream {
+    input
+}
 
-    #[cfg(feature = "debug")]
-    {
-        println!();
-        println!("Output: {}", output.to_string());
-        println!();
+#[cfg(feature = "compat")]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `maybe-async compatibility` section)
+pub fn sync_impl(_args: TokenStream, input: TokenStream) -> TokenStream {
+    if compat::IS_SYNC {
+        input
+    } else {
+        TokenStream::new()
     }
+}
 
-    output
+#[cfg(feature = "compat")]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `maybe-async compatibility` section)
+pub fn async_impl(_args: TokenStream, input: TokenStream) -> TokenStream {
+    if compat::IS_SYNC {
+        TokenStream::new()
+    } else {
+        input
+    }
+}
+
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `Build-specific code` section)
+pub fn replace_with(_args: TokenStream, input: TokenStream) -> TokenStream {
+    // only needed for the async build, where the item isn't replaced
+    input
+}
+
+#[proc_macro]
+/// Please see crate level documentation for usage and examples. (Specifically the `Build-specific code` section)
+pub fn async_only(input: TokenStream) -> TokenStream {
+    // only expanded in the async build, since the blocking build drops it
+    input
+}
+
+#[proc_macro]
+/// Please see crate level documentation for usage and examples. (Specifically the `Build-specific code` section)
+pub fn sync_only(_input: TokenStream) -> TokenStream {
+    // only expanded in the async build, since the blocking build inlines it
+    TokenStream::new()
+}
+
+#[proc_macro]
+/// Please see crate level documentation for usage and examples. (Specifically the `Build-specific code` section)
+pub fn maybe_await(input: TokenStream) -> TokenStream {
+    maybe_await::expand(input.into()).into()
+}
+
+#[proc_macro]
+/// Please see crate level documentation for usage and examples. (Specifically the `Build-specific code` section)
+pub fn spawn_or_call(input: TokenStream) -> TokenStream {
+    spawn_or_call::expand(input.into()).into()
+}
+
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `Keeping code as is` section)
+pub fn keep(args: TokenStream, input: TokenStream) -> TokenStream {
+    if let Some(err) = no_args("keep", args) {
+        return err;
+    }
+    // only needed for the async build, where the item isn't transformed
+    input
+}
+
+#[cfg(any(feature = "full", feature = "wasm"))]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `Mirrored modules` section)
+pub fn mirror(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand!(expand_mirror, args, input)
+}
+
+#[cfg(any(feature = "full", feature = "wasm"))]
+#[proc_macro_attribute]
+/// Please see crate level documentation for usage and examples. (Specifically the `Feature gating` section)
+pub fn dual(args: TokenStream, input: TokenStream) -> TokenStream {
+    expand!(expand_dual, args, input)
+}
+
+/// The error for arguments passed to an attribute that doesn't take any.
+fn no_args(name: &str, args: TokenStream) -> Option<TokenStream> {
+    let span = args.into_iter().next()?.span();
+    let message = format!("`{}` doesn't take arguments", name);
+    Some(TokenStream::from(quote::quote_spanned! {span.into()=>
+        compile_error!(#message);
+    }))
 }
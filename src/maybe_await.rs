@@ -0,0 +1,35 @@
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+
+/// Expands `maybe_await!(expr)` or `maybe_await!(expr, feature = "...")`: the expression is awaited when the feature
+/// (`async` by default) is enabled in the calling crate, and left as is when it isn't. The transform replaces it with
+/// `expr` in the code it makes blocking, whatever the features are.
+pub(crate) fn expand(input: TokenStream) -> TokenStream {
+    let mut tokens: Vec<_> = input.into_iter().collect();
+    let feature = match tokens.as_slice() {
+        [.., TokenTree::Punct(comma), TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(feature)]
+            if comma.as_char() == ',' && key == "feature" && eq.as_char() == '=' =>
+        {
+            let feature = feature.clone();
+            tokens.truncate(tokens.len() - 4);
+            quote!(#feature)
+        }
+        _ => quote!("async"),
+    };
+    if tokens.is_empty() {
+        return quote! {
+            compile_error!("`maybe_await!` takes the expression to await, like `maybe_await!(get())`");
+        };
+    }
+    let expr: TokenStream = tokens.into_iter().collect();
+    let value = Ident::new("value", Span::mixed_site());
+    quote_spanned! {Span::mixed_site()=>
+        {
+            #[cfg(feature = #feature)]
+            let #value = (#expr).await;
+            #[cfg(not(feature = #feature))]
+            let #value = #expr;
+            #value
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+
+/// Expands `spawn_or_call!(options, { ... })`: the block is spawned with `tokio::spawn(async move { ... })` when the
+/// feature (`async` by default) is enabled in the calling crate, and otherwise run right away, or on a thread with
+/// `thread`. The transform does the same as the blocking build in the code it makes blocking.
+pub(crate) fn expand(input: TokenStream) -> TokenStream {
+    let mut tokens: Vec<_> = input.into_iter().collect();
+    let block = match tokens.pop() {
+        Some(TokenTree::Group(block)) if block.delimiter() == Delimiter::Brace => block,
+        _ => {
+            return error(
+                Span::call_site(),
+                "`spawn_or_call!` takes a block last, like `spawn_or_call!(thread, { ... })`",
+            )
+        }
+    };
+    let mut thread = false;
+    let mut feature = quote!("async");
+    let options: TokenStream = tokens.into_iter().collect();
+    for option in split_commas(options) {
+        match option.as_slice() {
+            [TokenTree::Ident(mode)] if mode == "call" => thread = false,
+            [TokenTree::Ident(mode)] if mode == "thread" => thread = true,
+            [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(value)]
+                if key == "feature" && eq.as_char() == '=' =>
+            {
+                feature = quote!(#value);
+            }
+            [] => {}
+            [first, ..] => {
+                return error(
+                    first.span(),
+                    "expected `call`, `thread` or `feature = \"...\"`",
+                )
+            }
+        }
+    }
+    let blocking = if thread {
+        quote!(::std::thread::spawn(move || #block))
+    } else {
+        remove_async_await_core::tokens::in_closure(quote!(move), block.span(), quote!(#block))
+    };
+    let task = Ident::new("task", Span::mixed_site());
+    quote_spanned! {Span::mixed_site()=>
+        {
+            #[cfg(feature = #feature)]
+            let #task = ::tokio::spawn(async move #block);
+            #[cfg(not(feature = #feature))]
+            let #task = #blocking;
+            #task
+        }
+    }
+}
+
+/// Splits the options on commas, leaving out the commas.
+fn split_commas(tokens: TokenStream) -> Vec<Vec<TokenTree>> {
+    let mut options = vec![Vec::new()];
+    for token in tokens {
+        match token {
+            TokenTree::Punct(comma) if comma.as_char() == ',' => options.push(Vec::new()),
+            token => options.last_mut().unwrap().push(token),
+        }
+    }
+    options
+}
+
+fn error(span: Span, message: &str) -> TokenStream {
+    quote_spanned! {span=>
+        compile_error!(#message);
+    }
+}
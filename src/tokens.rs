@@ -0,0 +1,99 @@
+use proc_macro2::{token_stream, Delimiter, Span, TokenStream, TokenTree};
+use std::iter::Peekable;
+
+/// The paths of `keep(...)`, by segment.
+pub(crate) type Kept = Vec<Vec<String>>;
+
+/// Expands `#[remove_async_await]` without the `full` feature, by walking the tokens instead of parsing them: `async`
+/// is removed (along with `move` before a block, so `async move { ... }` becomes a block) and so is `.await`. Nothing
+/// else is rewritten, so arguments aren't supported.
+#[cfg(not(any(feature = "full", feature = "wasm")))]
+pub(crate) fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    if let Some(arg) = args.into_iter().next() {
+        return error(
+            arg.span(),
+            "arguments for `remove_async_await` need the `full` feature",
+        );
+    }
+    remove_async_await_core::tokens::remove(input, &[])
+}
+
+/// Expands `#[remove_async_await_string]`, whose only argument is `keep(...)`: the paths in it are left as they are,
+/// along with the call or macro input right after them and an `.await` on it.
+pub(crate) fn expand_string(args: TokenStream, input: TokenStream) -> TokenStream {
+    match parse_keep(args) {
+        Ok(kept) => remove_async_await_core::tokens::remove(input, &kept),
+        Err(err) => err,
+    }
+}
+
+fn parse_keep(args: TokenStream) -> Result<Kept, TokenStream> {
+    let mut args = args.into_iter();
+    let group = match (args.next(), args.next()) {
+        (None, _) => return Ok(Kept::new()),
+        (Some(TokenTree::Ident(keep)), Some(TokenTree::Group(group)))
+            if keep == "keep" && group.delimiter() == Delimiter::Parenthesis =>
+        {
+            group
+        }
+        (Some(TokenTree::Ident(keep)), _) if keep == "keep" => {
+            return Err(error(
+                keep.span(),
+                "expected the paths to keep, like `keep(async_runtime)`",
+            ))
+        }
+        (Some(arg), _) => {
+            return Err(error(
+                arg.span(),
+                "unknown argument for `remove_async_await_string`, expected `keep(...)`",
+            ))
+        }
+    };
+    if let Some(arg) = args.next() {
+        return Err(error(arg.span(), "unexpected argument after `keep(...)`"));
+    }
+
+    let mut kept = Kept::new();
+    let mut tokens = group.stream().into_iter().peekable();
+    while tokens.peek().is_some() {
+        kept.push(parse_path(&mut tokens)?);
+        match tokens.next() {
+            Some(TokenTree::Punct(comma)) if comma.as_char() == ',' => {}
+            Some(token) => return Err(error(token.span(), "expected `,` between the paths")),
+            None => break,
+        }
+    }
+    Ok(kept)
+}
+
+/// Parses a path like `tokio::spawn` up to the next `,`.
+fn parse_path(tokens: &mut Peekable<token_stream::IntoIter>) -> Result<Vec<String>, TokenStream> {
+    let mut path = Vec::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) => path.push(ident.to_string()),
+            token => {
+                let span = token.map_or_else(Span::call_site, |token| token.span());
+                return Err(error(
+                    span,
+                    "expected a path, like `async_runtime` or `tokio::spawn`",
+                ));
+            }
+        }
+        let mut ahead = tokens.clone();
+        match (ahead.next(), ahead.next()) {
+            (Some(TokenTree::Punct(first)), Some(TokenTree::Punct(second)))
+                if first.as_char() == ':' && second.as_char() == ':' =>
+            {
+                *tokens = ahead;
+            }
+            _ => return Ok(path),
+        }
+    }
+}
+
+fn error(span: Span, message: &str) -> TokenStream {
+    quote::quote_spanned! {span=>
+        compile_error!(#message);
+    }
+}
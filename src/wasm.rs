@@ -0,0 +1,10 @@
+use proc_macro::TokenStream;
+use watt::WasmMacro;
+
+/// The transform compiled from the `wasm` crate, see the `Precompiled mode` section of the docs.
+static MACRO: WasmMacro = WasmMacro::new(include_bytes!("remove_async_await.wasm"));
+
+/// Runs one of the core crate's `expand` functions in the blob.
+pub(crate) fn expand(name: &str, args: TokenStream, input: TokenStream) -> TokenStream {
+    MACRO.proc_macro_attribute(name, args, input)
+}
@@ -0,0 +1,50 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+#[allow(async_fn_in_trait)]
+trait Fetch {
+    async fn get(&self) -> u32;
+
+    fn fetch(&self) -> impl std::future::Future<Output = u32> + Send;
+
+    fn name<'a>(&'a self, names: &'a [&'a str]) -> impl std::future::Future<Output = &'a str> + 'a;
+
+    fn log(&self) -> impl std::future::Future<Output = ()> + Send + '_ {
+        async move {
+            let _ = self.get().await;
+        }
+    }
+
+    async fn total(&self) -> u32 {
+        self.get().await + self.fetch().await
+    }
+}
+
+struct Value(u32);
+
+#[remove_async_await::remove_async_await]
+impl Fetch for Value {
+    async fn get(&self) -> u32 {
+        self.0
+    }
+
+    fn fetch(&self) -> impl std::future::Future<Output = u32> + Send {
+        let value = self.0;
+        async move { value * 10 }
+    }
+
+    async fn name<'a>(&'a self, names: &'a [&'a str]) -> &'a str {
+        names[self.0 as usize]
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn afit() {
+    let value = Value(1);
+    let total = value.total().await;
+    assert_eq!(total, 11);
+    let name = value.name(&["a", "b"]).await;
+    assert_eq!(name, "b");
+    value.log().await;
+}
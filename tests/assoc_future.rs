@@ -0,0 +1,53 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+trait Service {
+    type Fut: std::future::Future<Output = u32>;
+    type Done: std::future::Future<Output = ()> + Send;
+
+    fn call(&self, n: u32) -> Self::Fut;
+    fn done(&self) -> Self::Done;
+}
+
+struct Double;
+
+#[remove_async_await::remove_async_await]
+impl Service for Double {
+    type Fut = std::future::Ready<u32>;
+    type Done = std::future::Ready<()>;
+
+    fn call(&self, n: u32) -> Self::Fut {
+        std::future::ready(n * 2)
+    }
+
+    fn done(&self) -> Self::Done {
+        std::future::ready(())
+    }
+}
+
+struct Increment;
+
+#[remove_async_await::remove_async_await]
+impl Service for Increment {
+    type Fut = std::pin::Pin<Box<dyn std::future::Future<Output = u32>>>;
+    type Done = futures::future::BoxFuture<'static, ()>;
+
+    fn call(&self, n: u32) -> Self::Fut {
+        Box::pin(async move { n + 1 })
+    }
+
+    fn done(&self) -> Self::Done {
+        async {}.boxed()
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn assoc_future() {
+    let doubled = Double.call(2).await;
+    assert_eq!(doubled, 4);
+    let incremented = Increment.call(2).await;
+    assert_eq!(incremented, 3);
+    Double.done().await;
+    Increment.done().await;
+}
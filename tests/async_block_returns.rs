@@ -0,0 +1,78 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::num::ParseIntError;
+
+#[remove_async_await::remove_async_await]
+async fn next_even(values: &[u32]) -> Option<u32> {
+    // `return` leaves the block, not the function
+    let found = async {
+        for value in values {
+            if value % 2 == 0 {
+                return Some(*value);
+            }
+        }
+        None
+    }
+    .await;
+    Some(found.unwrap_or(0) + 1)
+}
+
+#[remove_async_await::remove_async_await]
+async fn double_or_zero(input: &str) -> u32 {
+    let doubled: Result<u32, ParseIntError> = async move {
+        let value: u32 = input.parse()?;
+        Ok(value * 2)
+    }
+    .await;
+    doubled.unwrap_or(0)
+}
+
+// the block is what the function returns, so it's flattened without a closure
+#[remove_async_await::remove_async_await]
+fn parse(input: &str) -> impl std::future::Future<Output = Result<u32, ParseIntError>> + '_ {
+    async move {
+        let value = input.parse()?;
+        Ok(value)
+    }
+}
+
+#[remove_async_await::remove_async_await]
+async fn sum(inputs: &[&str]) -> Result<u32, ParseIntError> {
+    // so is the block a closure returns
+    let parse = |input: &str| async move {
+        let value: u32 = input.parse()?;
+        Ok::<_, ParseIntError>(value)
+    };
+    let mut sum = 0;
+    for input in inputs {
+        sum += parse(input).await?;
+    }
+    Ok(sum)
+}
+
+fn call(value: u32) -> Option<u32> {
+    value.checked_sub(1)
+}
+
+#[remove_async_await::remove_async_await]
+async fn decremented(value: u32) -> u32 {
+    // `call` above isn't shadowed by the function the block is run with
+    async move {
+        let value = call(value)?;
+        Some(value)
+    }
+    .await
+    .unwrap_or(0)
+}
+
+#[test]
+fn async_block_returns() {
+    assert_eq!(next_even(&[1, 4, 6]), Some(5));
+    assert_eq!(next_even(&[1]), Some(1));
+    assert_eq!(double_or_zero("21"), 42);
+    assert_eq!(double_or_zero("a"), 0);
+    assert_eq!(parse("7"), Ok(7));
+    assert_eq!(sum(&["1", "2"]), Ok(3));
+    assert_eq!(decremented(2), 1);
+    assert_eq!(decremented(0), 0);
+}
@@ -0,0 +1,38 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn retry<F: AsyncFnMut(u32) -> Result<u32, ()>>(mut f: F) -> Result<u32, ()> {
+    let mut attempt = 0;
+    loop {
+        match f(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(()) if attempt < 3 => attempt += 1,
+            Err(()) => return Err(()),
+        }
+    }
+}
+
+#[remove_async_await::remove_async_await]
+async fn apply(f: impl AsyncFn(u32) -> u32, value: u32) -> u32 {
+    f(value).await
+}
+
+#[remove_async_await::remove_async_await]
+async fn once<F>(f: F) -> String
+where
+    F: AsyncFnOnce() -> String,
+{
+    f().await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn async_fn_bounds() {
+    let value = retry(async |attempt| if attempt == 2 { Ok(attempt) } else { Err(()) }).await;
+    assert_eq!(value, Ok(2));
+    let doubled = apply(async |n| n * 2, 21).await;
+    assert_eq!(doubled, 42);
+    let message = "hello".to_owned();
+    let message = once(async move || message).await;
+    assert_eq!(message, "hello");
+}
@@ -0,0 +1,35 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+#[async_recursion::async_recursion]
+async fn fib(n: u32) -> u32 {
+    if n < 2 {
+        n
+    } else {
+        fib(n - 1).await + fib(n - 2).await
+    }
+}
+
+struct Tree(Vec<Tree>);
+
+impl Tree {
+    #[remove_async_await::remove_async_await]
+    #[async_recursion::async_recursion(?Send)]
+    async fn count(&self) -> usize {
+        let mut count = 1;
+        for child in &self.0 {
+            count += child.count().await;
+        }
+        count
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn async_recursion() {
+    let fib = fib(10).await;
+    assert_eq!(fib, 55);
+    let tree = Tree(vec![Tree(vec![]), Tree(vec![Tree(vec![])])]);
+    let count = tree.count().await;
+    assert_eq!(count, 4);
+}
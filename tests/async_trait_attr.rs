@@ -0,0 +1,24 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+#[async_trait::async_trait]
+trait Greet {
+    async fn greet(&self) -> String;
+}
+
+struct English;
+
+#[remove_async_await::remove_async_await]
+#[async_trait::async_trait(?Send)]
+impl Greet for English {
+    async fn greet(&self) -> String {
+        "hello".to_owned()
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn async_trait_attr() {
+    let greeting = English.greet().await;
+    assert_eq!(greeting, "hello");
+}
@@ -0,0 +1,45 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+trait Store {
+    fn get<'life0, 'life1, 'async_trait>(
+        &'life0 self,
+        key: &'life1 str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Option<u32>> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        'life1: 'async_trait,
+        Self: 'async_trait;
+}
+
+struct Single(&'static str, u32);
+
+#[remove_async_await::remove_async_await]
+impl Store for Single {
+    fn get<'life0, 'life1, 'async_trait>(
+        &'life0 self,
+        key: &'life1 str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Option<u32>> + Send + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        'life1: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move {
+            let __self = self;
+            let __ret: Option<u32> = { (__self.0 == key).then_some(__self.1) };
+            #[allow(unreachable_code)]
+            __ret
+        })
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn async_trait_style() {
+    let store = Single("a", 1);
+    let found = store.get("a").await;
+    assert_eq!(found, Some(1));
+    let missing = store.get("b").await;
+    assert_eq!(missing, None);
+}
@@ -0,0 +1,32 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn get() -> u32 {
+    1
+}
+
+// `#[remove_async_await::test]` needs the async item, so it has to expand first
+#[remove_async_await::remove_async_await(before(remove_async_await::test))]
+#[remove_async_await::test]
+async fn before_test() {
+    let value = get().await;
+    assert_eq!(value, 1);
+}
+
+// the transform runs again through the path it was called with
+use remove_async_await as raa;
+
+#[raa::remove_async_await(before(raa::test))]
+#[raa::test]
+async fn before_renamed() {
+    let value = get().await;
+    assert_eq!(value, 1);
+}
+
+#[remove_async_await::remove_async_await(after(test))]
+#[tokio::test]
+#[should_panic(expected = "1")]
+async fn after_test() {
+    let value = get().await;
+    panic!("{}", value);
+}
@@ -0,0 +1,35 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+#[mockall::automock]
+trait Store {
+    async fn get(&self, key: u32) -> u32;
+}
+
+// mockall needs `automock` to come before `async_trait` for the async trait
+#[remove_async_await::remove_async_await]
+#[cfg_attr(test, mockall::automock)]
+#[async_trait::async_trait]
+trait Cache {
+    async fn get(&self, key: u32) -> Option<u32>;
+}
+
+#[remove_async_await::remove_async_await]
+async fn get_cached(cache: &impl Cache, store: &impl Store, key: u32) -> u32 {
+    match cache.get(key).await {
+        Some(value) => value,
+        None => store.get(key).await,
+    }
+}
+
+// expectations are set up the same way in both builds
+#[remove_async_await::remove_async_await]
+#[tokio::test]
+async fn automock() {
+    let mut cache = MockCache::new();
+    cache.expect_get().returning(|key| (key == 1).then_some(10));
+    let mut store = MockStore::new();
+    store.expect_get().returning(|key| key * 2);
+    assert_eq!(get_cached(&cache, &store, 1).await, 10);
+    assert_eq!(get_cached(&cache, &store, 2).await, 4);
+}
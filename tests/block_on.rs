@@ -0,0 +1,53 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+async fn get(value: u32) -> u32 {
+    value
+}
+
+// `join!` can't be rewritten, but the body doesn't have to be
+#[remove_async_await::remove_async_await(block_on)]
+pub async fn get_both() -> u32 {
+    let (a, b) = futures::join!(get(1), get(2));
+    a + b
+}
+
+#[remove_async_await::remove_async_await(block_on = futures::executor::block_on)]
+pub fn get_boxed<'a>(value: &'a u32) -> futures::future::BoxFuture<'a, u32> {
+    Box::pin(async move { get(*value).await })
+}
+
+pub struct Client {
+    base: u32,
+}
+
+#[remove_async_await::remove_async_await(block_on = self::executor().run)]
+impl Client {
+    pub async fn get(&self, value: u32) -> u32 {
+        self.base + get(value).await
+    }
+
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+}
+
+pub struct Executor;
+
+impl Executor {
+    pub fn run<F: std::future::Future>(&self, future: F) -> F::Output {
+        futures::executor::block_on(future)
+    }
+}
+
+pub fn executor() -> Executor {
+    Executor
+}
+
+#[test]
+fn block_on() {
+    assert_eq!(get_both(), 3);
+    assert_eq!(get_boxed(&4), 4);
+    let client = Client { base: 10 };
+    assert_eq!(client.get(5), 15);
+    assert_eq!(client.base(), 10);
+}
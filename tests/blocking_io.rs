@@ -0,0 +1,25 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use remove_async_await_shim::BlockingIo;
+
+#[remove_async_await::remove_async_await]
+async fn copy<R, W>(mut reader: R, writer: &mut W) -> std::io::Result<Vec<u8>>
+where
+    R: futures::io::AsyncRead + Unpin,
+    W: futures::io::AsyncWrite + futures::io::AsyncSeek + Unpin,
+{
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await?;
+    writer.seek(std::io::SeekFrom::Start(1)).await?;
+    writer.write_all(&buf).await?;
+    writer.flush().await?;
+    Ok(buf)
+}
+
+#[test]
+fn blocking_io() {
+    let reader = BlockingIo::new(futures::io::Cursor::new(b"hello"));
+    let mut writer = BlockingIo::new(futures::io::Cursor::new(vec![0; 2]));
+    assert_eq!(copy(reader, &mut writer).unwrap(), b"hello");
+    assert_eq!(writer.into_inner().into_inner(), b"\0hello");
+}
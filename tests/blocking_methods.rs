@@ -0,0 +1,72 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::cell::RefCell;
+
+// stand-ins for a zbus-style proxy and flume's channels, with only their blocking methods
+struct Proxy;
+
+impl Proxy {
+    fn call_blocking(&self, method: &str) -> String {
+        format!("called {}", method)
+    }
+
+    fn blocking_ping(&self) -> bool {
+        true
+    }
+}
+
+mod flume {
+    pub struct Receiver<T>(pub std::cell::RefCell<Vec<T>>);
+
+    impl<T> Receiver<T> {
+        pub fn recv(&self) -> Option<T> {
+            self.0.borrow_mut().pop()
+        }
+    }
+}
+
+struct Cache(RefCell<u32>);
+
+impl Cache {
+    fn get(&self) -> u32 {
+        *self.0.borrow()
+    }
+}
+
+struct Client {
+    proxy: Proxy,
+    cache: Cache,
+}
+
+#[remove_async_await::remove_async_await(blocking_methods(suffix = "_blocking", on(self.proxy)))]
+impl Client {
+    async fn call(&self) -> (String, u32) {
+        let called = self.proxy.call("Introspect").await;
+        // other receivers keep their methods
+        let cached = self.cache.get().await;
+        (called, cached)
+    }
+}
+
+#[remove_async_await::remove_async_await(blocking_methods(prefix = "blocking_"))]
+async fn ping(proxy: &Proxy) -> bool {
+    proxy.ping().await
+}
+
+#[remove_async_await::remove_async_await(blocking_methods(strip = "_async", on(flume::Receiver)))]
+async fn next(rx: &flume::Receiver<u32>) -> Option<u32> {
+    rx.recv_async().await
+}
+
+#[test]
+fn blocking_methods() {
+    let client = Client {
+        proxy: Proxy,
+        cache: Cache(RefCell::new(2)),
+    };
+    assert_eq!(client.call(), ("called Introspect".to_owned(), 2));
+    assert!(ping(&client.proxy));
+    let rx = flume::Receiver(RefCell::new(vec![1]));
+    assert_eq!(next(&rx), Some(1));
+    assert_eq!(next(&rx), None);
+}
@@ -0,0 +1,33 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use remove_async_await_shim::{BlockingStream, IteratorExt};
+
+#[remove_async_await::remove_async_await]
+trait Source {
+    type Items: futures::Stream<Item = u32>;
+
+    fn items(&self) -> Self::Items;
+}
+
+struct Numbers;
+
+// the type the async build names is a stream, and the blocking build names a `BlockingStream` in its place
+impl Source for Numbers {
+    type Items = BlockingStream<std::vec::IntoIter<u32>>;
+
+    fn items(&self) -> Self::Items {
+        vec![1, 2, 3].into_iter().into_stream()
+    }
+}
+
+#[remove_async_await::remove_async_await]
+async fn doubled<S: Source>(source: &S) -> Vec<u32> {
+    source.items().map(|n| n * 2).collect().await
+}
+
+#[test]
+fn blocking_stream() {
+    assert_eq!(doubled(&Numbers), vec![2, 4, 6]);
+    let stream = BlockingStream::new(vec![1, 2]);
+    assert_eq!(stream.rev().collect::<Vec<_>>(), vec![2, 1]);
+}
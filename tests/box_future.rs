@@ -0,0 +1,99 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+// `BoxFuture` is rewritten away, so `futures` doesn't need to be a dependency
+
+trait Greeter {
+    #[remove_async_await::remove_async_await]
+    fn greet<'a>(&'a self, name: &'a str) -> futures::future::BoxFuture<'a, String>;
+}
+
+struct English;
+
+impl Greeter for English {
+    #[remove_async_await::remove_async_await]
+    fn greet<'a>(&'a self, name: &'a str) -> futures::future::BoxFuture<'a, String> {
+        async move { format!("hello {}", name) }.boxed()
+    }
+}
+
+#[remove_async_await::remove_async_await]
+fn greet_twice<'a>(
+    greeter: &'a dyn Greeter,
+    name: &'a str,
+) -> futures::future::BoxFuture<'a, String> {
+    let future = async move {
+        let first = greeter.greet(name).await;
+        let second = greeter.greet(name).await;
+        format!("{}, {}", first, second)
+    };
+    Box::pin(future)
+}
+
+pub struct Node {
+    value: u32,
+    _pinned: std::marker::PhantomPinned,
+}
+
+pub struct Builder(u32);
+
+impl Builder {
+    pub fn new(value: u32) -> Self {
+        Builder(value)
+    }
+
+    pub fn boxed(self) -> Box<u32> {
+        Box::new(self.0)
+    }
+}
+
+// values that aren't futures keep their box
+#[remove_async_await::remove_async_await]
+async fn pinned(value: u32) -> (std::pin::Pin<Box<Node>>, Box<u32>) {
+    let node = Node {
+        value,
+        _pinned: std::marker::PhantomPinned,
+    };
+    (Box::pin(node), Builder::new(value).boxed())
+}
+
+#[remove_async_await::remove_async_await]
+async fn doubled(value: u32) -> u32 {
+    let future = async move { value * 2 };
+    Box::pin(future).await
+}
+
+fn make_buf() -> [u8; 4] {
+    [1, 2, 3, 4]
+}
+
+// `make_buf` isn't known to return a future, so its value keeps its box
+#[remove_async_await::remove_async_await]
+async fn buffer() -> usize {
+    let buf: std::pin::Pin<Box<[u8; 4]>> = Box::pin(make_buf());
+    buf.len()
+}
+
+// but calls to the function itself are futures
+#[remove_async_await::remove_async_await]
+async fn sum_to(n: u32) -> u32 {
+    if n == 0 {
+        return 0;
+    }
+    let rest: std::pin::Pin<Box<dyn std::future::Future<Output = u32>>> = Box::pin(sum_to(n - 1));
+    n + rest.await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn box_future() {
+    assert_eq!(English.greet("world").await, "hello world");
+    assert_eq!(
+        greet_twice(&English, "world").await,
+        "hello world, hello world"
+    );
+    let (node, built) = pinned(3).await;
+    assert_eq!((node.value, *built), (3, 3));
+    assert_eq!(doubled(2).await, 4);
+    assert_eq!(buffer().await, 4);
+    assert_eq!(sum_to(3).await, 6);
+}
@@ -0,0 +1,51 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+struct Lines<'a> {
+    lines: futures::stream::BoxStream<'a, &'a str>,
+}
+
+#[remove_async_await::remove_async_await]
+fn lines(text: &str) -> Lines<'_> {
+    Lines {
+        lines: futures::stream::iter(text.lines()).boxed(),
+    }
+}
+
+#[remove_async_await::remove_async_await]
+fn numbers<'a>(numbers: &'a [u32]) -> futures::stream::BoxStream<'a, u32> {
+    futures::stream::iter(numbers).map(|n| n * 2).boxed()
+}
+
+#[remove_async_await::remove_async_await]
+fn evens() -> std::pin::Pin<Box<dyn futures::Stream<Item = u32> + Send>> {
+    Box::pin(futures::stream::iter(vec![2, 4]))
+}
+
+trait Source {
+    #[remove_async_await::remove_async_await]
+    fn items(&self) -> futures::stream::LocalBoxStream<'_, u32>;
+}
+
+struct Repeat(u32);
+
+impl Source for Repeat {
+    #[remove_async_await::remove_async_await]
+    fn items(&self) -> futures::stream::LocalBoxStream<'_, u32> {
+        futures::stream::repeat(self.0).take(2).boxed_local()
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn box_stream() {
+    let lines = lines("a\nb").lines.collect::<Vec<_>>().await;
+    assert_eq!(lines, vec!["a", "b"]);
+    let numbers = numbers(&[1, 2]).collect::<Vec<_>>().await;
+    assert_eq!(numbers, vec![2, 4]);
+    let evens = evens().collect::<Vec<_>>().await;
+    assert_eq!(evens, vec![2, 4]);
+    let source: &dyn Source = &Repeat(7);
+    let items = source.items().collect::<Vec<_>>().await;
+    assert_eq!(items, vec![7, 7]);
+}
@@ -0,0 +1,43 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+type Handler = fn(u32) -> futures::future::BoxFuture<'static, u32>;
+
+#[remove_async_await::remove_async_await]
+fn double(n: u32) -> futures::future::BoxFuture<'static, u32> {
+    Box::pin(async move { n * 2 })
+}
+
+#[remove_async_await::remove_async_await]
+fn log(_: u32) -> futures::future::BoxFuture<'static, ()> {
+    async {}.boxed()
+}
+
+#[remove_async_await::remove_async_await]
+static ROUTES: &[(&str, Handler)] = &[("double", double)];
+
+#[remove_async_await::remove_async_await]
+const LOGGER: fn(u32) -> futures::future::BoxFuture<'static, ()> = log;
+
+#[remove_async_await::remove_async_await]
+fn callbacks() -> Vec<Box<dyn Fn(u32) -> std::pin::Pin<Box<dyn std::future::Future<Output = u32>>>>>
+{
+    vec![
+        Box::new(|n| Box::pin(async move { n + 1 })),
+        Box::new(|n| Box::pin(async move { double(n * 3).await })),
+    ]
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn callbacks_test() {
+    let handler: Handler = ROUTES[0].1;
+    let doubled = handler(2).await;
+    assert_eq!(doubled, 4);
+    LOGGER(1).await;
+    let mut results = Vec::new();
+    for callback in callbacks() {
+        results.push(callback(1).await);
+    }
+    assert_eq!(results, vec![2, 6]);
+}
@@ -0,0 +1,85 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+// stand-in for sqlx's query builder, which the blocking code still uses to build queries
+mod sqlx {
+    pub struct Query {
+        pub sql: &'static str,
+        pub args: Vec<u32>,
+    }
+
+    pub fn query(sql: &'static str) -> Query {
+        Query {
+            sql,
+            args: Vec::new(),
+        }
+    }
+
+    impl Query {
+        pub fn bind(mut self, arg: u32) -> Self {
+            self.args.push(arg);
+            self
+        }
+    }
+}
+
+macro_rules! query {
+    ($sql:expr) => {
+        crate::sqlx::query($sql)
+    };
+}
+
+struct Pool;
+
+mod db {
+    use super::{sqlx::Query, Pool};
+
+    pub fn fetch_one(query: Query, _: &Pool) -> Result<String, ()> {
+        Ok(format!("{} {:?}", query.sql, query.args))
+    }
+
+    pub struct Db;
+
+    impl Db {
+        pub fn execute(&self, query: Query, _: &Pool) -> u64 {
+            query.args.len() as u64
+        }
+    }
+}
+
+struct Users {
+    db: db::Db,
+    pool: Pool,
+}
+
+#[remove_async_await::remove_async_await(chains(
+    sqlx::query.fetch_one = db::fetch_one,
+    sqlx::query!.execute = self.db.execute,
+))]
+impl Users {
+    async fn name(&self, id: u32) -> Result<String, ()> {
+        sqlx::query("SELECT name FROM users WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    async fn delete(&self, id: u32) -> u64 {
+        query!("DELETE FROM users WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+    }
+}
+
+#[test]
+fn chains() {
+    let users = Users {
+        db: db::Db,
+        pool: Pool,
+    };
+    assert_eq!(
+        users.name(1).unwrap(),
+        "SELECT name FROM users WHERE id = ? [1]"
+    );
+    assert_eq!(users.delete(1), 1);
+}
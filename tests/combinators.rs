@@ -0,0 +1,37 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn number(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await]
+async fn zip() -> (u32, u32) {
+    let first = futures_lite::future::zip(number(1), number(2)).await;
+    let second = number(3).zip(async { 4 }).await;
+    (first.0 + second.0, first.1 + second.1)
+}
+
+#[remove_async_await::remove_async_await]
+async fn or() -> u32 {
+    future::or(number(1), number(2)).await + number(3).or(number(4)).await
+}
+
+#[remove_async_await::remove_async_await]
+async fn race_first() -> u32 {
+    future::race(number(1), number(2)).await
+}
+
+#[remove_async_await::remove_async_await(race = second)]
+async fn race_second() -> u32 {
+    number(1).race(number(2)).await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn combinators() {
+    assert_eq!(zip().await, (4, 6));
+    assert_eq!(or().await, 4);
+    assert_eq!(race_first().await, 1);
+    assert_eq!(race_second().await, 2);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "compat")]
+
+use remove_async_await::{async_impl, maybe_async, must_be_async, must_be_sync, sync_impl};
+
+#[maybe_async(?Send)]
+trait Get {
+    async fn get(&self) -> u32;
+}
+
+struct One;
+
+#[maybe_async(AFIT)]
+impl Get for One {
+    async fn get(&self) -> u32 {
+        1
+    }
+}
+
+#[must_be_sync(map(future_value = value))]
+async fn sync_value() -> u32 {
+    future_value().await
+}
+
+#[must_be_async]
+async fn future_value() -> u32 {
+    value()
+}
+
+fn value() -> u32 {
+    2
+}
+
+#[sync_impl]
+fn mode() -> &'static str {
+    "sync"
+}
+
+#[async_impl]
+fn mode() -> &'static str {
+    "async"
+}
+
+#[test]
+fn compat() {
+    let expected = if cfg!(feature = "is_sync") {
+        "sync"
+    } else {
+        "async"
+    };
+    assert_eq!(mode(), expected);
+    assert_eq!(sync_value(), 2);
+    // `must_be_sync` maps it away, so it has to be used here
+    drop(future_value());
+}
+
+#[cfg(feature = "is_sync")]
+#[test]
+fn compat_is_sync() {
+    assert_eq!(One.get(), 1);
+}
+
+#[cfg(not(feature = "is_sync"))]
+#[test]
+fn compat_async() {
+    // the async items are unchanged, so there's nothing to run them with here
+    drop(One.get());
+}
@@ -0,0 +1,59 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+// stand-in for embassy-time's `Duration`, since its timers are only named by the async code
+mod embassy_time {
+    pub struct Duration(u64);
+
+    impl Duration {
+        pub fn from_millis(millis: u64) -> Self {
+            Duration(millis)
+        }
+    }
+
+    impl From<Duration> for core::time::Duration {
+        fn from(duration: Duration) -> Self {
+            core::time::Duration::from_millis(duration.0)
+        }
+    }
+}
+
+#[remove_async_await::remove_async_await(delay = std::thread::sleep)]
+async fn wait() {
+    embassy_time::Timer::after(embassy_time::Duration::from_millis(10)).await;
+    embassy_time::Timer::after_millis(10).await;
+}
+
+struct Driver {
+    waited: Cell<Duration>,
+}
+
+#[remove_async_await::remove_async_await(delay = |duration| self.wait(duration))]
+impl Driver {
+    fn wait(&self, duration: Duration) {
+        self.waited.set(self.waited.get() + duration);
+    }
+
+    async fn reset(&self) {
+        embassy_time::Timer::after_secs(1).await;
+        embassy_time::Delay.delay_ms(20).await;
+        embassy_time::Delay.delay_us(30).await;
+    }
+}
+
+#[test]
+fn delay() {
+    let start = Instant::now();
+    wait();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+
+    let driver = Driver {
+        waited: Cell::new(Duration::ZERO),
+    };
+    driver.reset();
+    assert_eq!(driver.waited.get(), Duration::from_micros(1_020_030));
+}
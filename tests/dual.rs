@@ -0,0 +1,30 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::dual]
+async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::dual(feature = "async", map(get_one = get))]
+async fn get_twice() -> u32 {
+    get_one().await * 2
+}
+
+#[remove_async_await::dual(when(all(feature = "async", not(target_arch = "wasm32"))))]
+trait Get {
+    async fn get(&self) -> u32;
+}
+
+struct Three;
+
+#[remove_async_await::dual]
+impl Get for Three {
+    async fn get(&self) -> u32 {
+        get().await + get_twice().await
+    }
+}
+
+#[test]
+fn dual() {
+    assert_eq!(Three.get(), 3);
+}
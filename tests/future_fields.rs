@@ -0,0 +1,47 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn compute(n: u32) -> u32 {
+    n * 2
+}
+
+#[remove_async_await::remove_async_await]
+struct Machine {
+    pending: Option<std::pin::Pin<Box<dyn std::future::Future<Output = u32> + Send>>>,
+}
+
+#[remove_async_await::remove_async_await]
+impl Machine {
+    fn start(&mut self, n: u32) {
+        // `compute` is another item, so its future is boxed through an async block
+        self.pending = Some(Box::pin(async move { compute(n).await }));
+    }
+
+    async fn finish(&mut self) -> Option<u32> {
+        let pending = self.pending.take()?;
+        Some(pending.await)
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+// the futures only need to be mutable to be awaited in place
+#[allow(unused_mut)]
+async fn future_fields() {
+    let mut machine = Machine { pending: None };
+    machine.start(2);
+    let finished = machine.finish().await;
+    assert_eq!(finished, Some(4));
+    let finished = machine.finish().await;
+    assert_eq!(finished, None);
+
+    let mut future = Box::pin(async { compute(1).await });
+    let value = (&mut future).await;
+    assert_eq!(value, 2);
+    let mut future = Box::pin(async { compute(2).await });
+    let value = future.as_mut().await;
+    assert_eq!(value, 4);
+    let mut future = async { compute(3).await }.boxed();
+    let value = std::pin::Pin::new(&mut future).await;
+    assert_eq!(value, 6);
+}
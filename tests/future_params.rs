@@ -0,0 +1,66 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn run<F: std::future::Future<Output = u32>>(future: F) -> u32 {
+    let output: F::Output = future.await;
+    output + 1
+}
+
+#[remove_async_await::remove_async_await(closure(F))]
+async fn run_lazily<
+    F: std::future::Future<Output = u32> + Send,
+    G: std::future::Future<Output = String>,
+>(
+    future: F,
+    message: G,
+) -> String {
+    format!("{} {}", message.await, future.await * 2)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn future_params() {
+    assert_eq!(run(async { 1 }).await, 2);
+}
+
+#[test]
+fn closure_params() {
+    assert_eq!(run_lazily(|| 21, "answer".to_owned()), "answer 42");
+}
+
+#[remove_async_await::remove_async_await(closure(retry))]
+async fn with(
+    value: impl std::future::Future<Output = u32>,
+    retry: impl std::future::Future<Output = u32> + Clone,
+) -> u32 {
+    value.await + retry.await
+}
+
+#[test]
+fn impl_future_args() {
+    assert_eq!(with(1, || 2), 3);
+}
+
+#[remove_async_await::remove_async_await]
+async fn run_where<F, T>(future: F) -> T
+where
+    F: std::future::Future<Output = T> + Send,
+    T: 'static,
+{
+    future.await
+}
+
+#[remove_async_await::remove_async_await(closure(F))]
+async fn run_lazily_where<F>(future: F) -> u32
+where
+    F: std::future::Future<Output = u32> + Send,
+    F: Clone,
+{
+    future.await + 1
+}
+
+#[test]
+fn where_clauses() {
+    assert_eq!(run_where(1), 1);
+    assert_eq!(run_lazily_where(|| 1), 2);
+}
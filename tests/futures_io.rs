@@ -0,0 +1,40 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn read_bytes(mut bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    use futures::io::{AsyncReadExt, SeekFrom};
+
+    let _ = SeekFrom::Start(0);
+    let mut buf = Vec::new();
+    bytes.read_to_end(&mut buf).await?;
+    Ok(buf)
+}
+
+#[remove_async_await::remove_async_await]
+async fn read_through_trait<R: futures::io::AsyncRead + Unpin>(
+    mut reader: R,
+) -> std::io::Result<String> {
+    let mut buf = String::new();
+    futures::io::AsyncReadExt::read_to_string(&mut reader, &mut buf).await?;
+    Ok(buf)
+}
+
+#[remove_async_await::remove_async_await]
+async fn write_and_read(data: &[u8]) -> std::io::Result<String> {
+    use futures::{AsyncBufReadExt as _, AsyncSeekExt, AsyncWriteExt};
+
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    AsyncWriteExt::write_all(&mut cursor, data).await?;
+    cursor.flush().await?;
+    AsyncSeekExt::seek(&mut cursor, std::io::SeekFrom::Start(0)).await?;
+    let mut line = String::new();
+    cursor.read_line(&mut line).await?;
+    Ok(line)
+}
+
+#[test]
+fn futures_io() {
+    assert_eq!(read_bytes(b"hello").unwrap(), b"hello");
+    assert_eq!(read_through_trait(&b"world"[..]).unwrap(), "world");
+    assert_eq!(write_and_read(b"a\nb").unwrap(), "a\n");
+}
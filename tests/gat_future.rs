@@ -0,0 +1,48 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+trait Lookup {
+    type Fut<'a>: std::future::Future<Output = Option<&'a str>> + 'a
+    where
+        Self: 'a;
+    type Count<'a>: std::future::Future<Output = usize>
+    where
+        Self: 'a;
+
+    fn get<'a>(&'a self, key: &str) -> Self::Fut<'a>;
+    fn count<'a>(&'a self) -> Self::Count<'a>;
+}
+
+struct Pairs(Vec<(String, String)>);
+
+#[remove_async_await::remove_async_await]
+impl Lookup for Pairs {
+    type Fut<'a> = futures::future::BoxFuture<'a, Option<&'a str>>;
+    type Count<'a> = std::future::Ready<usize>;
+
+    fn get<'a>(&'a self, key: &str) -> Self::Fut<'a> {
+        let key = key.to_owned();
+        Box::pin(async move {
+            self.0
+                .iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.as_str())
+        })
+    }
+
+    fn count<'a>(&'a self) -> Self::Count<'a> {
+        std::future::ready(self.0.len())
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn gat_future() {
+    let pairs = Pairs(vec![("a".to_owned(), "1".to_owned())]);
+    let found = pairs.get("a").await;
+    assert_eq!(found, Some("1"));
+    let missing = pairs.get("b").await;
+    assert_eq!(missing, None);
+    let count = pairs.count().await;
+    assert_eq!(count, 1);
+}
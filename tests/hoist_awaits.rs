@@ -0,0 +1,96 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::{
+    cell::RefCell,
+    sync::{Mutex, MutexGuard},
+};
+
+thread_local! {
+    static DROPS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Guard(&'static str);
+
+impl Guard {
+    #[remove_async_await::remove_async_await]
+    async fn get(&self, value: u32) -> u32 {
+        value
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.borrow_mut().push(self.0));
+    }
+}
+
+fn record(name: &'static str) -> u32 {
+    DROPS.with(|drops| drops.borrow_mut().push(name));
+    0
+}
+
+#[remove_async_await::remove_async_await(hoist_awaits)]
+async fn hoisted(mutex: &Mutex<u32>) -> u32 {
+    // the guard is dropped before `record` runs, and before the lock is taken again
+    let value = Guard("first").get(1).await + record("record") + *mutex.lock().unwrap();
+    let total = Guard("second").get(value).await + Guard("third").get(*mutex.lock().unwrap()).await;
+    if value > 0 {
+        total + Guard("branch").get(1).await
+    } else {
+        total
+    }
+}
+
+#[remove_async_await::remove_async_await(hoist_awaits)]
+async fn ordered() -> u32 {
+    // `record` runs before the awaited call, so the call isn't hoisted before it
+    record("record") + Guard("guard").get(1).await
+}
+
+struct Counter(Mutex<u32>);
+
+impl Counter {
+    /// Panics instead of deadlocking if the last guard is still alive.
+    #[remove_async_await::remove_async_await]
+    async fn lock(&self) -> MutexGuard<'_, u32> {
+        self.0.try_lock().unwrap()
+    }
+}
+
+#[remove_async_await::remove_async_await(hoist_awaits)]
+async fn increment(counter: &Counter) -> u32 {
+    // each guard is dropped at the end of its statement, like in the async item
+    *counter.lock().await += 1;
+    let value = *counter.lock().await;
+    let Some(value) = value.checked_add(*counter.lock().await - 1) else {
+        return 0;
+    };
+    value + *counter.lock().await
+}
+
+#[remove_async_await::remove_async_await]
+async fn name() -> String {
+    String::from("name")
+}
+
+#[remove_async_await::remove_async_await(hoist_awaits)]
+async fn borrowed() -> usize {
+    // the borrow keeps the awaited value alive past the statement, so it isn't hoisted
+    let owned = &name().await;
+    let first = &name().await[..1];
+    owned.len() + first.len()
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn hoist_awaits() {
+    let mutex = Mutex::new(2);
+    assert_eq!(hoisted(&mutex).await, 6);
+    let drops = DROPS.with(|drops| drops.take());
+    assert_eq!(drops, ["first", "record", "second", "third", "branch"]);
+    assert_eq!(ordered().await, 1);
+    let drops = DROPS.with(|drops| drops.take());
+    assert_eq!(drops, ["record", "guard"]);
+    assert_eq!(increment(&Counter(Mutex::new(0))).await, 2);
+    assert_eq!(borrowed().await, 5);
+}
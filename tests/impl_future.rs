@@ -0,0 +1,46 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+fn double(value: u32) -> impl std::future::Future<Output = u32> {
+    async move { value * 2 }
+}
+
+#[remove_async_await::remove_async_await]
+fn quadruple(value: u32) -> impl std::future::Future<Output = u32> + Send + 'static {
+    let value = value * 2;
+    async move { double(value).await }
+}
+
+#[remove_async_await::remove_async_await]
+fn ready(value: u32) -> impl std::future::Future<Output = u32> {
+    std::future::ready(value)
+}
+
+#[remove_async_await::remove_async_await]
+fn nothing() -> impl std::future::Future<Output = ()> {
+    async {}
+}
+
+trait Doubler {
+    #[remove_async_await::remove_async_await]
+    fn double(&self) -> impl std::future::Future<Output = u32>;
+}
+
+struct Number(u32);
+
+impl Doubler for Number {
+    #[remove_async_await::remove_async_await]
+    fn double(&self) -> impl std::future::Future<Output = u32> {
+        async move { double(self.0).await }
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn impl_future() {
+    assert_eq!(double(1).await, 2);
+    assert_eq!(quadruple(1).await, 4);
+    assert_eq!(ready(1).await, 1);
+    nothing().await;
+    assert_eq!(Number(21).double().await, 42);
+}
@@ -0,0 +1,77 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+#[tracing::instrument(skip(value, name), fields(double = value * 2))]
+async fn get(value: u32, name: String) -> u32 {
+    assert!(!name.is_empty());
+    value
+}
+
+#[remove_async_await::remove_async_await]
+#[tracing::instrument(level = "debug", skip(future))]
+#[inline]
+async fn add(future: impl std::future::Future<Output = u32>, value: u32) -> u32 {
+    future.await + value
+}
+
+#[remove_async_await::remove_async_await]
+#[tracing::instrument(skip(first, second), err)]
+async fn add_both<F: std::future::Future<Output = u32>>(
+    first: F,
+    second: F,
+) -> Result<u32, String> {
+    Ok(first.await + second.await)
+}
+
+#[remove_async_await::remove_async_await]
+#[tracing::instrument(err, ret)]
+async fn parse(text: &str) -> Result<u32, std::num::ParseIntError> {
+    text.parse()
+}
+
+static EVENTS: std::sync::Mutex<Vec<tracing::Level>> = std::sync::Mutex::new(Vec::new());
+
+/// Records the level of every event, like the ones from `err` and `ret`.
+struct Events;
+
+impl tracing::Subscriber for Events {
+    fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+        tracing::span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        EVENTS.lock().unwrap().push(*event.metadata().level());
+    }
+
+    fn enter(&self, _: &tracing::span::Id) {}
+
+    fn exit(&self, _: &tracing::span::Id) {}
+}
+
+#[test]
+fn instrument_err() {
+    tracing::subscriber::with_default(Events, || {
+        assert_eq!(parse("1"), Ok(1));
+        assert!(parse("a").is_err());
+    });
+    assert_eq!(
+        *EVENTS.lock().unwrap(),
+        [tracing::Level::INFO, tracing::Level::ERROR]
+    );
+}
+
+#[test]
+fn instrument() {
+    let value = add(get(1, "one".to_owned()), 2);
+    assert_eq!(value, 3);
+    let value = add_both(get(1, "one".to_owned()), get(2, "two".to_owned()));
+    assert_eq!(value, Ok(3));
+}
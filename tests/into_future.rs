@@ -0,0 +1,34 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn send<R: std::future::IntoFuture<Output = u32>>(request: R) -> u32 {
+    request.await + 1
+}
+
+#[remove_async_await::remove_async_await]
+async fn send_all(a: impl std::future::IntoFuture<Output = u32>, b: u32) -> u32 {
+    let future = a.into_future();
+    future.await + std::future::IntoFuture::into_future(b).await
+}
+
+mod wrapper {
+    /// Not `IntoFuture::into_future`, so calls to it are left alone.
+    fn into_future(value: u32) -> Vec<u32> {
+        vec![value]
+    }
+
+    #[remove_async_await::remove_async_await]
+    pub async fn wrapped(value: u32) -> Vec<u32> {
+        into_future(value)
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn into_future() {
+    let sent = send(1).await;
+    assert_eq!(sent, 2);
+    let all = send_all(1, 2).await;
+    assert_eq!(all, 3);
+    assert_eq!(wrapper::wrapped(4).await, vec![4]);
+}
@@ -0,0 +1,45 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn read_all<R: tokio::io::AsyncRead + Unpin>(mut reader: R) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await?;
+    Ok(buf)
+}
+
+#[remove_async_await::remove_async_await]
+async fn write_all<W>(writer: &mut W, data: &[u8]) -> std::io::Result<()>
+where
+    W: futures::io::AsyncWrite + Unpin + ?Sized,
+{
+    writer.write_all(data).await?;
+    writer.flush().await
+}
+
+#[remove_async_await::remove_async_await]
+async fn rewind(seeker: &mut (dyn tokio::io::AsyncSeek + Unpin)) -> std::io::Result<u64> {
+    seeker.seek(std::io::SeekFrom::Start(0)).await
+}
+
+#[remove_async_await::remove_async_await]
+async fn first_line(mut reader: impl tokio::io::AsyncBufRead + Unpin) -> std::io::Result<String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    Ok(line)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn io() {
+    let data = read_all(&b"hello"[..]).await.unwrap();
+    assert_eq!(data, b"hello");
+    let mut written = Vec::new();
+    write_all(&mut written, b"world").await.unwrap();
+    assert_eq!(written, b"world");
+    let mut cursor = std::io::Cursor::new(vec![1, 2, 3]);
+    cursor.set_position(2);
+    let position = rewind(&mut cursor).await.unwrap();
+    assert_eq!(position, 0);
+    let line = first_line(&b"a\nb"[..]).await.unwrap();
+    assert_eq!(line, "a\n");
+}
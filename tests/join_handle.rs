@@ -0,0 +1,54 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+fn fire(n: u32) -> tokio::task::JoinHandle<u32> {
+    tokio::spawn(async move { n * 2 })
+}
+
+#[remove_async_await::remove_async_await]
+fn hash(data: Vec<u8>) -> tokio::task::JoinHandle<u64> {
+    tokio::task::spawn_blocking(move || data.iter().map(|&b| b as u64).sum())
+}
+
+#[remove_async_await::remove_async_await]
+fn log(message: &'static str) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        println!("{}", message);
+    })
+}
+
+#[remove_async_await::remove_async_await]
+fn thread() -> std::thread::JoinHandle<u32> {
+    std::thread::spawn(|| 1)
+}
+
+// only tokio's tasks are inlined, not threads
+#[remove_async_await::remove_async_await]
+fn handoff(n: u32) -> tokio::task::JoinHandle<u32> {
+    let worker = std::thread::spawn(move || n + 1);
+    tokio::spawn(async move { worker.join().unwrap() * 2 })
+}
+
+mod imported {
+    use std::thread::JoinHandle;
+
+    // an imported `JoinHandle` could be a thread's, so it's only rewritten when a task is spawned
+    #[remove_async_await::remove_async_await]
+    pub fn thread(v: u32) -> JoinHandle<u32> {
+        std::thread::spawn(move || v)
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn join_handle() {
+    let n: u32 = fire(21);
+    assert_eq!(n, 42);
+    let sum: u64 = hash(vec![1, 2, 3]);
+    assert_eq!(sum, 6);
+    log("done");
+    assert_eq!(thread().join().unwrap(), 1);
+    let n: u32 = handoff(1);
+    assert_eq!(n, 4);
+    assert_eq!(imported::thread(3).join().unwrap(), 3);
+}
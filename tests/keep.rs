@@ -0,0 +1,43 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn item() -> u32 {
+    #[remove_async_await::keep]
+    async fn callback() -> u32 {
+        2
+    }
+    futures::executor::block_on(callback())
+}
+
+#[remove_async_await::dual]
+async fn statements() -> u32 {
+    #[remove_async_await::keep]
+    let future = async { 3 };
+    let mut value = futures::executor::block_on(future);
+    #[remove_async_await::keep]
+    {
+        value += futures::executor::block_on(async { 4 });
+    }
+    value
+}
+
+struct Client;
+
+#[cfg_attr(not(feature = "async"), remove_async_await::remove_async_await)]
+impl Client {
+    async fn get(&self) -> u32 {
+        futures::executor::block_on(self.poll())
+    }
+
+    #[remove_async_await::keep]
+    async fn poll(&self) -> u32 {
+        5
+    }
+}
+
+#[test]
+fn keep() {
+    assert_eq!(item(), 2);
+    assert_eq!(statements(), 7);
+    assert_eq!(Client.get(), 5);
+}
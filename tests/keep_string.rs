@@ -0,0 +1,22 @@
+// left async, since it isn't transformed
+async fn spawn_async(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn get(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await_string(keep(futures::executor::block_on, spawn_async))]
+async fn run(value: u32) -> u32 {
+    let kept = futures::executor::block_on(async move { get(value) + spawn_async(value).await });
+    let future = spawn_async(value);
+    kept + futures::executor::block_on(future) + get(value).await
+}
+
+#[remove_async_await::remove_async_await_string]
+#[test]
+async fn keep_string() {
+    assert_eq!(run(1).await, 4);
+}
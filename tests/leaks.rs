@@ -0,0 +1,17 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use futures::channel::oneshot;
+
+// `try_recv` doesn't need a runtime, so the warning about the receiver is silenced
+#[remove_async_await::remove_async_await(warn_leaks)]
+#[allow(deprecated)]
+pub async fn received(rx: &mut oneshot::Receiver<u32>) -> Option<u32> {
+    rx.try_recv().ok().flatten()
+}
+
+#[test]
+fn leaks() {
+    let (tx, mut rx) = oneshot::channel();
+    tx.send(1).unwrap();
+    assert_eq!(received(&mut rx), Some(1));
+}
@@ -0,0 +1,26 @@
+#[remove_async_await::remove_async_await]
+async fn fetch(value: u32) -> Option<u32> {
+    (value != 0).then_some(value)
+}
+
+#[remove_async_await::remove_async_await]
+async fn double(value: u32) -> Result<u32, String> {
+    let Some(value) = fetch(value).await else {
+        let fallback = fetch(1).await;
+        let message = format!("no value from {}, fallback {:?}", value, fallback);
+        return Err(message);
+    };
+    let Some(doubled): Option<u32> = fetch(value * 2).await else {
+        unreachable!()
+    };
+    Ok(doubled)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn let_else() {
+    let doubled = double(21).await;
+    assert_eq!(doubled, Ok(42));
+    let error = double(0).await;
+    assert_eq!(error, Err("no value from 0, fallback Some(1)".to_owned()));
+}
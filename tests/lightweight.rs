@@ -0,0 +1,34 @@
+// run with `cargo test --no-default-features --test lightweight`
+#![cfg(not(any(feature = "full", feature = "wasm")))]
+
+#[remove_async_await::remove_async_await]
+async fn get(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await]
+async fn add(a: u32, b: u32) -> u32 {
+    // in a macro too, since the tokens aren't parsed
+    assert_eq!(get(a).await, a);
+    let b = async move { get(b).await }.await;
+    get(a).await + b
+}
+
+#[remove_async_await::remove_async_await]
+fn double(value: u32) -> u32 {
+    let double = async |value: u32| get(value).await * 2;
+    double(value)
+}
+
+#[remove_async_await::remove_async_await]
+async fn raw(r#async: u32) -> u32 {
+    let r#await = r#async;
+    get(r#await).await
+}
+
+#[test]
+fn lightweight() {
+    assert_eq!(raw(1), 1);
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(double(2), 4);
+}
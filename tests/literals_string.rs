@@ -0,0 +1,12 @@
+#[remove_async_await::remove_async_await_string]
+/// Call the async version with `.await` instead.
+async fn message() -> &'static str {
+    "call the async version with .await"
+}
+
+#[remove_async_await::remove_async_await_string]
+#[test]
+async fn literals_string() {
+    assert_eq!(message().await, "call the async version with .await");
+    assert_eq!(concat!("async", ".await"), "async.await");
+}
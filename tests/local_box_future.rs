@@ -0,0 +1,41 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+// `LocalBoxFuture` is rewritten away, so `futures` doesn't need to be a dependency
+
+use std::rc::Rc;
+
+trait Task {
+    type Output;
+
+    fn run(&self) -> Self::Output;
+}
+
+struct Count(Rc<u32>);
+
+impl Task for Count {
+    #[remove_async_await::remove_async_await]
+    type Output = futures::future::LocalBoxFuture<'static, u32>;
+
+    #[remove_async_await::remove_async_await]
+    fn run(&self) -> Self::Output {
+        let count = self.0.clone();
+        async move { *count }.boxed_local()
+    }
+}
+
+#[remove_async_await::remove_async_await]
+fn run_twice(task: &Count) -> futures::future::LocalBoxFuture<'_, u32> {
+    async move {
+        let first: futures::future::LocalBoxFuture<'_, u32> = task.run();
+        first.await + task.run().await
+    }
+    .boxed_local()
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn local_box_future() {
+    let task = Count(Rc::new(21));
+    assert_eq!(task.run().await, 21);
+    assert_eq!(run_twice(&task).await, 42);
+}
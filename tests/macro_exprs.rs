@@ -0,0 +1,26 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+macro_rules! log {
+    ($($arg:tt)*) => {
+        format!($($arg)*)
+    };
+}
+
+#[remove_async_await::remove_async_await]
+async fn get(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await(macro_exprs)]
+async fn describe(value: u32) -> (String, &'static str) {
+    let block = log!("{}", async { get(value).await }.await);
+    // not a list of expressions, so it's left as is
+    let tokens = stringify!(get(value).await; 2);
+    (log!("{} {}", get(value).await, block), tokens)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn macro_exprs() {
+    assert_eq!(describe(1).await, ("1 1".to_owned(), "get(value).await; 2"));
+}
@@ -0,0 +1,28 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(strict, macro_tokens)]
+async fn describe() -> &'static str {
+    macro_rules! describe {
+        () => {
+            stringify!(async value.await)
+        };
+    }
+    std::future::ready(describe!()).await
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn describe_string() -> &'static str {
+    macro_rules! describe {
+        () => {
+            stringify!(async value.await)
+        };
+    }
+    describe!()
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn macro_rules() {
+    assert_eq!(describe().await, "async value.await");
+    assert_eq!(describe_string().await, "async value.await");
+}
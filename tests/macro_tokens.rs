@@ -0,0 +1,26 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+macro_rules! log {
+    ($($arg:tt)*) => {
+        format!($($arg)*)
+    };
+}
+
+#[remove_async_await::remove_async_await]
+async fn get(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await(macro_tokens)]
+async fn describe(value: u32) -> String {
+    // `vec![value; len]` isn't a list of expressions, so it's walked too
+    let values = vec![get(value).await; 2];
+    let block = log!("{}", async move { get(value).await }.await);
+    log!("{} {:?} {}", get(value).await, values, block)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn macro_tokens() {
+    assert_eq!(describe(1).await, "1 [1, 1] 1");
+}
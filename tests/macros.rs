@@ -0,0 +1,28 @@
+use std::fmt::Write;
+
+#[remove_async_await::remove_async_await]
+async fn parse(value: &str) -> Result<u32, std::num::ParseIntError> {
+    value.parse()
+}
+
+#[remove_async_await::remove_async_await]
+async fn describe(value: &str) -> Result<String, std::num::ParseIntError> {
+    let mut out = format!("parsed {}", parse(value).await?);
+    write!(out, ", doubled {}", parse(value).await? * 2).unwrap();
+    assert_eq!(
+        parse(value).await?,
+        21,
+        "unexpected value {}",
+        parse(value).await?
+    );
+    Ok(out)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn macros() {
+    assert_eq!(describe("21").await.unwrap(), "parsed 21, doubled 42");
+    assert!(describe("nope").await.is_err());
+    let values = vec![parse("1").await.unwrap(), parse("2").await.unwrap()];
+    assert_eq!(values, [1, 2]);
+}
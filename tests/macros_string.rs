@@ -0,0 +1,24 @@
+macro_rules! twice {
+    ($value:expr) => {
+        $value + $value
+    };
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn get(value: u32) -> u32 {
+    value
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn add(a: u32, b: u32) -> u32 {
+    // the tokens in macros are walked too
+    let sum = twice!(get(a).await) + twice!(async { get(b).await }.await);
+    assert_eq!(vec![get(a).await], [a]);
+    sum
+}
+
+#[remove_async_await::remove_async_await_string]
+#[test]
+async fn macros_string() {
+    assert_eq!(add(1, 2).await, 6);
+}
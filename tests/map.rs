@@ -0,0 +1,38 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::time::Duration;
+
+mod api {
+    pub fn fetch() -> String {
+        "fetched".to_owned()
+    }
+
+    pub mod blocking {
+        pub fn get(value: u32) -> u32 {
+            value * 2
+        }
+    }
+}
+
+#[remove_async_await::remove_async_await(map(api::fetch_async = api::fetch))]
+async fn fetch() -> String {
+    api::fetch_async().await
+}
+
+#[remove_async_await::remove_async_await(map(
+    tokio::time::sleep = std::thread::sleep,
+    api::nonblocking = api::blocking,
+))]
+async fn sleep_and_get() -> u32 {
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    api::nonblocking::get(21).await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn map() {
+    let fetched = fetch().await;
+    assert_eq!(fetched, "fetched");
+    let value = sleep_and_get().await;
+    assert_eq!(value, 42);
+}
@@ -0,0 +1,47 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::dual]
+async fn build() -> Vec<&'static str> {
+    let mut builds = vec!["both"];
+    remove_async_await::async_only! {
+        builds.push("async");
+    }
+    remove_async_await::sync_only! {
+        builds.push("blocking");
+    }
+    builds
+}
+
+#[remove_async_await::dual]
+async fn tail() -> u32 {
+    let value = futures::future::ready(1).await;
+    remove_async_await::async_only! {
+        value + 1
+    }
+    remove_async_await::sync_only! {
+        value + 2
+    }
+}
+
+// `all()` always holds, so this keeps the async function
+#[remove_async_await::dual(when(all()))]
+async fn async_build() -> Vec<&'static str> {
+    let mut builds = vec!["both"];
+    remove_async_await::async_only! {
+        builds.push("async");
+    }
+    remove_async_await::sync_only! {
+        builds.push("blocking");
+    }
+    builds
+}
+
+#[test]
+fn markers() {
+    assert_eq!(build(), ["both", "blocking"]);
+    assert_eq!(tail(), 3);
+    assert_eq!(
+        futures::executor::block_on(async_build()),
+        ["both", "async"]
+    );
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "full")]
+
+fn get_sync() -> u32 {
+    1
+}
+
+async fn get_async() -> u32 {
+    2
+}
+
+// outside any transformed function, like in a macro, the features of this crate decide: there's no `async` feature,
+// so `get_sync()` isn't awaited, while `full` is enabled
+macro_rules! values {
+    () => {
+        (
+            remove_async_await::maybe_await!(get_sync()),
+            remove_async_await::maybe_await!(get_async(), feature = "full"),
+        )
+    };
+}
+
+async fn outside() -> (u32, u32) {
+    values!()
+}
+
+#[remove_async_await::remove_async_await(sibling)]
+async fn get() -> u32 {
+    3
+}
+
+// the transform removes it like `.await`, whatever the features are
+#[remove_async_await::remove_async_await(sibling)]
+async fn inside() -> u32 {
+    remove_async_await::maybe_await!(self::get(), feature = "full");
+    remove_async_await::maybe_await!(self::get(), feature = "full") * 2
+}
+
+#[test]
+fn maybe_await() {
+    assert_eq!(futures::executor::block_on(outside()), (1, 2));
+    assert_eq!(futures::executor::block_on(inside()), 6);
+    assert_eq!(inside_blocking(), 6);
+}
@@ -0,0 +1,46 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use remove_async_await_shim::MaybeFuture;
+
+// a signature shared by both builds, implemented by hand
+trait Client {
+    fn get(&self) -> MaybeFuture<'_, u32>;
+}
+
+struct One;
+
+impl Client for One {
+    fn get(&self) -> MaybeFuture<'_, u32> {
+        MaybeFuture::new(async { 1 })
+    }
+}
+
+// made blocking, `MaybeFuture<'static, u32>` becomes `u32`
+#[remove_async_await::remove_async_await(sibling)]
+fn get() -> MaybeFuture<'static, u32> {
+    MaybeFuture::new(async { One.get().into_inner() })
+}
+
+#[remove_async_await::remove_async_await(sibling)]
+fn ready() -> MaybeFuture<'static, u32> {
+    MaybeFuture::ready(2)
+}
+
+#[remove_async_await::remove_async_await(sibling)]
+async fn get_twice() -> u32 {
+    self::get().await * 2
+}
+
+#[test]
+fn maybe_future() {
+    // awaited rather than passed to `block_on`, since it's only `IntoFuture` with the shim's `blocking` feature
+    assert_eq!(futures::executor::block_on(async { One.get().await }), 1);
+    assert_eq!(One.get().into_inner(), 1);
+    assert_eq!(futures::executor::block_on(get_twice()), 2);
+    let blocking: u32 = get_blocking();
+    assert_eq!(blocking, 1);
+    assert_eq!(get_twice_blocking(), 2);
+    assert_eq!(ready().into_inner(), 2);
+    let ready: u32 = ready_blocking();
+    assert_eq!(ready, 2);
+}
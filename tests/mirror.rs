@@ -0,0 +1,33 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::mirror(name = "blocking")]
+pub mod async_impl {
+    pub async fn get() -> u32 {
+        1
+    }
+
+    pub async fn get_twice() -> u32 {
+        get().await * 2
+    }
+}
+
+mod timers {
+    #[remove_async_await::mirror(map(futures::future::ready = std::convert::identity))]
+    pub mod timer {
+        pub async fn wait() -> u32 {
+            futures::future::ready(3).await
+        }
+    }
+}
+
+#[test]
+fn mirror() {
+    assert_eq!(blocking::get_twice(), 2);
+    assert_eq!(futures::executor::block_on(async_impl::get_twice()), 2);
+}
+
+#[test]
+fn default_name() {
+    let _ = timers::timer::wait;
+    assert_eq!(timers::blocking::wait(), 3);
+}
@@ -0,0 +1,38 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+macro_rules! getter {
+    ($name:ident, $value:expr) => {
+        #[remove_async_await::remove_async_await(sibling, name_span = "call_site")]
+        pub async fn $name() -> u32 {
+            $value
+        }
+    };
+}
+
+getter!(get, 1);
+
+#[remove_async_await::remove_async_await(trait_pair, name_span = "call_site")]
+trait Client {
+    async fn get(&self) -> u32;
+}
+
+struct One;
+
+impl Client for One {
+    fn get(&self) -> u32 {
+        get_blocking()
+    }
+}
+
+impl ClientAsync for One {
+    async fn get(&self) -> u32 {
+        get().await
+    }
+}
+
+#[test]
+fn name_span() {
+    assert_eq!(futures::executor::block_on(get()), 1);
+    assert_eq!(Client::get(&One), 1);
+    assert_eq!(futures::executor::block_on(ClientAsync::get(&One)), 1);
+}
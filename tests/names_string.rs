@@ -0,0 +1,25 @@
+struct Client {
+    is_async: bool,
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn async_client() -> Client {
+    Client { is_async: false }
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn do_async_work(awaited: u32) -> u32 {
+    let client = async_client().await;
+    let work = async move { awaited + 1 }.await;
+    if client.is_async {
+        0
+    } else {
+        work
+    }
+}
+
+#[remove_async_await::remove_async_await_string]
+#[test]
+async fn names_string() {
+    assert_eq!(do_async_work(1).await, 2);
+}
@@ -0,0 +1,42 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+trait Handler {
+    #[remove_async_await::remove_async_await]
+    fn handle<'a>(
+        &'a self,
+        request: &'a str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = String> + Send + 'a>>;
+}
+
+struct Echo;
+
+impl Handler for Echo {
+    #[remove_async_await::remove_async_await]
+    fn handle<'a>(
+        &'a self,
+        request: &'a str,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = String> + Send + 'a>> {
+        Box::pin(async move { request.to_uppercase() })
+    }
+}
+
+#[remove_async_await::remove_async_await]
+fn handle_all<'a>(
+    handler: &'a dyn Handler,
+    requests: &'a [&'a str],
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Vec<String>> + 'a>> {
+    Box::pin(async move {
+        let mut responses = Vec::new();
+        for request in requests {
+            responses.push(handler.handle(request).await);
+        }
+        responses
+    })
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn pin_box_future() {
+    assert_eq!(Echo.handle("hi").await, "HI");
+    assert_eq!(handle_all(&Echo, &["a", "b"]).await, ["A", "B"]);
+}
@@ -0,0 +1,28 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::time::{Duration, Instant};
+
+#[remove_async_await::remove_async_await(profile(async_std))]
+async fn read(path: &async_std::path::Path) -> async_std::io::Result<String> {
+    async_std::task::sleep(Duration::from_millis(10)).await;
+    async_std::task::yield_now().await;
+    let contents = async_std::fs::read_to_string(path).await?;
+    // run inline, since awaiting the handle gives the output
+    let trimmed = async_std::task::spawn(async move { contents.trim().to_owned() }).await;
+    Ok(trimmed)
+}
+
+#[remove_async_await::remove_async_await(profile(async_std))]
+fn localhost() -> async_std::net::IpAddr {
+    async_std::net::IpAddr::V4(async_std::net::Ipv4Addr::LOCALHOST)
+}
+
+#[test]
+fn profile_async_std() {
+    let path = std::env::temp_dir().join("remove_async_await_profile_async_std.txt");
+    std::fs::write(&path, "contents\n").unwrap();
+    let start = Instant::now();
+    assert_eq!(read(&path).unwrap(), "contents");
+    assert!(start.elapsed() >= Duration::from_millis(10));
+    assert!(localhost().is_loopback());
+}
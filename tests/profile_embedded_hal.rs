@@ -0,0 +1,74 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+// stand-in for embedded-hal's blocking traits, so the transformed code has something to resolve to
+mod embedded_hal {
+    pub mod delay {
+        pub trait DelayNs {
+            fn delay_ms(&mut self, ms: u32);
+        }
+    }
+
+    pub mod i2c {
+        pub trait I2c {
+            type Error;
+
+            fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error>;
+        }
+    }
+}
+
+struct Delay(u32);
+
+impl embedded_hal::delay::DelayNs for Delay {
+    fn delay_ms(&mut self, ms: u32) {
+        self.0 += ms;
+    }
+}
+
+struct Bus(Vec<(u8, Vec<u8>)>);
+
+impl embedded_hal::i2c::I2c for Bus {
+    type Error = ();
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), ()> {
+        self.0.push((address, bytes.to_vec()));
+        Ok(())
+    }
+}
+
+struct Sensor<I, D> {
+    i2c: I,
+    delay: D,
+}
+
+#[remove_async_await::remove_async_await(profile(embedded_hal))]
+impl<I: embedded_hal_async::i2c::I2c, D> Sensor<I, D>
+where
+    D: embedded_hal_async::delay::DelayNs,
+{
+    async fn reset(&mut self) -> Result<(), I::Error> {
+        self.i2c.write(0x40, &[0x01]).await?;
+        self.delay.delay_ms(10).await;
+        Ok(())
+    }
+}
+
+#[remove_async_await::remove_async_await(profile(embedded_hal))]
+async fn wait(delay: &mut impl embedded_hal_async::delay::DelayNs) {
+    use embedded_hal_async::delay::{self, DelayNs};
+
+    DelayNs::delay_ms(delay, 5).await;
+    let _: Option<&dyn delay::DelayNs> = None;
+}
+
+#[test]
+fn profile_embedded_hal() {
+    let mut sensor = Sensor {
+        i2c: Bus(Vec::new()),
+        delay: Delay(0),
+    };
+    sensor.reset().unwrap();
+    wait(&mut sensor.delay);
+    assert_eq!(sensor.i2c.0, [(0x40, vec![0x01])]);
+    assert_eq!(sensor.delay.0, 15);
+}
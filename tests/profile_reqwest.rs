@@ -0,0 +1,53 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+// stand-in for reqwest's blocking API, so the transformed code has something to resolve to
+mod reqwest {
+    pub type Result<T> = std::result::Result<T, Error>;
+
+    #[derive(Debug)]
+    pub struct Error;
+
+    pub mod blocking {
+        pub struct Client;
+
+        impl Client {
+            pub fn new() -> Self {
+                Client
+            }
+
+            pub fn get(&self, url: &str) -> RequestBuilder {
+                RequestBuilder(url.to_owned())
+            }
+        }
+
+        pub struct RequestBuilder(String);
+
+        impl RequestBuilder {
+            pub fn send(self) -> super::Result<Response> {
+                Ok(Response(self.0))
+            }
+        }
+
+        pub struct Response(String);
+
+        impl Response {
+            pub fn text(self) -> super::Result<String> {
+                Ok(format!("body of {}", self.0))
+            }
+        }
+    }
+}
+
+#[remove_async_await::remove_async_await(profile(reqwest))]
+async fn get_text(client: &reqwest::Client, url: &str) -> reqwest::Result<String> {
+    let text = client.get(url).send().await?.text().await?;
+    Ok(text)
+}
+
+#[remove_async_await::remove_async_await(profile(reqwest))]
+#[test]
+async fn profile_reqwest() {
+    let client = reqwest::Client::new();
+    let text = get_text(&client, "https://example.com").await.unwrap();
+    assert_eq!(text, "body of https://example.com");
+}
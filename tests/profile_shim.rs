@@ -0,0 +1,23 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::time::{Duration, Instant};
+
+#[remove_async_await::remove_async_await(profile(shim))]
+async fn wait(duration: Duration) -> Result<u32, tokio::time::error::Elapsed> {
+    tokio::time::sleep(duration).await;
+    tokio::task::yield_now().await;
+    tokio::time::timeout(duration, get()).await
+}
+
+#[remove_async_await::remove_async_await(profile(shim))]
+async fn get() -> u32 {
+    async_std::task::sleep(Duration::from_millis(1)).await;
+    1
+}
+
+#[test]
+fn profile_shim() {
+    let start = Instant::now();
+    assert_eq!(wait(Duration::from_millis(10)), Ok(1));
+    assert!(start.elapsed() >= Duration::from_millis(11));
+}
@@ -0,0 +1,30 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::time::{Duration, Instant};
+
+#[remove_async_await::remove_async_await(profile(smol))]
+async fn read(path: &std::path::Path) -> smol::io::Result<String> {
+    smol::Timer::after(Duration::from_millis(10)).await;
+    smol::future::yield_now().await;
+    let contents = smol::fs::read_to_string(path).await?;
+    // run inline, since awaiting the task gives the output
+    let trimmed = smol::unblock(move || contents.trim().to_owned()).await;
+    Ok(trimmed)
+}
+
+#[remove_async_await::remove_async_await(profile(smol))]
+async fn write(path: &std::path::Path) -> smol::io::Result<()> {
+    let mut file = async_fs::File::create(path).await?;
+    smol::io::AsyncWriteExt::write_all(&mut file, b"written\n").await?;
+    async_io::Timer::after(Duration::from_millis(10)).await;
+    Ok(())
+}
+
+#[test]
+fn profile_smol() {
+    let path = std::env::temp_dir().join("remove_async_await_profile_smol.txt");
+    let start = Instant::now();
+    write(&path).unwrap();
+    assert_eq!(read(&path).unwrap(), "written");
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
@@ -0,0 +1,50 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+struct Task {
+    r#async: bool,
+    r#await: u32,
+}
+
+impl Task {
+    #[remove_async_await::remove_async_await]
+    async fn r#async(&self) -> bool {
+        self.r#async
+    }
+}
+
+#[remove_async_await::remove_async_await]
+async fn run(task: &Task) -> u32 {
+    let r#await = task.r#await;
+    if task.r#async().await {
+        r#await
+    } else {
+        0
+    }
+}
+
+#[remove_async_await::remove_async_await_string]
+async fn run_string(task: &Task) -> u32 {
+    let r#await = task.r#await;
+    if task.r#async().await {
+        r#await
+    } else {
+        0
+    }
+}
+
+#[remove_async_await::remove_async_await(sibling)]
+async fn r#try(value: u32) -> u32 {
+    std::future::ready(value).await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn raw_idents() {
+    let task = Task {
+        r#async: true,
+        r#await: 1,
+    };
+    assert_eq!(run(&task).await, 1);
+    assert_eq!(run_string(&task).await, 1);
+    assert_eq!(futures::executor::block_on(r#try(1)), try_blocking(1));
+}
@@ -0,0 +1,45 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(rename = "blocking_{name}")]
+pub async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await(rename = "{name}_sync")]
+trait Client {
+    async fn get(&self) -> u32;
+
+    async fn get_twice(&self) -> u32 {
+        self.get().await * 2
+    }
+}
+
+struct One;
+
+#[remove_async_await::remove_async_await(rename = "{name}_sync", map(get = blocking_get))]
+impl Client for One {
+    async fn get(&self) -> u32 {
+        get().await
+    }
+}
+
+#[remove_async_await::remove_async_await(rename = "blocking_{name}")]
+pub mod net {
+    pub async fn fetch() -> u32 {
+        get_port().await + 1
+    }
+
+    async fn get_port() -> u32 {
+        80
+    }
+}
+
+#[test]
+fn rename() {
+    assert_eq!(blocking_get(), 1);
+    assert_eq!(ClientSync::get_twice(&One), 2);
+    assert_eq!(blocking_net::fetch(), 81);
+    // the async versions are still there
+    drop(Client::get_twice(&One));
+    drop(net::fetch());
+}
@@ -0,0 +1,25 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::time::Duration;
+
+#[remove_async_await::dual]
+async fn wait(duration: Duration) -> Duration {
+    #[remove_async_await::replace_with(std::thread::sleep(duration))]
+    async_io::Timer::after(duration).await;
+    duration
+}
+
+#[remove_async_await::dual]
+async fn statements() -> u32 {
+    #[remove_async_await::replace_with(let value = 1; let value = value + 1;)]
+    let value = async_channel::unbounded::<u32>().1.recv().await.unwrap();
+    #[remove_async_await::replace_with(value * 2)]
+    async_io::block_on(async { value })
+}
+
+#[test]
+fn replace_with() {
+    let duration = Duration::from_millis(1);
+    assert_eq!(wait(duration), duration);
+    assert_eq!(statements(), 4);
+}
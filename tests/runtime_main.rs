@@ -0,0 +1,19 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+#[tokio::main(flavor = "multi_thread", worker_threads = 2)]
+async fn run() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await]
+#[async_std::main]
+async fn run_async_std() -> Result<u32, ()> {
+    Ok(2)
+}
+
+#[test]
+fn runtime_main() {
+    assert_eq!(run(), 1);
+    assert_eq!(run_async_std(), Ok(2));
+}
@@ -0,0 +1,21 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await]
+#[tokio::test(flavor = "multi_thread")]
+async fn tokio_test() {
+    let value = get().await;
+    assert_eq!(value, 1);
+}
+
+#[remove_async_await::remove_async_await]
+#[async_std::test]
+async fn async_std_test() -> Result<(), String> {
+    let value = get().await;
+    assert_eq!(value, 1);
+    Ok(())
+}
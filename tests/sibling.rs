@@ -0,0 +1,42 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(sibling)]
+pub async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await(sibling)]
+pub async fn get_twice() -> u32 {
+    // `get()` isn't defined here, so it's called through the module
+    self::get().await + crate::get().await
+}
+
+pub struct Counter {
+    count: u32,
+}
+
+#[remove_async_await::remove_async_await(sibling = "_sync", map(get = get_blocking))]
+impl Counter {
+    pub const STEP: u32 = 1;
+
+    pub async fn increment(&mut self) -> u32 {
+        self.count += Self::STEP;
+        self.count
+    }
+
+    pub async fn increment_twice(&mut self) -> u32 {
+        self.increment().await;
+        self.increment().await + get().await
+    }
+}
+
+#[test]
+fn sibling() {
+    assert_eq!(get_blocking(), 1);
+    assert_eq!(get_twice_blocking(), 2);
+    let mut counter = Counter { count: 0 };
+    assert_eq!(counter.increment_twice_sync(), 3);
+    // the async versions are still there
+    drop(get_twice());
+    drop(counter.increment_twice());
+}
@@ -0,0 +1,34 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::{future::Future, pin::Pin};
+
+pub struct Client {
+    base: u32,
+}
+
+#[remove_async_await::remove_async_await(sibling)]
+impl Client {
+    pub fn new(base: u32) -> Self {
+        Client { base }
+    }
+
+    fn offset(&self) -> u32 {
+        self.base
+    }
+
+    pub async fn get(&self, value: u32) -> u32 {
+        value + self.offset()
+    }
+
+    pub fn get_later(&self, value: u32) -> Pin<Box<dyn Future<Output = u32> + '_>> {
+        Box::pin(async move { self.get(value).await * 2 })
+    }
+}
+
+#[test]
+fn sibling_impl() {
+    let client = Client::new(1);
+    assert_eq!(client.get_blocking(1), 2);
+    assert_eq!(client.get_later_blocking(1), 4);
+    assert_eq!(futures::executor::block_on(client.get_later(1)), 4);
+}
@@ -0,0 +1,70 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(sink = closure)]
+async fn forward<S>(mut sink: S, items: Vec<u32>) -> Result<(), String>
+where
+    S: futures::Sink<u32, Error = String> + Unpin,
+{
+    for item in items {
+        sink.send(item).await?;
+    }
+    sink.flush().await
+}
+
+#[remove_async_await::remove_async_await(sink = closure)]
+async fn send_one(sink: &mut impl futures::Sink<u32, Error = ()>, item: u32) -> Result<(), ()> {
+    sink.feed(item).await?;
+    sink.close().await
+}
+
+trait Writer<T> {
+    type Error;
+
+    fn send(&mut self, item: T) -> Result<(), Self::Error>;
+}
+
+impl Writer<u32> for Vec<u32> {
+    type Error = ();
+
+    fn send(&mut self, item: u32) -> Result<(), ()> {
+        self.push(item);
+        Ok(())
+    }
+}
+
+#[remove_async_await::remove_async_await(sink = Writer)]
+async fn write<S: futures::Sink<u32, Error = ()> + Unpin>(sink: &mut S) -> Result<(), S::Error> {
+    sink.send(1).await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn sink() {
+    let mut received = Vec::new();
+    forward(
+        |item| {
+            received.push(item);
+            Ok(())
+        },
+        vec![1, 2],
+    )
+    .await
+    .unwrap();
+    assert_eq!(received, vec![1, 2]);
+
+    let mut sent = None;
+    send_one(
+        &mut |item| {
+            sent = Some(item);
+            Ok(())
+        },
+        3,
+    )
+    .await
+    .unwrap();
+    assert_eq!(sent, Some(3));
+
+    let mut written = Vec::new();
+    write(&mut written).await.unwrap();
+    assert_eq!(written, vec![1]);
+}
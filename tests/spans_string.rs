@@ -0,0 +1,15 @@
+#[remove_async_await::remove_async_await_string]
+async fn lines() -> (u32, u32) {
+    // reparsing the input as a string would put both on the line of the attribute
+    let first = line!();
+    let second = async { line!() }.await;
+    (first, second)
+}
+
+#[remove_async_await::remove_async_await_string]
+#[test]
+async fn spans_string() {
+    let (first, second) = lines().await;
+    assert_eq!(first, 4);
+    assert_eq!(second, first + 1);
+}
@@ -0,0 +1,42 @@
+use std::sync::mpsc;
+
+// there's no `async` feature, so the block runs right away, or on a thread with `thread`
+fn outside(sender: mpsc::Sender<u32>) -> (u32, std::thread::JoinHandle<u32>) {
+    let value = 1;
+    // `return` only leaves the block, like in the spawned task
+    let called = remove_async_await::spawn_or_call!({
+        if value > 0 {
+            return value;
+        }
+        0
+    });
+    let spawned = remove_async_await::spawn_or_call!(thread, {
+        sender.send(2).unwrap();
+        2
+    });
+    (called, spawned)
+}
+
+#[remove_async_await::remove_async_await]
+async fn get() -> u32 {
+    3
+}
+
+#[remove_async_await::remove_async_await]
+async fn inside(sender: mpsc::Sender<u32>) -> u32 {
+    remove_async_await::spawn_or_call!(thread, feature = "full", {
+        sender.send(get().await).unwrap();
+    });
+    remove_async_await::spawn_or_call!(feature = "full", { get().await + 1 })
+}
+
+#[test]
+fn spawn_or_call() {
+    let (sender, receiver) = mpsc::channel();
+    let (called, spawned) = outside(sender.clone());
+    assert_eq!(called, 1);
+    assert_eq!(spawned.join().unwrap(), 2);
+    assert_eq!(receiver.recv().unwrap(), 2);
+    assert_eq!(inside(sender), 4);
+    assert_eq!(receiver.recv().unwrap(), 3);
+}
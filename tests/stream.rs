@@ -0,0 +1,111 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+fn numbers() -> impl futures::Stream<Item = u32> {
+    futures::stream::iter(vec![1, 2, 3])
+}
+
+#[remove_async_await::remove_async_await]
+async fn sum<S: futures::Stream<Item = u32> + Unpin>(mut stream: S) -> u32 {
+    let first = stream.next().await.unwrap_or_default();
+    stream.fold(first, |sum, n| async move { sum + n }).await
+}
+
+#[remove_async_await::remove_async_await]
+fn countdown(from: u32) -> impl futures::Stream<Item = u32> {
+    futures::stream::unfold(from, |n| async move {
+        if n == 0 {
+            None
+        } else {
+            Some((n, n - 1))
+        }
+    })
+}
+
+#[remove_async_await::remove_async_await]
+async fn doubled() -> Vec<u32> {
+    numbers()
+        .then(|n| async move { n * 2 })
+        .buffered(2)
+        .collect::<Vec<_>>()
+        .await
+}
+
+#[remove_async_await::remove_async_await]
+async fn parse(strings: Vec<&'static str>) -> Result<Vec<u32>, std::num::ParseIntError> {
+    let mut first = futures::stream::iter(strings.clone()).map(str::parse::<u32>);
+    first.try_next().await?;
+    futures::stream::iter(strings)
+        .map(str::parse::<u32>)
+        .try_collect()
+        .await
+}
+
+mod water {
+    /// Not a futures stream, so it isn't rewritten to `Iterator`.
+    pub trait Stream {
+        fn flow(&self) -> u32;
+    }
+
+    impl Stream for u32 {
+        fn flow(&self) -> u32 {
+            *self
+        }
+    }
+}
+
+#[remove_async_await::remove_async_await]
+async fn flow<S: water::Stream>(stream: S) -> u32 {
+    stream.flow()
+}
+
+/// Not a stream, so its `try_next` and `buffered` are left alone.
+struct Parser(Vec<u32>);
+
+impl Parser {
+    fn try_next(&mut self) -> Result<Option<u32>, ()> {
+        Ok(self.0.pop())
+    }
+
+    fn buffered(self, n: usize) -> Parser {
+        Parser(self.0.into_iter().take(n).collect())
+    }
+}
+
+#[remove_async_await::remove_async_await]
+async fn parser_next(parser: Parser) -> Result<Option<u32>, ()> {
+    let mut parser = parser.buffered(2);
+    parser.try_next()
+}
+
+#[remove_async_await::remove_async_await]
+async fn pending<S: futures::Stream<Item = Result<u32, ()>> + Unpin>(
+    mut stream: S,
+) -> Result<Option<u32>, ()> {
+    stream.try_next().await
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn stream() {
+    let total = sum(numbers()).await;
+    assert_eq!(total, 6);
+    let countdown = countdown(3).collect::<Vec<_>>().await;
+    assert_eq!(countdown, vec![3, 2, 1]);
+    let doubled = doubled().await;
+    assert_eq!(doubled, vec![2, 4, 6]);
+    let once = futures::stream::once(async { 1 })
+        .chain(futures::stream::empty())
+        .collect::<Vec<u32>>()
+        .await;
+    assert_eq!(once, vec![1]);
+    let parsed = parse(vec!["1", "2"]).await;
+    assert_eq!(parsed, Ok(vec![1, 2]));
+    assert!(parse(vec!["a"]).await.is_err());
+    assert_eq!(flow(5).await, 5);
+    assert_eq!(parser_next(Parser(vec![1, 2, 3])).await, Ok(Some(2)));
+    assert_eq!(
+        pending(futures::stream::iter(vec![Ok(1)])).await,
+        Ok(Some(1))
+    );
+}
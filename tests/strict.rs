@@ -0,0 +1,17 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(strict)]
+async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await(strict)]
+async fn get_twice() -> u32 {
+    println!("{}", get().await);
+    get().await * 2
+}
+
+#[test]
+fn strict() {
+    assert_eq!(get_twice(), 2);
+}
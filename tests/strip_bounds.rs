@@ -0,0 +1,35 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+use std::{cell::Cell, rc::Rc};
+
+#[remove_async_await::remove_async_await(strip_bounds)]
+async fn call<F, T: Send + Clone>(f: F, value: T) -> T
+where
+    F: Fn(T) -> T + Send + Sync + 'static,
+    T: 'static,
+{
+    f(value)
+}
+
+#[remove_async_await::remove_async_await(strip_bounds(Send))]
+async fn boxed(value: u32) -> Box<dyn Fn() -> u32 + Send> {
+    Box::new(move || value)
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn strip_bounds() {
+    // neither `Rc` nor a closure capturing a `Cell` reference are `Send`
+    let counter = Cell::new(0);
+    let result = call(
+        |value: Rc<u32>| {
+            counter.set(counter.get() + 1);
+            value
+        },
+        Rc::new(1),
+    )
+    .await;
+    assert_eq!(*result, 1);
+    assert_eq!(counter.get(), 1);
+    assert_eq!(boxed(2).await(), 2);
+}
@@ -0,0 +1,36 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(strip_unpin)]
+async fn first<T: Unpin + Copy>(values: &[T]) -> T {
+    values[0]
+}
+
+#[remove_async_await::remove_async_await(strip_unpin)]
+trait Reset: Unpin {
+    async fn reset(&mut self) -> u32
+    where
+        Self: Unpin;
+}
+
+struct Counter(u32);
+
+#[remove_async_await::remove_async_await(strip_unpin)]
+impl Reset for Counter {
+    async fn reset(&mut self) -> u32
+    where
+        Self: Unpin,
+    {
+        std::mem::take(&mut self.0)
+    }
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn strip_unpin() {
+    let first = first(&[1, 2]).await;
+    assert_eq!(first, 1);
+    let mut counter = Counter(3);
+    let reset = counter.reset().await;
+    assert_eq!(reset, 3);
+    assert_eq!(counter.0, 0);
+}
@@ -0,0 +1,30 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+fn blocking_get(value: u32) -> u32 {
+    value + 1
+}
+
+#[remove_async_await::remove_async_await(sync_body = { blocking_get(value) })]
+async fn get(value: u32) -> u32 {
+    async_client::get(value).await
+}
+
+#[remove_async_await::remove_async_await]
+trait Client {
+    async fn get(&self) -> u32;
+}
+
+struct Local;
+
+impl Client for Local {
+    #[remove_async_await::remove_async_await(sync_body = { blocking_get(1) })]
+    async fn get(&self) -> u32 {
+        async_client::get(1).await
+    }
+}
+
+#[test]
+fn sync_body() {
+    assert_eq!(get(1), 2);
+    assert_eq!(Local.get(), 2);
+}
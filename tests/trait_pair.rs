@@ -0,0 +1,64 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(trait_pair)]
+trait Store {
+    const NAME: &'static str;
+
+    async fn get(&self, key: u32) -> u32;
+
+    async fn get_twice(&self, key: u32) -> u32 {
+        self.get(key).await * 2
+    }
+}
+
+struct Blocking;
+
+impl Store for Blocking {
+    const NAME: &'static str = "blocking";
+
+    fn get(&self, key: u32) -> u32 {
+        key
+    }
+}
+
+impl StoreAsync for Blocking {
+    const NAME: &'static str = "async";
+
+    async fn get(&self, key: u32) -> u32 {
+        key
+    }
+}
+
+#[remove_async_await::remove_async_await(trait_pair(sync_name = "Blocking{name}", async_name = "{name}", bridge = futures::executor::block_on))]
+trait Cache<K> {
+    type Value;
+
+    async fn get(&self, key: K) -> Option<Self::Value>;
+
+    fn contains(&self, key: K) -> bool;
+}
+
+struct Async;
+
+impl Cache<u32> for Async {
+    type Value = u32;
+
+    async fn get(&self, key: u32) -> Option<u32> {
+        Cache::contains(self, key).then_some(key)
+    }
+
+    fn contains(&self, key: u32) -> bool {
+        key < 10
+    }
+}
+
+#[test]
+fn trait_pair() {
+    assert_eq!(<Blocking as Store>::NAME, "blocking");
+    assert_eq!(Store::get_twice(&Blocking, 2), 4);
+    assert_eq!(<Blocking as StoreAsync>::NAME, "async");
+    drop(StoreAsync::get_twice(&Blocking, 2));
+    // `Async` only implements the async trait, and gets the blocking one from the bridge
+    assert_eq!(BlockingCache::get(&Async, 1), Some(1));
+    assert!(!BlockingCache::contains(&Async, 10));
+}
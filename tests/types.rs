@@ -0,0 +1,42 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+mod net {
+    pub struct Conn(pub u32);
+
+    impl Conn {
+        pub fn id(&self) -> u32 {
+            self.0
+        }
+    }
+}
+
+struct Wrapper<T>(T);
+
+#[remove_async_await::remove_async_await(types(net::AsyncConn = net::Conn, AsyncWrapper = Wrapper))]
+struct Pool {
+    conns: Vec<net::AsyncConn>,
+}
+
+#[remove_async_await::remove_async_await(types(net::AsyncConn = net::Conn, AsyncWrapper = Wrapper))]
+async fn first(pool: &Pool) -> AsyncWrapper<&net::AsyncConn> {
+    Wrapper(&pool.conns[0])
+}
+
+#[remove_async_await::remove_async_await(types(net::AsyncConn = net::Conn))]
+async fn id<C>(conn: C) -> u32
+where
+    C: std::borrow::Borrow<net::AsyncConn>,
+{
+    conn.borrow().id()
+}
+
+#[remove_async_await::remove_async_await]
+#[test]
+async fn types() {
+    let pool = Pool {
+        conns: vec![net::Conn(1)],
+    };
+    let conn = first(&pool).await.0;
+    let id = id(conn).await;
+    assert_eq!(id, 1);
+}
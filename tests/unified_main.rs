@@ -0,0 +1,34 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+mod bin {
+    #[remove_async_await::remove_async_await]
+    async fn get() -> u32 {
+        1
+    }
+
+    #[remove_async_await::main]
+    pub async fn main() {
+        let value = get().await;
+        assert_eq!(value, 1);
+    }
+}
+
+mod bin_with_args {
+    #[remove_async_await::remove_async_await]
+    async fn get() -> u32 {
+        1
+    }
+
+    #[remove_async_await::main(feature = "async", runtime = tokio::main(flavor = "current_thread"))]
+    pub async fn main() -> Result<(), String> {
+        let value = get().await;
+        assert_eq!(value, 1);
+        Ok(())
+    }
+}
+
+#[test]
+fn unified_main() {
+    bin::main();
+    bin_with_args::main().unwrap();
+}
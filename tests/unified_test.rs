@@ -0,0 +1,26 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::test]
+async fn unified_test() {
+    let value = get().await;
+    assert_eq!(value, 1);
+}
+
+#[remove_async_await::test(
+    feature = "async",
+    runtime = tokio::test(flavor = "multi_thread"),
+    map(get = get_twice)
+)]
+async fn unified_test_with_args() {
+    let value = get().await;
+    assert_eq!(value, 2);
+}
+
+fn get_twice() -> u32 {
+    get() * 2
+}
@@ -0,0 +1,30 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(unless_feature = "async")]
+async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await(unless_feature = "async")]
+trait Get {
+    async fn get(&self) -> u32;
+}
+
+struct One;
+
+#[remove_async_await::remove_async_await(unless_feature = "async")]
+impl Get for One {
+    async fn get(&self) -> u32 {
+        get().await
+    }
+}
+
+#[remove_async_await::remove_async_await(unless_feature = "async")]
+async fn get_twice(value: &impl Get) -> u32 {
+    value.get().await + get().await
+}
+
+#[test]
+fn unless_feature() {
+    assert_eq!(get_twice(&One), 2);
+}
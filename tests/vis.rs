@@ -0,0 +1,38 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+mod client {
+    #[remove_async_await::remove_async_await(sibling, vis = "pub(crate)")]
+    pub async fn get() -> u32 {
+        1
+    }
+
+    pub struct Client;
+
+    #[remove_async_await::remove_async_await(sibling, vis = "pub(super)")]
+    impl Client {
+        async fn get_twice(&self) -> u32 {
+            self::get().await * 2
+        }
+    }
+
+    #[remove_async_await::mirror(vis = "pub(crate)")]
+    mod net {
+        pub async fn port() -> u16 {
+            80
+        }
+    }
+
+    pub async fn use_private() -> u16 {
+        Client.get_twice().await;
+        net::port().await
+    }
+}
+
+#[test]
+fn vis() {
+    let _ = client::get;
+    let _ = client::use_private;
+    assert_eq!(client::get_blocking(), 1);
+    assert_eq!(client::Client.get_twice_blocking(), 2);
+    assert_eq!(client::blocking::port(), 80);
+}
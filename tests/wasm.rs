@@ -0,0 +1,32 @@
+// run with `cargo test --no-default-features --features wasm --test wasm`
+#![cfg(all(feature = "wasm", not(feature = "full")))]
+
+#[remove_async_await::remove_async_await]
+async fn get(value: u32) -> u32 {
+    value
+}
+
+// rewritten like with `full`, which the lightweight mode can't do
+#[remove_async_await::remove_async_await]
+fn add(a: u32, b: u32) -> impl std::future::Future<Output = u32> {
+    async move { get(a).await + get(b).await }
+}
+
+#[remove_async_await::remove_async_await(map(tokio::time::sleep = std::thread::sleep))]
+async fn nap() -> &'static str {
+    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+    r#"done"#
+}
+
+// recent arguments, which a stale blob doesn't know about
+#[remove_async_await::remove_async_await(warn_leaks, hoist_awaits)]
+async fn twice(value: u32) -> u32 {
+    get(value).await + get(value).await
+}
+
+#[remove_async_await::test]
+async fn wasm() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(nap(), "done");
+    assert_eq!(twice(2), 4);
+}
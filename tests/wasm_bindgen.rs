@@ -0,0 +1,31 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+mod native {
+    pub struct Promise(pub u32);
+
+    pub fn resolve(promise: Promise) -> Result<u32, String> {
+        Ok(promise.0)
+    }
+}
+
+use native::Promise;
+
+#[remove_async_await::remove_async_await(wasm_bindgen(js_future = native::resolve))]
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub async fn fetch(value: u32) -> Result<u32, String> {
+    let promise = Promise(value);
+    let resolved = wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(resolved + 1)
+}
+
+#[remove_async_await::remove_async_await(wasm_bindgen)]
+#[wasm_bindgen(start)]
+pub async fn start() -> u32 {
+    let value = fetch(1).await;
+    value.unwrap()
+}
+
+#[test]
+fn wasm_bindgen() {
+    assert_eq!(start(), 2);
+}
@@ -0,0 +1,20 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await(when(all(
+    feature = "async",
+    not(target_arch = "wasm32")
+)))]
+async fn get() -> u32 {
+    1
+}
+
+#[remove_async_await::remove_async_await(when(target_arch = "wasm32"))]
+async fn get_twice() -> u32 {
+    get().await * 2
+}
+
+#[remove_async_await::test(when(any(feature = "async", target_arch = "wasm32")))]
+async fn when_test() {
+    assert_eq!(get().await, 1);
+    assert_eq!(get_twice().await, 2);
+}
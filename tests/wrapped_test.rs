@@ -0,0 +1,19 @@
+#![cfg(any(feature = "full", feature = "wasm"))]
+
+#[remove_async_await::remove_async_await]
+async fn get() -> u32 {
+    1
+}
+
+// `test_log::test` without arguments uses `#[test]`
+#[remove_async_await::remove_async_await]
+#[test_log::test(tokio::test)]
+async fn wrapped_test() {
+    assert_eq!(get().await, 1);
+}
+
+#[remove_async_await::remove_async_await]
+#[test_log::test(tokio::test(flavor = "multi_thread"))]
+async fn wrapped_test_with_args() {
+    assert_eq!(get().await, 1);
+}
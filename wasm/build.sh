@@ -0,0 +1,12 @@
+#!/bin/sh
+# Rebuilds `src/remove_async_await.wasm` in the main crate from the core crate. The toolchain and dependencies are
+# pinned and the paths remapped, so the blob comes out the same on every machine and CI can check that it's up to date.
+set -eu
+cd "$(dirname "$0")"
+toolchain="${WASM_TOOLCHAIN:-nightly-2026-05-19}"
+root="$(cd .. && pwd)"
+sysroot="$(rustc "+$toolchain" --print sysroot)"
+# watt only reads the original WebAssembly features, so std is rebuilt for them too
+RUSTFLAGS="-C target-cpu=mvp --remap-path-prefix=$root=/remove-async-await --remap-path-prefix=${CARGO_HOME:-$HOME/.cargo}=/cargo --remap-path-prefix=$sysroot=/rustc" \
+    cargo "+$toolchain" build --locked --release --target wasm32-unknown-unknown -Zbuild-std=std,panic_abort "$@"
+cp target/wasm32-unknown-unknown/release/remove_async_await_wasm.wasm ../src/remove_async_await.wasm
@@ -0,0 +1,36 @@
+//! The transform behind `remove-async-await`, compiled to WebAssembly so its `wasm` feature can run it through
+//! [watt](https://docs.rs/watt) instead of building syn. Every export here is one of the core crate's `expand`
+//! functions.
+
+mod watt;
+
+use proc_macro2::TokenStream;
+
+fn attribute(args: u32, input: u32, expand: fn(TokenStream, TokenStream) -> TokenStream) -> u32 {
+    watt::store(expand(watt::take(args), watt::take(input)))
+}
+
+#[no_mangle]
+pub extern "C" fn expand(args: u32, input: u32) -> u32 {
+    attribute(args, input, remove_async_await_core::expand)
+}
+
+#[no_mangle]
+pub extern "C" fn expand_test(args: u32, input: u32) -> u32 {
+    attribute(args, input, remove_async_await_core::expand_test)
+}
+
+#[no_mangle]
+pub extern "C" fn expand_main(args: u32, input: u32) -> u32 {
+    attribute(args, input, remove_async_await_core::expand_main)
+}
+
+#[no_mangle]
+pub extern "C" fn expand_mirror(args: u32, input: u32) -> u32 {
+    attribute(args, input, remove_async_await_core::expand_mirror)
+}
+
+#[no_mangle]
+pub extern "C" fn expand_dual(args: u32, input: u32) -> u32 {
+    attribute(args, input, remove_async_await_core::expand_dual)
+}
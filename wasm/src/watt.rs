@@ -0,0 +1,198 @@
+//! The guest side of watt's ABI: the host hands over token streams as handles, which are serialized into bytes here
+//! and read into `proc_macro2` tokens, and the output goes back the same way. The host's spans can't be carried by
+//! `proc_macro2`'s own tokens, so everything in the output is spanned at the call site.
+
+use std::{cell::RefCell, str::FromStr, sync::Once};
+
+use proc_macro2::{Delimiter, Group, Punct, Spacing, TokenStream, TokenTree};
+
+#[link(wasm_import_module = "watt-0.5")]
+extern "C" {
+    fn token_stream_serialize(stream: u32) -> u32;
+    fn token_stream_deserialize(ptr: *const u8, len: usize) -> u32;
+    fn literal_to_string(literal: u32) -> u32;
+    fn string_new(ptr: *const u8, len: usize) -> u32;
+    fn string_len(string: u32) -> usize;
+    fn string_read(string: u32, ptr: *mut u8);
+    fn bytes_len(bytes: u32) -> usize;
+    fn bytes_read(bytes: u32, ptr: *mut u8);
+    fn print_panic(string: u32);
+}
+
+/// A span the host reads as its call site.
+const CALL_SITE: u32 = u32::MAX;
+
+thread_local! {
+    /// The token streams the exported functions take and return, by handle.
+    static STREAMS: RefCell<Vec<TokenStream>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Takes the stream behind a handle returned by `raw_to_token_stream`.
+pub(crate) fn take(handle: u32) -> TokenStream {
+    STREAMS.with(|streams| std::mem::take(&mut streams.borrow_mut()[handle as usize]))
+}
+
+/// Stores a stream for `token_stream_into_raw`, returning its handle.
+pub(crate) fn store(stream: TokenStream) -> u32 {
+    STREAMS.with(|streams| {
+        let mut streams = streams.borrow_mut();
+        streams.push(stream);
+        (streams.len() - 1) as u32
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn raw_to_token_stream(raw: u32) -> u32 {
+    // panics abort without a message in wasm, so they go through the host
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        std::panic::set_hook(Box::new(|info| {
+            let message = info.to_string();
+            unsafe { print_panic(string_new(message.as_ptr(), message.len())) };
+        }))
+    });
+    let bytes = unsafe {
+        let bytes = token_stream_serialize(raw);
+        let mut buf = vec![0; bytes_len(bytes)];
+        bytes_read(bytes, buf.as_mut_ptr());
+        buf
+    };
+    store(Decoder { bytes: &bytes }.stream())
+}
+
+#[no_mangle]
+pub extern "C" fn token_stream_into_raw(handle: u32) -> u32 {
+    let mut bytes = Vec::new();
+    encode(take(handle), &mut bytes);
+    unsafe { token_stream_deserialize(bytes.as_ptr(), bytes.len()) }
+}
+
+/// Reads watt's serialized tokens.
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl Decoder<'_> {
+    fn byte(&mut self) -> u8 {
+        let byte = self.bytes[0];
+        self.bytes = &self.bytes[1..];
+        byte
+    }
+
+    fn u32(&mut self) -> u32 {
+        let (int, rest) = self.bytes.split_at(4);
+        self.bytes = rest;
+        u32::from_le_bytes(int.try_into().unwrap())
+    }
+
+    fn str(&mut self) -> &str {
+        let len = self.u32() as usize;
+        let (string, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        std::str::from_utf8(string).unwrap()
+    }
+
+    fn stream(&mut self) -> TokenStream {
+        let mut tokens = Vec::new();
+        loop {
+            match self.byte() {
+                0 => break,
+                1 => {
+                    let delimiter = match self.byte() {
+                        0 => Delimiter::Parenthesis,
+                        1 => Delimiter::Brace,
+                        2 => Delimiter::Bracket,
+                        _ => Delimiter::None,
+                    };
+                    // the span of the group and its delimiters
+                    self.u32();
+                    self.u32();
+                    self.u32();
+                    tokens.push(TokenTree::Group(Group::new(delimiter, self.stream())));
+                }
+                2 => {
+                    self.u32();
+                    tokens.extend(parse(self.str()));
+                }
+                3 => {
+                    let ch = char::from_u32(self.u32()).unwrap();
+                    let spacing = match self.byte() {
+                        0 => Spacing::Alone,
+                        _ => Spacing::Joint,
+                    };
+                    self.u32();
+                    tokens.push(TokenTree::Punct(Punct::new(ch, spacing)));
+                }
+                _ => {
+                    self.u32();
+                    let literal = if self.byte() == 0 {
+                        self.str().to_owned()
+                    } else {
+                        read_string(unsafe { literal_to_string(self.u32()) })
+                    };
+                    // negative numbers are one literal for the host but two tokens here
+                    tokens.extend(parse(&literal));
+                }
+            }
+        }
+        tokens.into_iter().collect()
+    }
+}
+
+fn read_string(string: u32) -> String {
+    unsafe {
+        let mut buf = vec![0; string_len(string)];
+        string_read(string, buf.as_mut_ptr());
+        String::from_utf8(buf).unwrap()
+    }
+}
+
+/// Parses an identifier or literal, which handles raw identifiers and every kind of literal.
+fn parse(token: &str) -> TokenStream {
+    TokenStream::from_str(token).unwrap()
+}
+
+/// Writes tokens the way the host reads them.
+fn encode(stream: TokenStream, bytes: &mut Vec<u8>) {
+    fn str(string: &str, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&(string.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(string.as_bytes());
+    }
+
+    for token in stream {
+        match token {
+            TokenTree::Group(group) => {
+                bytes.push(1);
+                bytes.push(match group.delimiter() {
+                    Delimiter::Parenthesis => 0,
+                    Delimiter::Brace => 1,
+                    Delimiter::Bracket => 2,
+                    Delimiter::None => 3,
+                });
+                bytes.extend_from_slice(&CALL_SITE.to_le_bytes());
+                encode(group.stream(), bytes);
+            }
+            TokenTree::Ident(ident) => {
+                bytes.push(2);
+                bytes.extend_from_slice(&CALL_SITE.to_le_bytes());
+                str(&ident.to_string(), bytes);
+            }
+            TokenTree::Punct(punct) => {
+                bytes.push(3);
+                bytes.extend_from_slice(&(punct.as_char() as u32).to_le_bytes());
+                bytes.push(match punct.spacing() {
+                    Spacing::Alone => 0,
+                    Spacing::Joint => 1,
+                });
+                bytes.extend_from_slice(&CALL_SITE.to_le_bytes());
+            }
+            TokenTree::Literal(literal) => {
+                bytes.push(4);
+                bytes.extend_from_slice(&CALL_SITE.to_le_bytes());
+                bytes.push(0);
+                str(&literal.to_string(), bytes);
+            }
+        }
+    }
+    bytes.push(0);
+}